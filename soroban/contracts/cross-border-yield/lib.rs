@@ -3,10 +3,18 @@
 
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
+    contract, contractclient, contractimpl, contracttype, symbol_short,
     Address, Env, Map, String, Symbol, Vec, log
 };
 
+// ===== EXTERNAL CONTRACTS =====
+
+/// Subset of the SaveCoin token interface used for fee discounts
+#[contractclient(name = "SaveCoinClient")]
+pub trait SaveCoinInterface {
+    fn use_discount(env: Env, consumer: Address, user: Address, max_amount: i128) -> i128;
+}
+
 // ===== YIELD POOL STRUCTURES =====
 
 #[contracttype]
@@ -79,6 +87,17 @@ pub enum TransactionStatus {
     Cancelled = 5,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantReport {
+    pub pool_id: u32,
+    pub pool_total_deposited: i128,
+    pub positions_total: i128,     // Sum of principals across all positions in the pool
+    pub total_value_locked: i128,
+    pub pools_total: i128,         // Sum of total_deposited across all pools
+    pub holds: bool,
+}
+
 // ===== STORAGE KEYS =====
 
 #[contracttype]
@@ -96,6 +115,7 @@ pub enum DataKey {
     Admin,
     TotalValueLocked,
     GlobalYieldStats,
+    SaveCoinToken,                // SaveCoin contract holding fee discount credits
 }
 
 // ===== ERRORS =====
@@ -283,6 +303,8 @@ impl CrossBorderYieldContract {
             (user, amount, lock_until)
         );
         
+        debug_assert!(Self::check_invariants(&env, pool_id).holds);
+        
         log!(&env, "User {} deposited {} to pool {}", user, amount, pool_id);
         
         Ok(())
@@ -309,31 +331,49 @@ impl CrossBorderYieldContract {
             .get(&DataKey::YieldPool(pool_id))
             .ok_or(YieldError::PoolNotFound)?;
         
+        // Shares are computed against the pre-distribution total so that
+        // compounding earlier positions doesn't dilute later ones
+        let total_before = pool.total_deposited;
+        let mut compounded: i128 = 0;
+        
         // Distribute yield proportionally to each participant
-        for participant in pool.participants.iter() {
-            let mut user_positions: Vec<YieldPosition> = env.storage().persistent()
-                .get(&DataKey::UserPositions(participant.clone()))
-                .unwrap_or(Vec::new(&env));
-            
-            for position in user_positions.iter_mut() {
-                if position.pool_id == pool_id {
-                    let user_share = (position.principal * total_yield) / pool.total_deposited;
-                    position.yield_earned += user_share;
-                    
-                    // Auto-compound if enabled
-                    if position.auto_compound {
-                        position.principal += user_share;
-                        pool.total_deposited += user_share;
+        if total_before > 0 {
+            for participant in pool.participants.iter() {
+                let mut user_positions: Vec<YieldPosition> = env.storage().persistent()
+                    .get(&DataKey::UserPositions(participant.clone()))
+                    .unwrap_or(Vec::new(&env));
+                
+                for i in 0..user_positions.len() {
+                    let mut position = user_positions.get(i).unwrap();
+                    if position.pool_id == pool_id {
+                        let user_share = (position.principal * total_yield) / total_before;
+                        position.yield_earned += user_share;
+                        
+                        // Auto-compound if enabled
+                        if position.auto_compound {
+                            position.principal += user_share;
+                            compounded += user_share;
+                        }
+                        user_positions.set(i, position);
                     }
                 }
+                
+                env.storage().persistent().set(&DataKey::UserPositions(participant.clone()), &user_positions);
             }
-            
-            env.storage().persistent().set(&DataKey::UserPositions(participant.clone()), &user_positions);
         }
         
+        pool.total_deposited += compounded;
         pool.total_yield_earned += total_yield;
         env.storage().persistent().set(&DataKey::YieldPool(pool_id), &pool);
         
+        // Compounded yield is now locked principal, so it counts toward TVL
+        let tvl: i128 = env.storage().instance()
+            .get(&DataKey::TotalValueLocked)
+            .unwrap_or(0);
+        env.storage().instance().set(&DataKey::TotalValueLocked, &(tvl + compounded));
+        
+        debug_assert!(Self::check_invariants(&env, pool_id).holds);
+        
         env.events().publish(
             (symbol_short!("yield_dist"), pool_id),
             total_yield
@@ -372,6 +412,10 @@ impl CrossBorderYieldContract {
         let corridor_premium = amount * 25 / 10000; // 0.25% corridor premium
         let total_fees = base_fee + corridor_premium;
         
+        // Redeemed SaveCoin discount credits reduce the fee
+        let discount = Self::apply_fee_discount(&env, &sender, total_fees);
+        let total_fees = total_fees - discount;
+        
         let tx_id: u32 = env.storage().instance()
             .get(&DataKey::NextTransactionId)
             .unwrap_or(1);
@@ -437,6 +481,32 @@ impl CrossBorderYieldContract {
         Ok(())
     }
     
+    /// Set the SaveCoin token used for fee discounts (admin only)
+    pub fn set_savecoin_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<(), YieldError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(YieldError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(YieldError::NotAuthorized);
+        }
+        
+        env.storage().instance().set(&DataKey::SaveCoinToken, &token);
+        
+        env.events().publish(
+            (symbol_short!("savecoin"), admin),
+            token
+        );
+        
+        Ok(())
+    }
+    
     // ===== ARBITRAGE AND YIELD OPTIMIZATION =====
     
     /// Execute arbitrage opportunity across corridors
@@ -517,6 +587,15 @@ impl CrossBorderYieldContract {
             .unwrap_or(Vec::new(&env))
     }
     
+    /// Verify accounting invariants for a pool and the global TVL (for monitors)
+    pub fn verify_invariants(env: Env, pool_id: u32) -> Result<InvariantReport, YieldError> {
+        if !env.storage().persistent().has(&DataKey::YieldPool(pool_id)) {
+            return Err(YieldError::PoolNotFound);
+        }
+        
+        Ok(Self::check_invariants(&env, pool_id))
+    }
+    
     /// Calculate projected yield for a position
     pub fn calculate_projected_yield(
         env: Env,
@@ -537,6 +616,69 @@ impl CrossBorderYieldContract {
     
     // ===== HELPER FUNCTIONS =====
     
+    /// Spend the sender's SaveCoin discount credit against a fee, returning the discount
+    fn apply_fee_discount(env: &Env, sender: &Address, fee: i128) -> i128 {
+        let token: Option<Address> = env.storage().instance()
+            .get(&DataKey::SaveCoinToken);
+        
+        match token {
+            Some(token) if fee > 0 => SaveCoinClient::new(env, &token)
+                .use_discount(&env.current_contract_address(), sender, &fee),
+            _ => 0,
+        }
+    }
+    
+    /// Recompute pool and global totals from positions and compare them to stored totals
+    fn check_invariants(env: &Env, pool_id: u32) -> InvariantReport {
+        let pool: Option<YieldPool> = env.storage().persistent()
+            .get(&DataKey::YieldPool(pool_id));
+        
+        let mut pool_total_deposited: i128 = 0;
+        let mut positions_total: i128 = 0;
+        
+        if let Some(pool) = pool {
+            pool_total_deposited = pool.total_deposited;
+            
+            for participant in pool.participants.iter() {
+                let user_positions: Vec<YieldPosition> = env.storage().persistent()
+                    .get(&DataKey::UserPositions(participant))
+                    .unwrap_or(Vec::new(env));
+                
+                for position in user_positions.iter() {
+                    if position.pool_id == pool_id {
+                        positions_total += position.principal;
+                    }
+                }
+            }
+        }
+        
+        // Sum every pool ever created
+        let next_pool_id: u32 = env.storage().instance()
+            .get(&DataKey::NextPoolId)
+            .unwrap_or(1);
+        let mut pools_total: i128 = 0;
+        for id in 1..next_pool_id {
+            let other: Option<YieldPool> = env.storage().persistent()
+                .get(&DataKey::YieldPool(id));
+            if let Some(other) = other {
+                pools_total += other.total_deposited;
+            }
+        }
+        
+        let total_value_locked: i128 = env.storage().instance()
+            .get(&DataKey::TotalValueLocked)
+            .unwrap_or(0);
+        
+        InvariantReport {
+            pool_id,
+            pool_total_deposited,
+            positions_total,
+            total_value_locked,
+            pools_total,
+            holds: positions_total == pool_total_deposited && pools_total == total_value_locked,
+        }
+    }
+    
     fn get_currency_country(currency: &str) -> &str {
         match currency {
             "USD" | "USDC" => "US",
@@ -2,9 +2,10 @@
 // StellarSave SaveCoin Reward Token Contract
 
 #![no_std]
+#![allow(clippy::inconsistent_digit_grouping, clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
-    Address, Env, String, Symbol, Map, log
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Map, Val, Vec, log
 };
 
 // ===== TOKEN METADATA =====
@@ -18,16 +19,118 @@ pub struct TokenMetadata {
     pub total_supply: i128,
 }
 
-// ===== REWARD TYPES =====
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenUris {
+    pub icon_uri: Option<String>,  // Wallet display icon
+    pub home_uri: Option<String>,  // Project home page
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingMaxSupply {
+    pub new_max_supply: i128,
+    pub effective_at: u64,
+}
+
+// Delay before a proposed max supply increase can be applied (7 days)
+const MAX_SUPPLY_TIMELOCK: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterBudget {
+    pub amount_per_epoch: i128,
+    pub epoch_length: u64,         // Epoch length in seconds
+    pub epoch_start: u64,
+    pub minted_in_epoch: i128,
+}
+
+// ===== SNAPSHOTS =====
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotInfo {
+    pub id: u32,
+    pub ledger: u32,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub snapshot_id: u32,  // First snapshot this value applies to
+    pub value: i128,       // Value as it stood when that snapshot was taken
+}
+
+// ===== GOVERNANCE =====
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfig {
+    pub voting_period: u64,         // Voting window in seconds
+    pub quorum_bps: u32,            // Share of snapshot supply that must vote (basis points)
+    pub approval_threshold_bps: u32, // Share of for/(for+against) needed to pass (basis points)
+    pub proposal_threshold: i128,   // Minimum voting power to create a proposal
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteType {
+    Against = 0,
+    For = 1,
+    Abstain = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalState {
+    Active = 0,
+    Defeated = 1,
+    Succeeded = 2,
+    Executed = 3,
+    Canceled = 4,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub description: String,
+    pub target: Address,           // StellarSave contract to call on execution
+    pub function: Symbol,          // Admin function to invoke on the target; the pair must be allowlisted
+    pub args: Vec<Val>,
+    pub snapshot_id: u32,          // Voting weight is read at this snapshot
+    pub start_time: u64,
+    pub end_time: u64,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub votes_abstain: i128,
+    pub executed: bool,
+    pub canceled: bool,
+}
+
+// Largest page returned by paginated queries
+const MAX_PAGE_SIZE: u32 = 50;
+
+// ===== REWARD TYPES =====
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum RewardType {
     WeeklyContribution = 1,
     MilestoneReached = 2,
     ChallengeCompleted = 3,
     StreakBonus = 4,
     ReferralBonus = 5,
+    SocialCheer = 6,
 }
 
 #[contracttype]
@@ -42,6 +145,7 @@ pub struct RewardRecord {
 }
 
 // ===== STORAGE KEYS =====
+// Split across enums to keep each under the contracttype variant limit
 
 #[contracttype]
 pub enum DataKey {
@@ -49,23 +153,120 @@ pub enum DataKey {
     Balance(Address),
     Allowance(Address, Address), // Owner, Spender
     Metadata,
+    MetadataUris,
     Admin,
+    PendingAdmin,
     
     // Reward system keys
     RewardConfig,
-    RewardHistory(Address), // User's reward history
+    RewardRecord(Address, u32), // User, Index -> RewardRecord
+    RewardCount(Address),       // Number of reward records for a user
     TotalRewards,
     MinterContracts, // Vec<Address> of authorized minter contracts
     
     // Statistics
     RewardStats(RewardType), // Total distributed per reward type
     UserRewardStats(Address), // User's total rewards by type
+    
+    // Supply cap
+    MaxSupply,
+    PendingMaxSupply,
+    
+    // Emission limits
+    MinterBudget(Address),
+    MintReceipt(Address, BytesN<32>), // Minter, Dedupe key -> already rewarded
+    
+    // Claimable rewards
+    PendingRewards(Address), // Accrued but not yet minted
+    
+    // Snapshots
+    CurrentSnapshotId,
+    Snapshot(u32),
+    BalanceCheckpoints(Address), // Vec<Checkpoint>, ordered by snapshot id
+    SupplyCheckpoints,           // Vec<Checkpoint>, ordered by snapshot id
+    
+    // Extensions
+    TransferHook,
+    
+    // Staking
+    Staked(Address),             // Portion of the balance locked as stake
 }
 
-// ===== ERRORS =====
+#[contracttype]
+pub enum GovernanceKey {
+    // Governance
+    GovernanceConfig,
+    NextProposalId,
+    Proposal(u32),
+    Vote(u32, Address),          // Proposal ID, Voter -> VoteType
+    Delegate(Address),           // Delegator -> Delegatee (absent means self)
+    Votes(Address),              // Voting power currently delegated to an address
+    VoteCheckpoints(Address),    // Vec<Checkpoint>, ordered by snapshot id
+    ProposalTarget(Address, Symbol), // Target, Function -> callable by proposals
+}
 
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ComplianceKey {
+    // Compliance
+    Freezers,                    // Vec<Address> of authorized freezers
+    Frozen(Address),
+    RewardDenied(Address),       // Flagged for reward farming; no new rewards
+    ClawbackHistory(Address),    // Vec<ClawbackRecord>
+}
+
+#[contracttype]
+pub enum RewardKey {
+    // Loyalty
+    LifetimeRewards(Address),    // Total rewards ever earned by a user
+    TierThresholds,
+    Leaderboard,                 // Vec<LeaderboardEntry>, highest first
+    
+    // Tokenomics
+    EmissionSchedule,
+    EmissionCap,
+    NextCampaignId,
+    Campaign(u32),
+    LiveCampaigns,               // Vec<u32> of campaigns not yet ended
+    
+    // Soulbound badges (never transferable)
+    Badge(u32),                  // Badge ID -> BadgeInfo
+    BadgeBalance(Address, u32),  // User, Badge ID -> count
+    BadgeAwards(Address),        // Vec<BadgeAward>
+    
+    // Referrals
+    Referrer(Address),           // Referee -> Referrer
+    ReferralPaid(Address),       // Referee whose referrer has been rewarded
+    ReferralCount(Address),      // Referrer -> number of referees
+    
+    // Raffles
+    NextRaffleId,
+    Raffle(u32),                 // Raffle ID -> Raffle
+    RaffleEntry(u32, u32),       // Raffle ID, Index -> RaffleEntry, in ticket order
+    
+    // Airdrops
+    Airdrop,
+    AirdropClaimed(u32, Address), // Round, User -> claimed
+}
+
+#[contracttype]
+pub enum SinkKey {
+    // Token sinks
+    DiscountCredit(Address),     // Fee discount credit from redeemed SaveCoin
+    DiscountRateBps,             // Credit granted per SaveCoin redeemed (basis points)
+    DiscountConsumers,           // Vec<Address> of contracts allowed to spend credits
+    RedemptionPool(Address),     // Payout asset -> RedemptionPool
+    
+    // Merchant spending
+    Merchant(Address),           // Merchant -> Merchant
+    NextReceiptId,
+    SpendReceipt(u32),           // Receipt ID -> SpendReceipt
+}
+
+// ===== ERRORS =====
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
 pub enum TokenError {
     NotAuthorized = 1,
     InsufficientBalance = 2,
@@ -77,22 +278,266 @@ pub enum TokenError {
     NotMinter = 8,
     InvalidRewardType = 9,
     RewardConfigNotSet = 10,
+    InvalidExpiration = 11,
+    MaxSupplyExceeded = 12,
+    TimelockNotElapsed = 13,
+    NoPendingChange = 14,
+    MinterBudgetExceeded = 15,
+    SnapshotNotFound = 16,
+    GovernanceNotConfigured = 17,
+    ProposalNotFound = 18,
+    BelowProposalThreshold = 19,
+    VotingClosed = 20,
+    AlreadyVoted = 21,
+    NoVotingPower = 22,
+    ProposalNotSucceeded = 23,
+    AccountFrozen = 24,
+    NotFreezer = 25,
+    NotDiscountConsumer = 26,
+    PoolNotFound = 27,
+    PoolInactive = 28,
+    RedemptionCapExceeded = 29,
+    InsufficientReserves = 30,
+    BadgeNotFound = 31,
+    ReferralAlreadySet = 32,
+    ReferralTooLate = 33,
+    AlreadyRewarded = 34,
+    CampaignNotFound = 35,
+    InvalidStreakCurve = 36,
+    InsufficientStake = 37,
+    RaffleNotFound = 38,
+    RaffleClosed = 39,
+    RaffleNotEnded = 40,
+    NotEligible = 41,
+    AirdropNotSet = 42,
+    InvalidProof = 43,
+    AirdropAlreadyClaimed = 44,
+    AirdropExhausted = 45,
+    NotMerchant = 46,
+    MerchantLimitExceeded = 47,
+    TargetNotAllowed = 48,
+}
+
+// ===== TRANSFER HOOKS =====
+
+// Soroban gives a contract no way to cap the budget of a call it makes, so neither policy can
+// bound a hook's cost: a hook that exhausts the transaction budget aborts the transfer either way.
+// Skip only contains hooks that trap or return an error. Register hooks with a known, small cost.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookFailurePolicy {
+    Revert = 0,  // A failing hook reverts the transfer
+    Skip = 1,    // A failing hook is skipped; repeated failures disable it
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferHook {
+    pub contract: Address,         // Must expose before_transfer/after_transfer(token, from, to, amount)
+    pub before: bool,
+    pub after: bool,
+    pub failure_policy: HookFailurePolicy,
+    pub consecutive_failures: u32,
+    pub enabled: bool,
+}
+
+// Consecutive failures after which a Skip-policy hook is switched off
+const MAX_HOOK_FAILURES: u32 = 3;
+
+// ===== CLAWBACK =====
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClawbackRecord {
+    pub from: Address,
+    pub amount: i128,
+    pub reason_code: u32,
+    pub timestamp: u64,
+}
+
+// ===== LOYALTY TIERS =====
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum LoyaltyTier {
+    None = 0,
+    Bronze = 1,
+    Silver = 2,
+    Gold = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierThresholds {
+    pub bronze: i128,   // Lifetime rewards needed for each tier
+    pub silver: i128,
+    pub gold: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaderboardEntry {
+    pub user: Address,
+    pub lifetime_rewards: i128,
 }
 
+// Number of top earners kept on-chain
+const LEADERBOARD_SIZE: u32 = 100;
+
 // ===== REWARD CONFIGURATION =====
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreakTier {
+    pub from_week: u32,                // First streak week this rate applies to
+    pub bonus_per_week: i128,          // Bonus earned for each week in this tier
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RewardConfig {
     pub base_weekly_reward: i128,      // Base reward for weekly contributions
     pub milestone_multiplier: u32,     // Multiplier for milestone rewards (basis points)
     pub completion_multiplier: u32,    // Multiplier for challenge completion (basis points)
-    pub streak_bonus_per_week: i128,   // Additional reward per week in streak
+    pub streak_curve: Vec<StreakTier>, // Per-week streak bonus, ascending by from_week
     pub max_streak_bonus: i128,        // Maximum streak bonus
     pub referral_reward: i128,         // Reward for successful referrals
     pub min_contribution_for_reward: i128, // Minimum contribution to earn rewards
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmissionSchedule {
+    pub start_time: u64,           // When decay starts counting
+    pub halving_interval: u64,     // Seconds between halvings (0 = no decay)
+    pub floor_bps: u32,            // Decay never goes below this share of the base reward
+}
+
+// ===== REDEMPTION =====
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedemptionPool {
+    pub asset: Address,            // Payout asset (XLM or USDC contract)
+    pub rate: i128,                // Asset units paid per SaveCoin, scaled by 10^7
+    pub reserves: i128,            // Asset held for redemptions
+    pub epoch_length: u64,         // Seconds per cap epoch
+    pub epoch_cap: i128,           // Max SaveCoin redeemable per epoch
+    pub epoch_start: u64,
+    pub redeemed_in_epoch: i128,
+    pub is_active: bool,
+}
+
+// Fixed-point scale for redemption rates
+const RATE_SCALE: i128 = 10_000_000;
+
+// ===== ACHIEVEMENT BADGES =====
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeInfo {
+    pub id: u32,
+    pub name: String,
+    pub description: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeAward {
+    pub badge_id: u32,
+    pub challenge_id: u32,         // Challenge the achievement was earned in (0 if none)
+    pub awarded_at: u64,
+}
+
+// Built-in achievement badges
+pub const BADGE_FIRST_CHALLENGE: u32 = 1;
+pub const BADGE_52_WEEK_STREAK: u32 = 2;
+pub const BADGE_CHALLENGE_COMPLETED: u32 = 3;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmissionCap {
+    pub cap_per_epoch: i128,       // Max reward SaveCoin minted per epoch across all minters
+    pub epoch_length: u64,         // Seconds per epoch
+    pub epoch_start: u64,
+    pub minted_in_epoch: i128,
+}
+
+// ===== CAMPAIGNS =====
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Campaign {
+    pub id: u32,
+    pub reward_type: RewardType,
+    pub multiplier_bps: u32,       // Applied on top of the reward (15000 = 1.5x)
+    pub start: u64,
+    pub end: u64,
+    pub canceled: bool,
+    pub rewards_boosted: u32,      // Number of rewards the campaign applied to
+    pub extra_minted: i128,        // SaveCoin issued above the un-boosted amount
+}
+
+// ===== RAFFLES =====
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Raffle {
+    pub id: u32,
+    pub treasury: Address,         // Funded the prize; refunded if nobody enters
+    pub prize_asset: Address,
+    pub prize_amount: i128,
+    pub ticket_price: i128,        // SaveCoin burned per ticket
+    pub ends_at: u64,
+    pub total_tickets: u32,
+    pub entry_count: u32,          // RaffleEntry records written, one per enter_raffle call
+    pub drawn: bool,
+    pub winner: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RaffleEntry {
+    pub user: Address,
+    pub tickets_end: u32,          // Cumulative ticket count after this entry
+}
+
+// ===== AIRDROPS =====
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Airdrop {
+    pub round: u32,                // Bumped each time a new root is set
+    pub root: BytesN<32>,          // Merkle root over sha256(user xdr || amount be bytes)
+    pub total: i128,               // Most that can be claimed this round
+    pub claimed: i128,
+}
+
+// ===== MERCHANTS =====
+
+const DAY_IN_SECONDS: u64 = 86400;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Merchant {
+    pub daily_limit: i128,         // Most SaveCoin the merchant can accept per day
+    pub burn_on_spend: bool,       // Burn spent SaveCoin instead of paying the merchant
+    pub day_start: u64,
+    pub spent_today: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendReceipt {
+    pub id: u32,
+    pub user: Address,
+    pub merchant: Address,
+    pub amount: i128,
+    pub burned: bool,
+    pub memo: String,
+    pub timestamp: u64,
+}
+
 // ===== CONTRACT IMPLEMENTATION =====
 
 #[contract]
@@ -110,6 +555,7 @@ impl SaveCoinToken {
         name: String,
         symbol: String,
         decimals: u32,
+        max_supply: i128,
     ) -> Result<(), TokenError> {
         admin.require_auth();
         
@@ -118,6 +564,10 @@ impl SaveCoinToken {
             return Err(TokenError::AlreadyInitialized);
         }
         
+        if max_supply <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
         // Set admin
         env.storage().instance().set(&DataKey::Admin, &admin);
         
@@ -129,6 +579,7 @@ impl SaveCoinToken {
             total_supply: 0,
         };
         env.storage().instance().set(&DataKey::Metadata, &metadata);
+        env.storage().instance().set(&DataKey::MaxSupply, &max_supply);
         
         // Initialize minter contracts list
         let minters: Vec<Address> = Vec::new(&env);
@@ -139,7 +590,12 @@ impl SaveCoinToken {
             base_weekly_reward: 10_0000000,        // 10 SaveCoin
             milestone_multiplier: 15000,           // 1.5x (150%)
             completion_multiplier: 20000,          // 2.0x (200%)
-            streak_bonus_per_week: 1_0000000,      // 1 SaveCoin per week
+            streak_curve: vec![
+                &env,
+                StreakTier { from_week: 1, bonus_per_week: 5000000 },    // 0.5 SaveCoin, weeks 1-4
+                StreakTier { from_week: 5, bonus_per_week: 1_0000000 },  // 1 SaveCoin, weeks 5-12
+                StreakTier { from_week: 13, bonus_per_week: 2_0000000 }, // 2 SaveCoin, weeks 13+
+            ],
             max_streak_bonus: 50_0000000,          // 50 SaveCoin max
             referral_reward: 25_0000000,           // 25 SaveCoin
             min_contribution_for_reward: 10_0000000, // 10 XLM minimum
@@ -149,6 +605,40 @@ impl SaveCoinToken {
         // Initialize total rewards counter
         env.storage().instance().set(&DataKey::TotalRewards, &0i128);
         
+        // Set default governance configuration
+        let default_governance = GovernanceConfig {
+            voting_period: 7 * 24 * 60 * 60,      // 7 days
+            quorum_bps: 400,                       // 4% of supply
+            approval_threshold_bps: 5000,          // Simple majority
+            proposal_threshold: 1_000_0000000,     // 1,000 SaveCoin
+        };
+        env.storage().instance().set(&GovernanceKey::GovernanceConfig, &default_governance);
+        env.storage().instance().set(&GovernanceKey::NextProposalId, &1u32);
+        
+        env.storage().instance().set(&RewardKey::TierThresholds, &Self::default_tier_thresholds());
+        
+        // Register built-in achievement badges
+        let first_challenge = BadgeInfo {
+            id: BADGE_FIRST_CHALLENGE,
+            name: String::from_str(&env, "First Challenge"),
+            description: String::from_str(&env, "Completed a first savings challenge"),
+        };
+        env.storage().persistent().set(&RewardKey::Badge(BADGE_FIRST_CHALLENGE), &first_challenge);
+        
+        let year_streak = BadgeInfo {
+            id: BADGE_52_WEEK_STREAK,
+            name: String::from_str(&env, "52-Week Streak"),
+            description: String::from_str(&env, "Saved every week for a full year"),
+        };
+        env.storage().persistent().set(&RewardKey::Badge(BADGE_52_WEEK_STREAK), &year_streak);
+        
+        let completed = BadgeInfo {
+            id: BADGE_CHALLENGE_COMPLETED,
+            name: String::from_str(&env, "Challenge Completed"),
+            description: String::from_str(&env, "Finished a savings challenge that reached its goal"),
+        };
+        env.storage().persistent().set(&RewardKey::Badge(BADGE_CHALLENGE_COMPLETED), &completed);
+        
         log!(&env, "SaveCoin token initialized: {} ({})", name, symbol);
         
         Ok(())
@@ -163,6 +653,12 @@ impl SaveCoinToken {
             .unwrap_or(0)
     }
     
+    /// Get the part of the balance that isn't locked (staked tokens still count for voting)
+    pub fn spendable_balance(env: Env, id: Address) -> i128 {
+        let staked = Self::get_staked(env.clone(), id.clone());
+        (Self::balance(env, id) - staked).max(0)
+    }
+    
     /// Transfer tokens between addresses
     pub fn transfer(
         env: Env,
@@ -171,6 +667,7 @@ impl SaveCoinToken {
         amount: i128,
     ) -> Result<(), TokenError> {
         from.require_auth();
+        Self::require_not_frozen(&env, &from)?;
         
         if amount < 0 {
             return Err(TokenError::InvalidAmount);
@@ -180,15 +677,12 @@ impl SaveCoinToken {
             return Ok(());
         }
         
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
+        Self::run_transfer_hook(&env, symbol_short!("before"), &from, &to, amount)?;
         
-        let to_balance = Self::balance(env.clone(), to.clone());
+        Self::spend_balance(&env, &from, amount)?;
+        Self::receive_balance(&env, &to, amount);
         
-        env.storage().persistent().set(&DataKey::Balance(from.clone()), &(from_balance - amount));
-        env.storage().persistent().set(&DataKey::Balance(to.clone()), &(to_balance + amount));
+        Self::run_transfer_hook(&env, symbol_short!("after"), &from, &to, amount)?;
         
         env.events().publish(
             (symbol_short!("transfer"), from, to),
@@ -198,12 +692,13 @@ impl SaveCoinToken {
         Ok(())
     }
     
-    /// Approve spending allowance
+    /// Approve spending allowance until `expiration_ledger`
     pub fn approve(
         env: Env,
         from: Address,
         spender: Address,
         amount: i128,
+        expiration_ledger: u32,
     ) -> Result<(), TokenError> {
         from.require_auth();
         
@@ -211,21 +706,82 @@ impl SaveCoinToken {
             return Err(TokenError::InvalidAmount);
         }
         
-        env.storage().persistent().set(&DataKey::Allowance(from.clone(), spender.clone()), &amount);
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            return Err(TokenError::InvalidExpiration);
+        }
+        
+        Self::write_allowance(&env, &from, &spender, amount, expiration_ledger);
         
         env.events().publish(
             (symbol_short!("approve"), from, spender),
-            amount
+            (amount, expiration_ledger)
         );
         
         Ok(())
     }
     
-    /// Get spending allowance
+    /// Atomically raise an allowance, setting a new expiration
+    pub fn increase_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<i128, TokenError> {
+        from.require_auth();
+        
+        if amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        if expiration_ledger < env.ledger().sequence() {
+            return Err(TokenError::InvalidExpiration);
+        }
+        
+        let current = Self::read_allowance(&env, &from, &spender);
+        let new_amount = current.amount
+            .checked_add(amount)
+            .ok_or(TokenError::InvalidAmount)?;
+        
+        Self::write_allowance(&env, &from, &spender, new_amount, expiration_ledger);
+        
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (new_amount, expiration_ledger)
+        );
+        
+        Ok(new_amount)
+    }
+    
+    /// Atomically lower an allowance (floored at zero), keeping its expiration
+    pub fn decrease_allowance(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+    ) -> Result<i128, TokenError> {
+        from.require_auth();
+        
+        if amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let current = Self::read_allowance(&env, &from, &spender);
+        let new_amount = (current.amount - amount).max(0);
+        
+        Self::write_allowance(&env, &from, &spender, new_amount, current.expiration_ledger);
+        
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (new_amount, current.expiration_ledger)
+        );
+        
+        Ok(new_amount)
+    }
+    
+    /// Get spending allowance (zero once expired)
     pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
-        env.storage().persistent()
-            .get(&DataKey::Allowance(from, spender))
-            .unwrap_or(0)
+        Self::read_allowance(&env, &from, &spender).amount
     }
     
     /// Transfer from allowance
@@ -237,6 +793,7 @@ impl SaveCoinToken {
         amount: i128,
     ) -> Result<(), TokenError> {
         spender.require_auth();
+        Self::require_not_frozen(&env, &from)?;
         
         if amount < 0 {
             return Err(TokenError::InvalidAmount);
@@ -246,21 +803,13 @@ impl SaveCoinToken {
             return Ok(());
         }
         
-        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
-        if allowance < amount {
-            return Err(TokenError::InsufficientAllowance);
-        }
-        
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
+        Self::run_transfer_hook(&env, symbol_short!("before"), &from, &to, amount)?;
         
-        let to_balance = Self::balance(env.clone(), to.clone());
+        Self::spend_allowance(&env, &from, &spender, amount)?;
+        Self::spend_balance(&env, &from, amount)?;
+        Self::receive_balance(&env, &to, amount);
         
-        env.storage().persistent().set(&DataKey::Balance(from.clone()), &(from_balance - amount));
-        env.storage().persistent().set(&DataKey::Balance(to.clone()), &(to_balance + amount));
-        env.storage().persistent().set(&DataKey::Allowance(from.clone(), spender.clone()), &(allowance - amount));
+        Self::run_transfer_hook(&env, symbol_short!("after"), &from, &to, amount)?;
         
         env.events().publish(
             (symbol_short!("transfer"), from, to),
@@ -270,25 +819,69 @@ impl SaveCoinToken {
         Ok(())
     }
     
-    // ===== TOKEN METADATA =====
-    
-    /// Get token name
-    pub fn name(env: Env) -> String {
-        let metadata: TokenMetadata = env.storage().instance()
-            .get(&DataKey::Metadata)
-            .unwrap_or(TokenMetadata {
-                name: String::from_str(&env, "SaveCoin"),
-                symbol: String::from_str(&env, "SAVE"),
-                decimals: 7,
-                total_supply: 0,
-            });
-        metadata.name
+    /// Burn tokens from an address
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
+        from.require_auth();
+        
+        if amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        Self::spend_balance(&env, &from, amount)?;
+        Self::adjust_supply(&env, -amount)?;
+        
+        env.events().publish(
+            (symbol_short!("burn"), from),
+            amount
+        );
+        
+        Ok(())
     }
     
-    /// Get token symbol
-    pub fn symbol(env: Env) -> String {
-        let metadata: TokenMetadata = env.storage().instance()
-            .get(&DataKey::Metadata)
+    /// Burn tokens from an address using the spender's allowance
+    pub fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128,
+    ) -> Result<(), TokenError> {
+        spender.require_auth();
+        
+        if amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        Self::spend_allowance(&env, &from, &spender, amount)?;
+        Self::spend_balance(&env, &from, amount)?;
+        Self::adjust_supply(&env, -amount)?;
+        
+        env.events().publish(
+            (symbol_short!("burn"), from),
+            amount
+        );
+        
+        Ok(())
+    }
+    
+    // ===== TOKEN METADATA =====
+    
+    /// Get token name
+    pub fn name(env: Env) -> String {
+        let metadata: TokenMetadata = env.storage().instance()
+            .get(&DataKey::Metadata)
+            .unwrap_or(TokenMetadata {
+                name: String::from_str(&env, "SaveCoin"),
+                symbol: String::from_str(&env, "SAVE"),
+                decimals: 7,
+                total_supply: 0,
+            });
+        metadata.name
+    }
+    
+    /// Get token symbol
+    pub fn symbol(env: Env) -> String {
+        let metadata: TokenMetadata = env.storage().instance()
+            .get(&DataKey::Metadata)
             .unwrap_or(TokenMetadata {
                 name: String::from_str(&env, "SaveCoin"),
                 symbol: String::from_str(&env, "SAVE"),
@@ -311,6 +904,16 @@ impl SaveCoinToken {
         metadata.decimals
     }
     
+    /// Get token icon and home page URIs
+    pub fn get_token_uris(env: Env) -> TokenUris {
+        env.storage().instance()
+            .get(&DataKey::MetadataUris)
+            .unwrap_or(TokenUris {
+                icon_uri: None,
+                home_uri: None,
+            })
+    }
+    
     /// Get total supply
     pub fn total_supply(env: Env) -> i128 {
         let metadata: TokenMetadata = env.storage().instance()
@@ -326,6 +929,28 @@ impl SaveCoinToken {
     
     // ===== REWARD SYSTEM =====
     
+    /// Mint SaveCoin (admin only)
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotInitialized)?;
+        admin.require_auth();
+        
+        if amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        Self::receive_balance(&env, &to, amount);
+        Self::adjust_supply(&env, amount)?;
+        
+        env.events().publish(
+            (symbol_short!("mint"), admin, to),
+            amount
+        );
+        
+        Ok(())
+    }
+    
     /// Mint SaveCoin rewards (only authorized minters)
     pub fn mint_reward(
         env: Env,
@@ -335,251 +960,2881 @@ impl SaveCoinToken {
         reward_type: RewardType,
         challenge_id: u32,
         multiplier: u32, // Basis points
+        dedupe_key: Option<BytesN<32>>, // e.g. hash of (challenge, user, week)
+        auto_stake: bool,
     ) -> Result<(), TokenError> {
         minter.require_auth();
+        Self::require_minter(&env, &minter)?;
+        Self::require_reward_eligible(&env, &to)?;
         
-        // Check if minter is authorized
-        let minters: Vec<Address> = env.storage().instance()
-            .get(&DataKey::MinterContracts)
-            .unwrap_or(Vec::new(&env));
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
         
-        if !minters.contains(&minter) {
-            return Err(TokenError::NotMinter);
+        if let Some(key) = dedupe_key {
+            Self::claim_dedupe_key(&env, &minter, key)?;
+        }
+        
+        // Apply multiplier and any running campaign boost
+        let final_amount = (amount * multiplier as i128) / 10000;
+        let final_amount = Self::apply_campaign(&env, &reward_type, final_amount);
+        
+        Self::consume_minter_budget(&env, &minter, final_amount)?;
+        
+        // Mint tokens (anything over the global epoch cap is queued as pending)
+        let minted = Self::issue_reward(&env, &minter, &to, final_amount)?;
+        
+        // Lock what was minted straight into the user's stake
+        if auto_stake && minted > 0 {
+            Self::add_stake(&env, &to, minted);
+        }
+        
+        let is_completion = reward_type == RewardType::ChallengeCompleted;
+        Self::record_reward(&env, &to, final_amount, reward_type, challenge_id, multiplier);
+        
+        // A referee's first completed challenge pays their referrer
+        if is_completion {
+            Self::pay_referral_bonus(&env, &minter, &to, challenge_id)?;
         }
         
+        log!(&env, "Minted {} SaveCoin reward to {} for challenge {}", final_amount, to, challenge_id);
+        
+        Ok(())
+    }
+    
+    /// Record a reward as pending without minting it (only authorized minters)
+    pub fn accrue_reward(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128,
+        reward_type: RewardType,
+        challenge_id: u32,
+        multiplier: u32, // Basis points
+        dedupe_key: Option<BytesN<32>>, // e.g. hash of (challenge, user, week)
+    ) -> Result<(), TokenError> {
+        minter.require_auth();
+        Self::require_minter(&env, &minter)?;
+        Self::require_reward_eligible(&env, &to)?;
+        
         if amount <= 0 {
             return Err(TokenError::InvalidAmount);
         }
         
-        // Apply multiplier
+        if let Some(key) = dedupe_key {
+            Self::claim_dedupe_key(&env, &minter, key)?;
+        }
+        
+        // Apply multiplier and any running campaign boost
         let final_amount = (amount * multiplier as i128) / 10000;
+        let final_amount = Self::apply_campaign(&env, &reward_type, final_amount);
         
-        // Mint tokens
-        let current_balance = Self::balance(env.clone(), to.clone());
-        env.storage().persistent().set(&DataKey::Balance(to.clone()), &(current_balance + final_amount));
+        Self::consume_minter_budget(&env, &minter, final_amount)?;
         
-        // Update total supply
-        let mut metadata: TokenMetadata = env.storage().instance()
-            .get(&DataKey::Metadata)
-            .ok_or(TokenError::NotInitialized)?;
-        metadata.total_supply += final_amount;
-        env.storage().instance().set(&DataKey::Metadata, &metadata);
+        let pending = Self::get_pending_rewards(env.clone(), to.clone());
+        env.storage().persistent().set(&DataKey::PendingRewards(to.clone()), &(pending + final_amount));
         
-        // Record reward
-        let reward_record = RewardRecord {
-            recipient: to.clone(),
-            amount: final_amount,
-            reward_type: reward_type.clone(),
-            challenge_id,
-            timestamp: env.ledger().timestamp(),
-            multiplier,
-        };
+        Self::record_reward(&env, &to, final_amount, reward_type, challenge_id, multiplier);
         
-        // Add to user's reward history
-        let mut user_rewards: Vec<RewardRecord> = env.storage().persistent()
-            .get(&DataKey::RewardHistory(to.clone()))
-            .unwrap_or(Vec::new(&env));
-        user_rewards.push_back(reward_record);
-        env.storage().persistent().set(&DataKey::RewardHistory(to.clone()), &user_rewards);
+        env.events().publish(
+            (symbol_short!("accrue"), minter, to),
+            final_amount
+        );
         
-        // Update statistics
-        let current_type_total: i128 = env.storage().persistent()
-            .get(&DataKey::RewardStats(reward_type.clone()))
-            .unwrap_or(0);
-        env.storage().persistent().set(&DataKey::RewardStats(reward_type.clone()), &(current_type_total + final_amount));
+        Ok(())
+    }
+    
+    /// Mint all pending rewards to the user
+    pub fn claim_rewards(env: Env, user: Address) -> Result<i128, TokenError> {
+        user.require_auth();
+        Self::require_not_frozen(&env, &user)?;
+        Self::require_reward_eligible(&env, &user)?;
         
-        let total_rewards: i128 = env.storage().instance()
-            .get(&DataKey::TotalRewards)
-            .unwrap_or(0);
-        env.storage().instance().set(&DataKey::TotalRewards, &(total_rewards + final_amount));
+        let pending = Self::get_pending_rewards(env.clone(), user.clone());
+        
+        // Claims share the global epoch cap; the remainder stays pending
+        let claimed = Self::take_emission_allowance(&env, pending);
+        if claimed == 0 {
+            return Ok(0);
+        }
+        
+        if claimed == pending {
+            env.storage().persistent().remove(&DataKey::PendingRewards(user.clone()));
+        } else {
+            env.storage().persistent().set(&DataKey::PendingRewards(user.clone()), &(pending - claimed));
+        }
+        
+        Self::receive_balance(&env, &user, claimed);
+        Self::adjust_supply(&env, claimed)?;
         
-        // Emit events
         env.events().publish(
-            (symbol_short!("mint"), to.clone()),
-            (final_amount, reward_type, challenge_id)
+            (symbol_short!("claim"), user),
+            claimed
         );
         
+        Ok(claimed)
+    }
+    
+    /// Burn SaveCoin in exchange for a platform fee discount credit
+    pub fn redeem_for_discount(env: Env, user: Address, amount: i128) -> Result<i128, TokenError> {
+        user.require_auth();
+        Self::require_not_frozen(&env, &user)?;
+        
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        Self::spend_balance(&env, &user, amount)?;
+        Self::adjust_supply(&env, -amount)?;
+        
+        let credit = (amount * Self::get_discount_rate(env.clone()) as i128) / 10000;
+        let balance = Self::get_discount_credit(env.clone(), user.clone());
+        env.storage().persistent().set(&SinkKey::DiscountCredit(user.clone()), &(balance + credit));
+        
         env.events().publish(
-            (symbol_short!("reward"), challenge_id),
-            (to, final_amount, reward_type)
+            (symbol_short!("burn"), user.clone()),
+            amount
         );
         
-        log!(&env, "Minted {} SaveCoin reward to {} for challenge {}", final_amount, to, challenge_id);
+        env.events().publish(
+            (symbol_short!("discount"), user),
+            (amount, credit)
+        );
         
-        Ok(())
+        Ok(credit)
     }
     
-    /// Calculate reward amount based on contribution and type
-    pub fn calculate_reward(
+    /// Spend up to `max_amount` of a user's discount credit (discount consumers only)
+    pub fn use_discount(
         env: Env,
-        contribution_amount: i128,
-        reward_type: RewardType,
-        streak_weeks: u32,
+        consumer: Address,
+        user: Address,
+        max_amount: i128,
     ) -> Result<i128, TokenError> {
-        let config: RewardConfig = env.storage().instance()
-            .get(&DataKey::RewardConfig)
-            .ok_or(TokenError::RewardConfigNotSet)?;
+        consumer.require_auth();
         
-        if contribution_amount < config.min_contribution_for_reward {
+        if !Self::get_discount_consumers(env.clone()).contains(&consumer) {
+            return Err(TokenError::NotDiscountConsumer);
+        }
+        
+        if max_amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let balance = Self::get_discount_credit(env.clone(), user.clone());
+        let used = balance.min(max_amount);
+        if used == 0 {
             return Ok(0);
         }
         
-        let base_reward = match reward_type {
-            RewardType::WeeklyContribution => config.base_weekly_reward,
-            RewardType::MilestoneReached => {
-                (config.base_weekly_reward * config.milestone_multiplier as i128) / 10000
-            },
-            RewardType::ChallengeCompleted => {
-                (config.base_weekly_reward * config.completion_multiplier as i128) / 10000
-            },
-            RewardType::StreakBonus => {
-                let streak_bonus = (streak_weeks as i128) * config.streak_bonus_per_week;
-                streak_bonus.min(config.max_streak_bonus)
-            },
-            RewardType::ReferralBonus => config.referral_reward,
-        };
+        env.storage().persistent().set(&SinkKey::DiscountCredit(user.clone()), &(balance - used));
         
-        // Scale reward based on contribution size (larger contributions get slightly more)
-        let contribution_factor = if contribution_amount >= 100_0000000 { // 100 XLM+
-            12000 // 1.2x
-        } else if contribution_amount >= 50_0000000 { // 50 XLM+
-            11000 // 1.1x
-        } else {
-            10000 // 1.0x
-        };
+        env.events().publish(
+            (symbol_short!("disc_used"), user, consumer),
+            used
+        );
         
-        let final_reward = (base_reward * contribution_factor) / 10000;
-        Ok(final_reward)
+        Ok(used)
     }
     
-    // ===== ADMIN FUNCTIONS =====
-    
-    /// Add authorized minter contract (admin only)
-    pub fn add_minter(env: Env, admin: Address, minter: Address) -> Result<(), TokenError> {
-        admin.require_auth();
+    /// Burn SaveCoin for a share of the treasury-funded redemption pool
+    pub fn redeem(env: Env, user: Address, asset: Address, amount: i128) -> Result<i128, TokenError> {
+        user.require_auth();
+        Self::require_not_frozen(&env, &user)?;
         
-        let stored_admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .ok_or(TokenError::NotAuthorized)?;
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
         
-        if admin != stored_admin {
-            return Err(TokenError::NotAuthorized);
+        let mut pool = Self::get_redemption_pool(env.clone(), asset.clone())?;
+        if !pool.is_active {
+            return Err(TokenError::PoolInactive);
         }
         
-        let mut minters: Vec<Address> = env.storage().instance()
-            .get(&DataKey::MinterContracts)
-            .unwrap_or(Vec::new(&env));
+        // Roll the cap window forward if the epoch has ended
+        let now = env.ledger().timestamp();
+        if now >= pool.epoch_start + pool.epoch_length {
+            let elapsed_epochs = (now - pool.epoch_start) / pool.epoch_length;
+            pool.epoch_start += elapsed_epochs * pool.epoch_length;
+            pool.redeemed_in_epoch = 0;
+        }
         
-        if !minters.contains(&minter) {
-            minters.push_back(minter.clone());
-            env.storage().instance().set(&DataKey::MinterContracts, &minters);
-            
-            env.events().publish(
-                (symbol_short!("minter_add"), admin),
-                minter
-            );
+        if pool.redeemed_in_epoch + amount > pool.epoch_cap {
+            return Err(TokenError::RedemptionCapExceeded);
         }
         
-        Ok(())
-    }
-    
-    /// Remove minter contract (admin only)
-    pub fn remove_minter(env: Env, admin: Address, minter: Address) -> Result<(), TokenError> {
-        admin.require_auth();
+        let payout = (amount * pool.rate) / RATE_SCALE;
+        if payout > pool.reserves {
+            return Err(TokenError::InsufficientReserves);
+        }
         
-        let stored_admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .ok_or(TokenError::NotAuthorized)?;
+        Self::spend_balance(&env, &user, amount)?;
+        Self::adjust_supply(&env, -amount)?;
         
-        if admin != stored_admin {
-            return Err(TokenError::NotAuthorized);
-        }
+        pool.redeemed_in_epoch += amount;
+        pool.reserves -= payout;
+        env.storage().persistent().set(&SinkKey::RedemptionPool(asset.clone()), &pool);
         
-        let mut minters: Vec<Address> = env.storage().instance()
-            .get(&DataKey::MinterContracts)
-            .unwrap_or(Vec::new(&env));
+        token::Client::new(&env, &asset).transfer(
+            &env.current_contract_address(),
+            &user,
+            &payout
+        );
         
-        if let Some(index) = minters.first_index_of(&minter) {
-            minters.remove(index);
-            env.storage().instance().set(&DataKey::MinterContracts, &minters);
-            
-            env.events().publish(
-                (symbol_short!("minter_rm"), admin),
-                minter
-            );
-        }
+        env.events().publish(
+            (symbol_short!("burn"), user.clone()),
+            amount
+        );
         
-        Ok(())
+        env.events().publish(
+            (symbol_short!("redeem"), user, asset),
+            (amount, payout)
+        );
+        
+        Ok(payout)
     }
     
-    /// Update reward configuration (admin only)
-    pub fn update_reward_config(
+    /// Add payout assets to a redemption pool (anyone, typically the treasury)
+    pub fn fund_redemption_pool(
         env: Env,
-        admin: Address,
-        config: RewardConfig,
+        funder: Address,
+        asset: Address,
+        amount: i128,
     ) -> Result<(), TokenError> {
-        admin.require_auth();
-        
-        let stored_admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .ok_or(TokenError::NotAuthorized)?;
+        funder.require_auth();
         
-        if admin != stored_admin {
-            return Err(TokenError::NotAuthorized);
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
         }
         
-        env.storage().instance().set(&DataKey::RewardConfig, &config);
+        let mut pool = Self::get_redemption_pool(env.clone(), asset.clone())?;
+        
+        token::Client::new(&env, &asset).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount
+        );
+        
+        pool.reserves += amount;
+        env.storage().persistent().set(&SinkKey::RedemptionPool(asset.clone()), &pool);
         
         env.events().publish(
-            (symbol_short!("config"), admin),
-            config.base_weekly_reward
+            (symbol_short!("rp_fund"), asset),
+            (funder, amount)
         );
         
         Ok(())
     }
     
-    // ===== QUERY FUNCTIONS =====
-    
-    /// Get user's reward history
-    pub fn get_reward_history(env: Env, user: Address) -> Vec<RewardRecord> {
-        env.storage().persistent()
-            .get(&DataKey::RewardHistory(user))
-            .unwrap_or(Vec::new(&env))
+    /// Open a raffle whose prize is escrowed from the treasury (admin only)
+    pub fn create_raffle(
+        env: Env,
+        admin: Address,
+        treasury: Address,
+        prize_asset: Address,
+        prize_amount: i128,
+        ticket_price: i128,
+        ends_at: u64,
+    ) -> Result<u32, TokenError> {
+        Self::require_admin(&env, &admin)?;
+        treasury.require_auth();
+        
+        if prize_amount <= 0 || ticket_price <= 0 || ends_at <= env.ledger().timestamp() {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        token::Client::new(&env, &prize_asset).transfer(
+            &treasury,
+            &env.current_contract_address(),
+            &prize_amount
+        );
+        
+        let raffle_id: u32 = env.storage().instance()
+            .get(&RewardKey::NextRaffleId)
+            .unwrap_or(1);
+        
+        let raffle = Raffle {
+            id: raffle_id,
+            treasury,
+            prize_asset,
+            prize_amount,
+            ticket_price,
+            ends_at,
+            total_tickets: 0,
+            entry_count: 0,
+            drawn: false,
+            winner: None,
+        };
+        env.storage().persistent().set(&RewardKey::Raffle(raffle_id), &raffle);
+        env.storage().instance().set(&RewardKey::NextRaffleId, &(raffle_id + 1));
+        
+        env.events().publish(
+            (symbol_short!("raffle"), raffle_id),
+            (prize_amount, ticket_price, ends_at)
+        );
+        
+        Ok(raffle_id)
     }
     
-    /// Get total rewards distributed by type
-    pub fn get_reward_stats(env: Env, reward_type: RewardType) -> i128 {
-        env.storage().persistent()
-            .get(&DataKey::RewardStats(reward_type))
-            .unwrap_or(0)
+    /// Burn SaveCoin for raffle tickets
+    pub fn enter_raffle(env: Env, user: Address, raffle_id: u32, tickets: u32) -> Result<(), TokenError> {
+        user.require_auth();
+        
+        if tickets == 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let mut raffle = Self::get_raffle(env.clone(), raffle_id)?;
+        if raffle.drawn || env.ledger().timestamp() >= raffle.ends_at {
+            return Err(TokenError::RaffleClosed);
+        }
+        
+        let cost = raffle.ticket_price * tickets as i128;
+        Self::spend_balance(&env, &user, cost)?;
+        Self::adjust_supply(&env, -cost)?;
+        
+        raffle.total_tickets += tickets;
+        
+        // Each entry gets its own key so a popular raffle never outgrows a ledger entry
+        env.storage().persistent().set(
+            &RewardKey::RaffleEntry(raffle_id, raffle.entry_count),
+            &RaffleEntry {
+                user: user.clone(),
+                tickets_end: raffle.total_tickets,
+            }
+        );
+        raffle.entry_count += 1;
+        env.storage().persistent().set(&RewardKey::Raffle(raffle_id), &raffle);
+        
+        env.events().publish(
+            (symbol_short!("burn"), user.clone()),
+            cost
+        );
+        env.events().publish(
+            (symbol_short!("r_enter"), raffle_id, user),
+            tickets
+        );
+        
+        Ok(())
     }
     
-    /// Get total rewards distributed
-    pub fn get_total_rewards(env: Env) -> i128 {
-        env.storage().instance()
-            .get(&DataKey::TotalRewards)
-            .unwrap_or(0)
+    /// Pick a winner once the raffle has ended (callable by anyone)
+    pub fn draw_raffle(env: Env, raffle_id: u32) -> Result<Option<Address>, TokenError> {
+        let mut raffle = Self::get_raffle(env.clone(), raffle_id)?;
+        if raffle.drawn {
+            return Err(TokenError::RaffleClosed);
+        }
+        if env.ledger().timestamp() < raffle.ends_at {
+            return Err(TokenError::RaffleNotEnded);
+        }
+        
+        // Nobody entered: the prize goes back to the treasury
+        let recipient = if raffle.total_tickets == 0 {
+            raffle.treasury.clone()
+        } else {
+            // PRNG is seeded from ledger data, so a draw is only as unpredictable as the ledger close
+            let ticket: u32 = env.prng().gen_range::<u64>(0..raffle.total_tickets as u64) as u32;
+            
+            // Binary search for the first entry whose cumulative count passes the ticket
+            let mut lo = 0u32;
+            let mut hi = raffle.entry_count - 1;
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                if Self::raffle_entry(&env, raffle_id, mid).tickets_end > ticket {
+                    hi = mid;
+                } else {
+                    lo = mid + 1;
+                }
+            }
+            let winner = Self::raffle_entry(&env, raffle_id, lo).user;
+            raffle.winner = Some(winner.clone());
+            winner
+        };
+        
+        raffle.drawn = true;
+        env.storage().persistent().set(&RewardKey::Raffle(raffle_id), &raffle);
+        
+        token::Client::new(&env, &raffle.prize_asset).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &raffle.prize_amount
+        );
+        
+        env.events().publish(
+            (symbol_short!("r_draw"), raffle_id),
+            (raffle.winner.clone(), raffle.prize_amount)
+        );
+        
+        Ok(raffle.winner)
     }
     
-    /// Get current reward configuration
-    pub fn get_reward_config(env: Env) -> Result<RewardConfig, TokenError> {
-        env.storage().instance()
+    /// Claim a retroactive airdrop by proving inclusion in the current Merkle root
+    pub fn claim_airdrop(
+        env: Env,
+        user: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), TokenError> {
+        user.require_auth();
+        Self::require_reward_eligible(&env, &user)?;
+        
+        let mut airdrop = Self::get_airdrop(env.clone())?;
+        
+        let claimed_key = RewardKey::AirdropClaimed(airdrop.round, user.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            return Err(TokenError::AirdropAlreadyClaimed);
+        }
+        
+        // Leaf = sha256(user xdr || amount), pairs hashed in sorted order
+        let mut leaf_data = user.clone().to_xdr(&env);
+        leaf_data.extend_from_array(&amount.to_be_bytes());
+        let mut node = env.crypto().sha256(&leaf_data).to_array();
+        
+        for sibling in proof.iter() {
+            let sibling = sibling.to_array();
+            let mut pair = Bytes::new(&env);
+            if node <= sibling {
+                pair.extend_from_array(&node);
+                pair.extend_from_array(&sibling);
+            } else {
+                pair.extend_from_array(&sibling);
+                pair.extend_from_array(&node);
+            }
+            node = env.crypto().sha256(&pair).to_array();
+        }
+        
+        if node != airdrop.root.to_array() {
+            return Err(TokenError::InvalidProof);
+        }
+        
+        if amount <= 0 || airdrop.claimed + amount > airdrop.total {
+            return Err(TokenError::AirdropExhausted);
+        }
+        
+        airdrop.claimed += amount;
+        env.storage().instance().set(&RewardKey::Airdrop, &airdrop);
+        env.storage().persistent().set(&claimed_key, &true);
+        
+        Self::receive_balance(&env, &user, amount);
+        Self::adjust_supply(&env, amount)?;
+        
+        env.events().publish(
+            (symbol_short!("airdrop"), user),
+            (airdrop.round, amount)
+        );
+        
+        Ok(())
+    }
+    
+    /// Spend SaveCoin at a partner merchant, returning the receipt ID
+    pub fn spend(
+        env: Env,
+        user: Address,
+        merchant: Address,
+        amount: i128,
+        memo: String,
+    ) -> Result<u32, TokenError> {
+        user.require_auth();
+        Self::require_not_frozen(&env, &user)?;
+        
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let mut config = Self::roll_merchant_day(&env, Self::get_merchant(env.clone(), merchant.clone())?);
+        if config.spent_today + amount > config.daily_limit {
+            return Err(TokenError::MerchantLimitExceeded);
+        }
+        config.spent_today += amount;
+        env.storage().persistent().set(&SinkKey::Merchant(merchant.clone()), &config);
+        
+        Self::run_transfer_hook(&env, symbol_short!("before"), &user, &merchant, amount)?;
+        
+        Self::spend_balance(&env, &user, amount)?;
+        if config.burn_on_spend {
+            Self::adjust_supply(&env, -amount)?;
+        } else {
+            Self::receive_balance(&env, &merchant, amount);
+        }
+        
+        Self::run_transfer_hook(&env, symbol_short!("after"), &user, &merchant, amount)?;
+        
+        let receipt_id: u32 = env.storage().instance()
+            .get(&SinkKey::NextReceiptId)
+            .unwrap_or(1);
+        
+        let receipt = SpendReceipt {
+            id: receipt_id,
+            user: user.clone(),
+            merchant: merchant.clone(),
+            amount,
+            burned: config.burn_on_spend,
+            memo,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&SinkKey::SpendReceipt(receipt_id), &receipt);
+        env.storage().instance().set(&SinkKey::NextReceiptId, &(receipt_id + 1));
+        
+        env.events().publish(
+            (symbol_short!("spend"), user, merchant),
+            (receipt_id, amount)
+        );
+        
+        Ok(receipt_id)
+    }
+    
+    /// Award a non-transferable achievement badge (only authorized minters)
+    pub fn mint_badge(
+        env: Env,
+        minter: Address,
+        to: Address,
+        badge_id: u32,
+        challenge_id: u32,
+    ) -> Result<u32, TokenError> {
+        minter.require_auth();
+        Self::require_minter(&env, &minter)?;
+        
+        if !env.storage().persistent().has(&RewardKey::Badge(badge_id)) {
+            return Err(TokenError::BadgeNotFound);
+        }
+        
+        let count = Self::badge_balance(env.clone(), to.clone(), badge_id) + 1;
+        env.storage().persistent().set(&RewardKey::BadgeBalance(to.clone(), badge_id), &count);
+        
+        let mut awards = Self::get_badges(env.clone(), to.clone());
+        awards.push_back(BadgeAward {
+            badge_id,
+            challenge_id,
+            awarded_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&RewardKey::BadgeAwards(to.clone()), &awards);
+        
+        env.events().publish(
+            (symbol_short!("badge"), to, badge_id),
+            challenge_id
+        );
+        
+        Ok(count)
+    }
+    
+    /// Register who referred a user (once, before the user earns any reward)
+    pub fn register_referral(env: Env, referee: Address, referrer: Address) -> Result<(), TokenError> {
+        referee.require_auth();
+        
+        if referee == referrer {
+            return Err(TokenError::InvalidAddress);
+        }
+        
+        if env.storage().persistent().has(&RewardKey::Referrer(referee.clone())) {
+            return Err(TokenError::ReferralAlreadySet);
+        }
+        
+        if Self::get_reward_count(env.clone(), referee.clone()) > 0 {
+            return Err(TokenError::ReferralTooLate);
+        }
+        
+        env.storage().persistent().set(&RewardKey::Referrer(referee.clone()), &referrer);
+        
+        let count = Self::get_referral_count(env.clone(), referrer.clone());
+        env.storage().persistent().set(&RewardKey::ReferralCount(referrer.clone()), &(count + 1));
+        
+        env.events().publish(
+            (symbol_short!("referral"), referrer),
+            referee
+        );
+        
+        Ok(())
+    }
+    
+    /// Calculate reward amount based on contribution and type
+    pub fn calculate_reward(
+        env: Env,
+        contribution_amount: i128,
+        reward_type: RewardType,
+        streak_weeks: u32,
+    ) -> Result<i128, TokenError> {
+        let mut config: RewardConfig = env.storage().instance()
             .get(&DataKey::RewardConfig)
-            .ok_or(TokenError::RewardConfigNotSet)
+            .ok_or(TokenError::RewardConfigNotSet)?;
+        
+        if contribution_amount < config.min_contribution_for_reward {
+            return Ok(0);
+        }
+        
+        // Apply emission decay to the base reward everything else scales from
+        config.base_weekly_reward = (config.base_weekly_reward * Self::get_emission_factor(env.clone()) as i128) / 10000;
+        
+        let base_reward = match reward_type {
+            RewardType::WeeklyContribution => config.base_weekly_reward,
+            RewardType::MilestoneReached => {
+                (config.base_weekly_reward * config.milestone_multiplier as i128) / 10000
+            },
+            RewardType::ChallengeCompleted => {
+                (config.base_weekly_reward * config.completion_multiplier as i128) / 10000
+            },
+            RewardType::StreakBonus => {
+                Self::streak_bonus(&config.streak_curve, streak_weeks).min(config.max_streak_bonus)
+            },
+            RewardType::ReferralBonus => config.referral_reward,
+            RewardType::SocialCheer => 0, // Cheer tips are sized by the challenge contract
+        };
+        
+        // Scale reward based on contribution size (larger contributions get slightly more)
+        let contribution_factor = if contribution_amount >= 100_0000000 { // 100 XLM+
+            12000 // 1.2x
+        } else if contribution_amount >= 50_0000000 { // 50 XLM+
+            11000 // 1.1x
+        } else {
+            10000 // 1.0x
+        };
+        
+        let final_reward = (base_reward * contribution_factor) / 10000;
+        Ok(final_reward)
     }
     
-    /// Get authorized minters
-    pub fn get_minters(env: Env) -> Vec<Address> {
-        env.storage().instance()
-            .get(&DataKey::MinterContracts)
-            .unwrap_or(Vec::new(&env))
+    // ===== STAKING =====
+    
+    /// Lock part of the caller's balance as stake
+    pub fn stake(env: Env, user: Address, amount: i128) -> Result<(), TokenError> {
+        user.require_auth();
+        
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let staked = Self::get_staked(env.clone(), user.clone());
+        if staked + amount > Self::balance(env.clone(), user.clone()) {
+            return Err(TokenError::InsufficientBalance);
+        }
+        
+        Self::add_stake(&env, &user, amount);
+        Ok(())
     }
     
-    /// Check if address is authorized minter
-    pub fn is_minter(env: Env, address: Address) -> bool {
-        let minters: Vec<Address> = env.storage().instance()
+    /// Release previously staked tokens back to the spendable balance
+    pub fn unstake(env: Env, user: Address, amount: i128) -> Result<(), TokenError> {
+        user.require_auth();
+        
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let staked = Self::get_staked(env.clone(), user.clone());
+        if amount > staked {
+            return Err(TokenError::InsufficientStake);
+        }
+        
+        env.storage().persistent().set(&DataKey::Staked(user.clone()), &(staked - amount));
+        
+        env.events().publish(
+            (symbol_short!("unstake"), user),
+            amount
+        );
+        
+        Ok(())
+    }
+    
+    // ===== ADMIN FUNCTIONS =====
+    
+    /// Add authorized minter contract (admin only)
+    pub fn add_minter(env: Env, admin: Address, minter: Address) -> Result<(), TokenError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(TokenError::NotAuthorized);
+        }
+        
+        let mut minters: Vec<Address> = env.storage().instance()
             .get(&DataKey::MinterContracts)
             .unwrap_or(Vec::new(&env));
-        minters.contains(&address)
+        
+        if !minters.contains(&minter) {
+            minters.push_back(minter.clone());
+            env.storage().instance().set(&DataKey::MinterContracts, &minters);
+            
+            env.events().publish(
+                (Symbol::new(&env, "minter_add"), admin),
+                minter
+            );
+        }
+        
+        Ok(())
     }
-}
\ No newline at end of file
+    
+    /// Remove minter contract (admin only)
+    pub fn remove_minter(env: Env, admin: Address, minter: Address) -> Result<(), TokenError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(TokenError::NotAuthorized);
+        }
+        
+        let mut minters: Vec<Address> = env.storage().instance()
+            .get(&DataKey::MinterContracts)
+            .unwrap_or(Vec::new(&env));
+        
+        if let Some(index) = minters.first_index_of(&minter) {
+            minters.remove(index);
+            env.storage().instance().set(&DataKey::MinterContracts, &minters);
+            
+            env.events().publish(
+                (symbol_short!("minter_rm"), admin),
+                minter
+            );
+        }
+        
+        Ok(())
+    }
+    
+    /// Set a minter's emission budget per epoch (admin only)
+    pub fn set_minter_budget(
+        env: Env,
+        admin: Address,
+        minter: Address,
+        amount_per_epoch: i128,
+        epoch_length: u64,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if amount_per_epoch < 0 || epoch_length == 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let budget = MinterBudget {
+            amount_per_epoch,
+            epoch_length,
+            epoch_start: env.ledger().timestamp(),
+            minted_in_epoch: 0,
+        };
+        env.storage().persistent().set(&DataKey::MinterBudget(minter.clone()), &budget);
+        
+        env.events().publish(
+            (symbol_short!("budget"), minter),
+            (amount_per_epoch, epoch_length)
+        );
+        
+        Ok(())
+    }
+    
+    /// Remove a minter's emission budget (admin only)
+    pub fn remove_minter_budget(env: Env, admin: Address, minter: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        env.storage().persistent().remove(&DataKey::MinterBudget(minter.clone()));
+        
+        env.events().publish(
+            (symbol_short!("budget_rm"), minter),
+            ()
+        );
+        
+        Ok(())
+    }
+    
+    /// Grant the freezer role (admin only)
+    pub fn add_freezer(env: Env, admin: Address, freezer: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let mut freezers = Self::get_freezers(env.clone());
+        if !freezers.contains(&freezer) {
+            freezers.push_back(freezer.clone());
+            env.storage().instance().set(&ComplianceKey::Freezers, &freezers);
+            
+            env.events().publish(
+                (symbol_short!("frzr_add"), admin),
+                freezer
+            );
+        }
+        
+        Ok(())
+    }
+    
+    /// Revoke the freezer role (admin only)
+    pub fn remove_freezer(env: Env, admin: Address, freezer: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let mut freezers = Self::get_freezers(env.clone());
+        if let Some(index) = freezers.first_index_of(&freezer) {
+            freezers.remove(index);
+            env.storage().instance().set(&ComplianceKey::Freezers, &freezers);
+            
+            env.events().publish(
+                (symbol_short!("frzr_rm"), admin),
+                freezer
+            );
+        }
+        
+        Ok(())
+    }
+    
+    /// Freeze an account, blocking outgoing transfers and reward claims (freezers only)
+    pub fn freeze(env: Env, freezer: Address, account: Address) -> Result<(), TokenError> {
+        Self::require_freezer(&env, &freezer)?;
+        
+        env.storage().persistent().set(&ComplianceKey::Frozen(account.clone()), &true);
+        
+        env.events().publish(
+            (symbol_short!("freeze"), account),
+            freezer
+        );
+        
+        Ok(())
+    }
+    
+    /// Lift a freeze on an account (freezers only)
+    pub fn unfreeze(env: Env, freezer: Address, account: Address) -> Result<(), TokenError> {
+        Self::require_freezer(&env, &freezer)?;
+        
+        env.storage().persistent().remove(&ComplianceKey::Frozen(account.clone()));
+        
+        env.events().publish(
+            (symbol_short!("unfreeze"), account),
+            freezer
+        );
+        
+        Ok(())
+    }
+    
+    /// Exclude an address from earning further rewards (admin only)
+    pub fn deny_rewards(env: Env, admin: Address, account: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        env.storage().persistent().set(&ComplianceKey::RewardDenied(account.clone()), &true);
+        
+        env.events().publish(
+            (symbol_short!("deny"), account),
+            admin
+        );
+        
+        Ok(())
+    }
+    
+    /// Make a denied address eligible for rewards again (admin only)
+    pub fn allow_rewards(env: Env, admin: Address, account: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        env.storage().persistent().remove(&ComplianceKey::RewardDenied(account.clone()));
+        
+        env.events().publish(
+            (symbol_short!("allow"), account),
+            admin
+        );
+        
+        Ok(())
+    }
+    
+    /// Burn fraudulently earned tokens from an address, recording why (admin only)
+    pub fn clawback(
+        env: Env,
+        admin: Address,
+        from: Address,
+        amount: i128,
+        reason_code: u32,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        // Clawback reaches locked tokens too: release as much stake as it needs
+        let shortfall = amount - Self::spendable_balance(env.clone(), from.clone());
+        if shortfall > 0 {
+            let staked = Self::get_staked(env.clone(), from.clone());
+            env.storage().persistent().set(&DataKey::Staked(from.clone()), &(staked - shortfall.min(staked)));
+        }
+        
+        Self::spend_balance(&env, &from, amount)?;
+        Self::adjust_supply(&env, -amount)?;
+        
+        let record = ClawbackRecord {
+            from: from.clone(),
+            amount,
+            reason_code,
+            timestamp: env.ledger().timestamp(),
+        };
+        let mut history = Self::get_clawback_history(env.clone(), from.clone());
+        history.push_back(record);
+        env.storage().persistent().set(&ComplianceKey::ClawbackHistory(from.clone()), &history);
+        
+        env.events().publish(
+            (symbol_short!("clawback"), admin, from),
+            (amount, reason_code)
+        );
+        
+        Ok(())
+    }
+    
+    /// Nominate a new admin; takes effect once they accept (admin only)
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        env.storage().instance().set(&DataKey::PendingAdmin, &new_admin);
+        
+        env.events().publish(
+            (symbol_short!("adm_prop"), admin),
+            new_admin
+        );
+        
+        Ok(())
+    }
+    
+    /// Accept a pending admin nomination (nominee only)
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), TokenError> {
+        new_admin.require_auth();
+        
+        let pending: Address = env.storage().instance()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(TokenError::NoPendingChange)?;
+        
+        if pending != new_admin {
+            return Err(TokenError::NotAuthorized);
+        }
+        
+        let previous: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotInitialized)?;
+        
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+        
+        env.events().publish(
+            (symbol_short!("adm_acpt"), new_admin),
+            previous
+        );
+        
+        Ok(())
+    }
+    
+    /// Register a contract to be called before and/or after every transfer and merchant spend; its
+    /// cost is not bounded beyond the transaction budget, see HookFailurePolicy (admin only)
+    pub fn set_transfer_hook(
+        env: Env,
+        admin: Address,
+        contract: Address,
+        before: bool,
+        after: bool,
+        failure_policy: HookFailurePolicy,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let hook = TransferHook {
+            contract: contract.clone(),
+            before,
+            after,
+            failure_policy,
+            consecutive_failures: 0,
+            enabled: true,
+        };
+        env.storage().instance().set(&DataKey::TransferHook, &hook);
+        
+        env.events().publish(
+            (symbol_short!("hook_set"), admin),
+            (contract, before, after)
+        );
+        
+        Ok(())
+    }
+    
+    /// Unregister the transfer hook (admin only)
+    pub fn remove_transfer_hook(env: Env, admin: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        env.storage().instance().remove(&DataKey::TransferHook);
+        
+        env.events().publish(
+            (symbol_short!("hook_rm"), admin),
+            ()
+        );
+        
+        Ok(())
+    }
+    
+    /// Update loyalty tier thresholds (admin only)
+    pub fn set_tier_thresholds(
+        env: Env,
+        admin: Address,
+        thresholds: TierThresholds,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if thresholds.bronze <= 0
+            || thresholds.silver <= thresholds.bronze
+            || thresholds.gold <= thresholds.silver
+        {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        env.storage().instance().set(&RewardKey::TierThresholds, &thresholds);
+        
+        env.events().publish(
+            (symbol_short!("tiers"), admin),
+            (thresholds.bronze, thresholds.silver, thresholds.gold)
+        );
+        
+        Ok(())
+    }
+    
+    /// Configure automatic halving of the base weekly reward (admin only)
+    pub fn set_emission_schedule(
+        env: Env,
+        admin: Address,
+        halving_interval: u64,
+        floor_bps: u32,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if floor_bps > 10000 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let schedule = EmissionSchedule {
+            start_time: env.ledger().timestamp(),
+            halving_interval,
+            floor_bps,
+        };
+        env.storage().instance().set(&RewardKey::EmissionSchedule, &schedule);
+        
+        env.events().publish(
+            (symbol_short!("emission"), admin),
+            (halving_interval, floor_bps)
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the fee credit granted per SaveCoin redeemed, in basis points (admin only)
+    pub fn set_discount_rate(env: Env, admin: Address, rate_bps: u32) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        env.storage().instance().set(&SinkKey::DiscountRateBps, &rate_bps);
+        
+        env.events().publish(
+            (symbol_short!("disc_rate"), admin),
+            rate_bps
+        );
+        
+        Ok(())
+    }
+    
+    /// Allow or disallow a contract (e.g. cross-border-yield) to spend discount credits (admin only)
+    pub fn set_discount_consumer(
+        env: Env,
+        admin: Address,
+        consumer: Address,
+        allowed: bool,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let mut consumers = Self::get_discount_consumers(env.clone());
+        let index = consumers.first_index_of(&consumer);
+        
+        match (allowed, index) {
+            (true, None) => consumers.push_back(consumer.clone()),
+            (false, Some(index)) => {
+                consumers.remove(index);
+            },
+            _ => return Ok(()),
+        }
+        env.storage().instance().set(&SinkKey::DiscountConsumers, &consumers);
+        
+        env.events().publish(
+            (symbol_short!("disc_cons"), admin),
+            (consumer, allowed)
+        );
+        
+        Ok(())
+    }
+    
+    /// Create or update the terms of a redemption pool (admin only)
+    pub fn set_redemption_terms(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        rate: i128,
+        epoch_cap: i128,
+        epoch_length: u64,
+        is_active: bool,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if rate < 0 || epoch_cap < 0 || epoch_length == 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let existing: Option<RedemptionPool> = env.storage().persistent()
+            .get(&SinkKey::RedemptionPool(asset.clone()));
+        
+        let pool = match existing {
+            Some(mut pool) => {
+                pool.rate = rate;
+                pool.epoch_cap = epoch_cap;
+                pool.epoch_length = epoch_length;
+                pool.is_active = is_active;
+                pool
+            },
+            None => RedemptionPool {
+                asset: asset.clone(),
+                rate,
+                reserves: 0,
+                epoch_length,
+                epoch_cap,
+                epoch_start: env.ledger().timestamp(),
+                redeemed_in_epoch: 0,
+                is_active,
+            },
+        };
+        env.storage().persistent().set(&SinkKey::RedemptionPool(asset.clone()), &pool);
+        
+        env.events().publish(
+            (symbol_short!("rp_terms"), asset),
+            (rate, epoch_cap, epoch_length, is_active)
+        );
+        
+        Ok(())
+    }
+    
+    /// Withdraw unused redemption reserves back to the treasury (admin only)
+    pub fn withdraw_redemption_reserves(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let mut pool = Self::get_redemption_pool(env.clone(), asset.clone())?;
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        if amount > pool.reserves {
+            return Err(TokenError::InsufficientReserves);
+        }
+        
+        pool.reserves -= amount;
+        env.storage().persistent().set(&SinkKey::RedemptionPool(asset.clone()), &pool);
+        
+        token::Client::new(&env, &asset).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount
+        );
+        
+        env.events().publish(
+            (symbol_short!("rp_wdraw"), asset),
+            (to, amount)
+        );
+        
+        Ok(())
+    }
+    
+    /// Define or update an achievement badge (admin only)
+    pub fn define_badge(
+        env: Env,
+        admin: Address,
+        badge_id: u32,
+        name: String,
+        description: String,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let badge = BadgeInfo {
+            id: badge_id,
+            name: name.clone(),
+            description,
+        };
+        env.storage().persistent().set(&RewardKey::Badge(badge_id), &badge);
+        
+        env.events().publish(
+            (symbol_short!("badge_def"), badge_id),
+            name
+        );
+        
+        Ok(())
+    }
+    
+    /// Cap total reward minting per epoch across all minters (admin only)
+    pub fn set_emission_cap(
+        env: Env,
+        admin: Address,
+        cap_per_epoch: i128,
+        epoch_length: u64,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if cap_per_epoch < 0 || epoch_length == 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let cap = EmissionCap {
+            cap_per_epoch,
+            epoch_length,
+            epoch_start: env.ledger().timestamp(),
+            minted_in_epoch: 0,
+        };
+        env.storage().instance().set(&RewardKey::EmissionCap, &cap);
+        
+        env.events().publish(
+            (symbol_short!("emis_cap"), admin),
+            (cap_per_epoch, epoch_length)
+        );
+        
+        Ok(())
+    }
+    
+    /// Update token name, symbol and branding URIs (admin only)
+    pub fn set_metadata(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        icon_uri: Option<String>,
+        home_uri: Option<String>,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let mut metadata: TokenMetadata = env.storage().instance()
+            .get(&DataKey::Metadata)
+            .ok_or(TokenError::NotInitialized)?;
+        metadata.name = name.clone();
+        metadata.symbol = symbol.clone();
+        env.storage().instance().set(&DataKey::Metadata, &metadata);
+        
+        let uris = TokenUris {
+            icon_uri,
+            home_uri,
+        };
+        env.storage().instance().set(&DataKey::MetadataUris, &uris);
+        
+        env.events().publish(
+            (symbol_short!("metadata"), admin),
+            (name, symbol, uris)
+        );
+        
+        Ok(())
+    }
+    
+    /// Schedule a boosted-reward window for a reward type (admin only)
+    pub fn create_campaign(
+        env: Env,
+        admin: Address,
+        reward_type: RewardType,
+        multiplier_bps: u32,
+        start: u64,
+        end: u64,
+    ) -> Result<u32, TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if multiplier_bps < 10000 || end <= start || end <= env.ledger().timestamp() {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let campaign_id: u32 = env.storage().instance()
+            .get(&RewardKey::NextCampaignId)
+            .unwrap_or(1);
+        
+        let campaign = Campaign {
+            id: campaign_id,
+            reward_type,
+            multiplier_bps,
+            start,
+            end,
+            canceled: false,
+            rewards_boosted: 0,
+            extra_minted: 0,
+        };
+        env.storage().persistent().set(&RewardKey::Campaign(campaign_id), &campaign);
+        env.storage().instance().set(&RewardKey::NextCampaignId, &(campaign_id + 1));
+        
+        // Track live campaigns, dropping any that have already ended
+        let now = env.ledger().timestamp();
+        let mut live = Vec::new(&env);
+        for id in Self::get_live_campaigns(&env).iter() {
+            let existing: Option<Campaign> = env.storage().persistent().get(&RewardKey::Campaign(id));
+            if let Some(existing) = existing {
+                if !existing.canceled && existing.end > now {
+                    live.push_back(id);
+                }
+            }
+        }
+        live.push_back(campaign_id);
+        env.storage().instance().set(&RewardKey::LiveCampaigns, &live);
+        
+        env.events().publish(
+            (symbol_short!("campaign"), campaign_id),
+            (reward_type, multiplier_bps, start, end)
+        );
+        
+        Ok(campaign_id)
+    }
+    
+    /// End a campaign early (admin only)
+    pub fn cancel_campaign(env: Env, admin: Address, campaign_id: u32) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let mut campaign = Self::get_campaign(env.clone(), campaign_id)?;
+        campaign.canceled = true;
+        env.storage().persistent().set(&RewardKey::Campaign(campaign_id), &campaign);
+        
+        env.events().publish(
+            (symbol_short!("camp_end"), campaign_id),
+            admin
+        );
+        
+        Ok(())
+    }
+    
+    /// Let a merchant accept SaveCoin up to a daily limit (admin only)
+    pub fn authorize_merchant(
+        env: Env,
+        admin: Address,
+        merchant: Address,
+        daily_limit: i128,
+        burn_on_spend: bool,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if daily_limit <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        // Keep today's usage when an existing merchant's limit changes
+        let (day_start, spent_today) = match Self::get_merchant(env.clone(), merchant.clone()) {
+            Ok(existing) => {
+                let existing = Self::roll_merchant_day(&env, existing);
+                (existing.day_start, existing.spent_today)
+            },
+            Err(_) => (env.ledger().timestamp(), 0),
+        };
+        
+        let config = Merchant {
+            daily_limit,
+            burn_on_spend,
+            day_start,
+            spent_today,
+        };
+        env.storage().persistent().set(&SinkKey::Merchant(merchant.clone()), &config);
+        
+        env.events().publish(
+            (symbol_short!("merchant"), merchant),
+            (daily_limit, burn_on_spend)
+        );
+        
+        Ok(())
+    }
+    
+    /// Stop a merchant from accepting SaveCoin (admin only)
+    pub fn revoke_merchant(env: Env, admin: Address, merchant: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        env.storage().persistent().remove(&SinkKey::Merchant(merchant.clone()));
+        
+        env.events().publish(
+            (symbol_short!("merch_rm"), merchant),
+            admin
+        );
+        
+        Ok(())
+    }
+    
+    /// Start a new airdrop round from a Merkle root of (user, amount) leaves (admin only)
+    pub fn set_airdrop_root(
+        env: Env,
+        admin: Address,
+        root: BytesN<32>,
+        total: i128,
+    ) -> Result<u32, TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if total <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let round = match Self::get_airdrop(env.clone()) {
+            Ok(previous) => previous.round + 1,
+            Err(_) => 1,
+        };
+        
+        let airdrop = Airdrop {
+            round,
+            root: root.clone(),
+            total,
+            claimed: 0,
+        };
+        env.storage().instance().set(&RewardKey::Airdrop, &airdrop);
+        
+        env.events().publish(
+            (symbol_short!("drop_root"), round),
+            (root, total)
+        );
+        
+        Ok(round)
+    }
+    
+    /// Update reward configuration (admin only)
+    pub fn update_reward_config(
+        env: Env,
+        admin: Address,
+        config: RewardConfig,
+    ) -> Result<(), TokenError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(TokenError::NotAuthorized);
+        }
+        
+        // Curve must start at week 1 and be strictly ascending
+        let mut expected_min = 1u32;
+        for (i, tier) in config.streak_curve.iter().enumerate() {
+            let valid_start = if i == 0 { tier.from_week == 1 } else { tier.from_week >= expected_min };
+            if !valid_start || tier.bonus_per_week < 0 {
+                return Err(TokenError::InvalidStreakCurve);
+            }
+            expected_min = tier.from_week + 1;
+        }
+        
+        env.storage().instance().set(&DataKey::RewardConfig, &config);
+        
+        env.events().publish(
+            (symbol_short!("config"), admin),
+            config.base_weekly_reward
+        );
+        
+        Ok(())
+    }
+    
+    /// Propose raising the max supply; takes effect after the timelock (admin only)
+    pub fn propose_max_supply(
+        env: Env,
+        admin: Address,
+        new_max_supply: i128,
+    ) -> Result<u64, TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if new_max_supply <= Self::max_supply(env.clone()) {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        let effective_at = env.ledger().timestamp() + MAX_SUPPLY_TIMELOCK;
+        let pending = PendingMaxSupply {
+            new_max_supply,
+            effective_at,
+        };
+        env.storage().instance().set(&DataKey::PendingMaxSupply, &pending);
+        
+        env.events().publish(
+            (symbol_short!("cap_prop"), admin),
+            (new_max_supply, effective_at)
+        );
+        
+        Ok(effective_at)
+    }
+    
+    /// Apply a proposed max supply once its timelock has elapsed (admin only)
+    pub fn apply_max_supply(env: Env, admin: Address) -> Result<i128, TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let pending: PendingMaxSupply = env.storage().instance()
+            .get(&DataKey::PendingMaxSupply)
+            .ok_or(TokenError::NoPendingChange)?;
+        
+        if env.ledger().timestamp() < pending.effective_at {
+            return Err(TokenError::TimelockNotElapsed);
+        }
+        
+        env.storage().instance().set(&DataKey::MaxSupply, &pending.new_max_supply);
+        env.storage().instance().remove(&DataKey::PendingMaxSupply);
+        
+        env.events().publish(
+            (symbol_short!("cap_set"), admin),
+            pending.new_max_supply
+        );
+        
+        Ok(pending.new_max_supply)
+    }
+    
+    /// Cancel a pending max supply change (admin only)
+    pub fn cancel_max_supply(env: Env, admin: Address) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if !env.storage().instance().has(&DataKey::PendingMaxSupply) {
+            return Err(TokenError::NoPendingChange);
+        }
+        env.storage().instance().remove(&DataKey::PendingMaxSupply);
+        
+        env.events().publish(
+            (symbol_short!("cap_cncl"), admin),
+            ()
+        );
+        
+        Ok(())
+    }
+    
+    // ===== SNAPSHOTS =====
+    
+    /// Record a point-in-time snapshot of all balances (admin only)
+    pub fn snapshot(env: Env) -> Result<u32, TokenError> {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotInitialized)?;
+        admin.require_auth();
+        
+        Ok(Self::take_snapshot(&env))
+    }
+    
+    /// Get the balance a user held when a snapshot was taken
+    pub fn balance_at(env: Env, user: Address, snapshot_id: u32) -> Result<i128, TokenError> {
+        Self::require_snapshot(&env, snapshot_id)?;
+        
+        let current = Self::balance(env.clone(), user.clone());
+        Ok(Self::value_at(&env, &DataKey::BalanceCheckpoints(user), snapshot_id, current))
+    }
+    
+    /// Get the total supply when a snapshot was taken
+    pub fn total_supply_at(env: Env, snapshot_id: u32) -> Result<i128, TokenError> {
+        Self::require_snapshot(&env, snapshot_id)?;
+        
+        let current = Self::total_supply(env.clone());
+        Ok(Self::value_at(&env, &DataKey::SupplyCheckpoints, snapshot_id, current))
+    }
+    
+    /// Get snapshot details
+    pub fn get_snapshot(env: Env, snapshot_id: u32) -> Result<SnapshotInfo, TokenError> {
+        env.storage().persistent()
+            .get(&DataKey::Snapshot(snapshot_id))
+            .ok_or(TokenError::SnapshotNotFound)
+    }
+    
+    /// Get the id of the most recent snapshot (0 if none)
+    pub fn get_current_snapshot_id(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&DataKey::CurrentSnapshotId)
+            .unwrap_or(0)
+    }
+    
+    // ===== GOVERNANCE =====
+    
+    /// Update governance parameters (admin only)
+    pub fn set_governance_config(
+        env: Env,
+        admin: Address,
+        config: GovernanceConfig,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        if config.voting_period == 0
+            || config.quorum_bps > 10000
+            || config.approval_threshold_bps > 10000
+            || config.proposal_threshold < 0
+        {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        env.storage().instance().set(&GovernanceKey::GovernanceConfig, &config);
+        
+        env.events().publish(
+            (symbol_short!("gov_cfg"), admin),
+            (config.quorum_bps, config.approval_threshold_bps)
+        );
+        
+        Ok(())
+    }
+    
+    /// Allow or disallow proposals calling `function` on `target`. Token contracts, including this
+    /// one, can never be allowed, since a proposal acts with this contract's authority over the
+    /// assets it holds (admin only)
+    pub fn set_proposal_target(
+        env: Env,
+        admin: Address,
+        target: Address,
+        function: Symbol,
+        allowed: bool,
+    ) -> Result<(), TokenError> {
+        Self::require_admin(&env, &admin)?;
+        
+        let key = GovernanceKey::ProposalTarget(target.clone(), function.clone());
+        if allowed {
+            if Self::is_asset_contract(&env, &target) {
+                return Err(TokenError::TargetNotAllowed);
+            }
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        
+        env.events().publish(
+            (symbol_short!("gov_tgt"), admin),
+            (target, function, allowed)
+        );
+        
+        Ok(())
+    }
+    
+    /// Create a proposal that invokes `function(args)` on `target` if it passes
+    pub fn create_proposal(
+        env: Env,
+        proposer: Address,
+        description: String,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+    ) -> Result<u32, TokenError> {
+        proposer.require_auth();
+        
+        let config = Self::get_governance_config(env.clone())?;
+        Self::require_proposal_target(&env, &target, &function)?;
+        
+        if Self::voting_power(&env, &proposer) < config.proposal_threshold {
+            return Err(TokenError::BelowProposalThreshold);
+        }
+        
+        let proposal_id: u32 = env.storage().instance()
+            .get(&GovernanceKey::NextProposalId)
+            .unwrap_or(1);
+        
+        // Freeze voting weights as of proposal creation
+        let snapshot_id = Self::take_snapshot(&env);
+        
+        let start_time = env.ledger().timestamp();
+        let proposal = Proposal {
+            id: proposal_id,
+            proposer: proposer.clone(),
+            description,
+            target: target.clone(),
+            function: function.clone(),
+            args,
+            snapshot_id,
+            start_time,
+            end_time: start_time + config.voting_period,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            executed: false,
+            canceled: false,
+        };
+        
+        env.storage().persistent().set(&GovernanceKey::Proposal(proposal_id), &proposal);
+        env.storage().instance().set(&GovernanceKey::NextProposalId, &(proposal_id + 1));
+        
+        env.events().publish(
+            (symbol_short!("proposed"), proposal_id),
+            (proposer, target, function, snapshot_id)
+        );
+        
+        Ok(proposal_id)
+    }
+    
+    /// Cast a vote weighted by the voter's SaveCoin at the proposal snapshot
+    pub fn vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u32,
+        support: VoteType,
+    ) -> Result<i128, TokenError> {
+        voter.require_auth();
+        
+        let mut proposal: Proposal = env.storage().persistent()
+            .get(&GovernanceKey::Proposal(proposal_id))
+            .ok_or(TokenError::ProposalNotFound)?;
+        
+        if proposal.canceled || env.ledger().timestamp() > proposal.end_time {
+            return Err(TokenError::VotingClosed);
+        }
+        
+        let vote_key = GovernanceKey::Vote(proposal_id, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(TokenError::AlreadyVoted);
+        }
+        
+        let weight = Self::voting_power_at(&env, &voter, proposal.snapshot_id);
+        if weight <= 0 {
+            return Err(TokenError::NoVotingPower);
+        }
+        
+        match support {
+            VoteType::For => proposal.votes_for += weight,
+            VoteType::Against => proposal.votes_against += weight,
+            VoteType::Abstain => proposal.votes_abstain += weight,
+        }
+        
+        env.storage().persistent().set(&vote_key, &support);
+        env.storage().persistent().set(&GovernanceKey::Proposal(proposal_id), &proposal);
+        
+        env.events().publish(
+            (symbol_short!("voted"), proposal_id, voter),
+            (support, weight)
+        );
+        
+        Ok(weight)
+    }
+    
+    /// Execute a succeeded proposal (callable by anyone after voting ends)
+    pub fn execute_proposal(env: Env, proposal_id: u32) -> Result<Val, TokenError> {
+        let mut proposal: Proposal = env.storage().persistent()
+            .get(&GovernanceKey::Proposal(proposal_id))
+            .ok_or(TokenError::ProposalNotFound)?;
+        
+        if Self::get_proposal_state(env.clone(), proposal_id)? != ProposalState::Succeeded {
+            return Err(TokenError::ProposalNotSucceeded);
+        }
+        
+        // The allowlist may have changed since the proposal was created
+        Self::require_proposal_target(&env, &proposal.target, &proposal.function)?;
+        
+        // Mark executed before the external call
+        proposal.executed = true;
+        env.storage().persistent().set(&GovernanceKey::Proposal(proposal_id), &proposal);
+        
+        // The target contract must have this token contract set as its admin
+        let result: Val = env.invoke_contract(&proposal.target, &proposal.function, proposal.args.clone());
+        
+        env.events().publish(
+            (symbol_short!("executed"), proposal_id),
+            (proposal.target, proposal.function)
+        );
+        
+        Ok(result)
+    }
+    
+    /// Cancel a proposal that has not been executed (proposer or admin)
+    pub fn cancel_proposal(env: Env, caller: Address, proposal_id: u32) -> Result<(), TokenError> {
+        caller.require_auth();
+        
+        let mut proposal: Proposal = env.storage().persistent()
+            .get(&GovernanceKey::Proposal(proposal_id))
+            .ok_or(TokenError::ProposalNotFound)?;
+        
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotInitialized)?;
+        
+        if caller != proposal.proposer && caller != admin {
+            return Err(TokenError::NotAuthorized);
+        }
+        
+        if proposal.executed || proposal.canceled {
+            return Err(TokenError::VotingClosed);
+        }
+        
+        proposal.canceled = true;
+        env.storage().persistent().set(&GovernanceKey::Proposal(proposal_id), &proposal);
+        
+        env.events().publish(
+            (symbol_short!("canceled"), proposal_id),
+            caller
+        );
+        
+        Ok(())
+    }
+    
+    /// Get proposal details
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, TokenError> {
+        env.storage().persistent()
+            .get(&GovernanceKey::Proposal(proposal_id))
+            .ok_or(TokenError::ProposalNotFound)
+    }
+    
+    /// Get the lifecycle state of a proposal
+    pub fn get_proposal_state(env: Env, proposal_id: u32) -> Result<ProposalState, TokenError> {
+        let proposal: Proposal = env.storage().persistent()
+            .get(&GovernanceKey::Proposal(proposal_id))
+            .ok_or(TokenError::ProposalNotFound)?;
+        
+        if proposal.canceled {
+            return Ok(ProposalState::Canceled);
+        }
+        if proposal.executed {
+            return Ok(ProposalState::Executed);
+        }
+        if env.ledger().timestamp() <= proposal.end_time {
+            return Ok(ProposalState::Active);
+        }
+        
+        let config = Self::get_governance_config(env.clone())?;
+        let supply = Self::total_supply_at(env.clone(), proposal.snapshot_id)?;
+        
+        let turnout = proposal.votes_for + proposal.votes_against + proposal.votes_abstain;
+        let quorum_reached = turnout * 10000 >= supply * config.quorum_bps as i128;
+        
+        let decisive = proposal.votes_for + proposal.votes_against;
+        let approved = decisive > 0
+            && proposal.votes_for * 10000 > decisive * config.approval_threshold_bps as i128;
+        
+        if quorum_reached && approved {
+            Ok(ProposalState::Succeeded)
+        } else {
+            Ok(ProposalState::Defeated)
+        }
+    }
+    
+    /// Delegate all of the caller's voting power to another address (or back to self)
+    pub fn delegate(env: Env, delegator: Address, to: Address) -> Result<(), TokenError> {
+        delegator.require_auth();
+        
+        let previous = Self::get_delegate(env.clone(), delegator.clone());
+        if previous == to {
+            return Ok(());
+        }
+        
+        if to == delegator {
+            env.storage().persistent().remove(&GovernanceKey::Delegate(delegator.clone()));
+        } else {
+            env.storage().persistent().set(&GovernanceKey::Delegate(delegator.clone()), &to);
+        }
+        
+        let weight = Self::balance(env.clone(), delegator.clone());
+        Self::move_votes(&env, Some(&previous), Some(&to), weight);
+        
+        env.events().publish(
+            (symbol_short!("delegate"), delegator),
+            (previous, to)
+        );
+        
+        Ok(())
+    }
+    
+    /// Get the address an account's voting power is delegated to
+    pub fn get_delegate(env: Env, account: Address) -> Address {
+        env.storage().persistent()
+            .get(&GovernanceKey::Delegate(account.clone()))
+            .unwrap_or(account)
+    }
+    
+    /// Get the current voting power delegated to an address
+    pub fn get_votes(env: Env, account: Address) -> i128 {
+        env.storage().persistent()
+            .get(&GovernanceKey::Votes(account))
+            .unwrap_or(0)
+    }
+    
+    /// Get the voting power delegated to an address as of a snapshot
+    pub fn get_votes_at(env: Env, account: Address, snapshot_id: u32) -> Result<i128, TokenError> {
+        Self::require_snapshot(&env, snapshot_id)?;
+        Ok(Self::voting_power_at(&env, &account, snapshot_id))
+    }
+    
+    /// Get how an address voted on a proposal
+    pub fn get_vote(env: Env, proposal_id: u32, voter: Address) -> Option<VoteType> {
+        env.storage().persistent()
+            .get(&GovernanceKey::Vote(proposal_id, voter))
+    }
+    
+    /// Get current governance parameters
+    pub fn get_governance_config(env: Env) -> Result<GovernanceConfig, TokenError> {
+        env.storage().instance()
+            .get(&GovernanceKey::GovernanceConfig)
+            .ok_or(TokenError::GovernanceNotConfigured)
+    }
+    
+    /// Check whether proposals may call `function` on `target`
+    pub fn is_proposal_target(env: Env, target: Address, function: Symbol) -> bool {
+        env.storage().persistent().has(&GovernanceKey::ProposalTarget(target, function))
+    }
+    
+    // ===== QUERY FUNCTIONS =====
+    
+    /// Get the current admin
+    pub fn get_admin(env: Env) -> Result<Address, TokenError> {
+        env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotInitialized)
+    }
+    
+    /// Get the admin nominee awaiting acceptance, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance()
+            .get(&DataKey::PendingAdmin)
+    }
+    
+    /// Get the hard cap on total supply
+    pub fn max_supply(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&DataKey::MaxSupply)
+            .unwrap_or(i128::MAX)
+    }
+    
+    /// Get the pending max supply change, if any
+    pub fn get_pending_max_supply(env: Env) -> Option<PendingMaxSupply> {
+        env.storage().instance()
+            .get(&DataKey::PendingMaxSupply)
+    }
+    
+    /// Get user's reward history
+    /// Only the most recent MAX_PAGE_SIZE records are returned; use get_reward_history_page for the rest
+    pub fn get_reward_history(env: Env, user: Address) -> Vec<RewardRecord> {
+        let count = Self::get_reward_count(env.clone(), user.clone());
+        let start = count.saturating_sub(MAX_PAGE_SIZE);
+        Self::get_reward_history_page(env, user, start, MAX_PAGE_SIZE)
+    }
+    
+    /// Get a page of a user's reward history, oldest first
+    pub fn get_reward_history_page(env: Env, user: Address, start: u32, limit: u32) -> Vec<RewardRecord> {
+        let count = Self::get_reward_count(env.clone(), user.clone());
+        let end = count.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        
+        let mut page = Vec::new(&env);
+        for index in start..end {
+            let record: Option<RewardRecord> = env.storage().persistent()
+                .get(&DataKey::RewardRecord(user.clone(), index));
+            if let Some(record) = record {
+                page.push_back(record);
+            }
+        }
+        page
+    }
+    
+    /// Get the number of reward records for a user
+    pub fn get_reward_count(env: Env, user: Address) -> u32 {
+        env.storage().persistent()
+            .get(&DataKey::RewardCount(user))
+            .unwrap_or(0)
+    }
+    
+    /// Get rewards accrued to a user but not yet claimed
+    pub fn get_pending_rewards(env: Env, user: Address) -> i128 {
+        env.storage().persistent()
+            .get(&DataKey::PendingRewards(user))
+            .unwrap_or(0)
+    }
+    
+    /// Get a user's total rewards broken down by reward type
+    pub fn get_user_reward_breakdown(env: Env, user: Address) -> Map<RewardType, i128> {
+        env.storage().persistent()
+            .get(&DataKey::UserRewardStats(user))
+            .unwrap_or(Map::new(&env))
+    }
+    
+    /// Get total rewards distributed by type
+    pub fn get_reward_stats(env: Env, reward_type: RewardType) -> i128 {
+        env.storage().persistent()
+            .get(&DataKey::RewardStats(reward_type))
+            .unwrap_or(0)
+    }
+    
+    /// Get total rewards distributed
+    pub fn get_total_rewards(env: Env) -> i128 {
+        env.storage().instance()
+            .get(&DataKey::TotalRewards)
+            .unwrap_or(0)
+    }
+    
+    /// Get current reward configuration
+    pub fn get_reward_config(env: Env) -> Result<RewardConfig, TokenError> {
+        env.storage().instance()
+            .get(&DataKey::RewardConfig)
+            .ok_or(TokenError::RewardConfigNotSet)
+    }
+    
+    /// Get the emission decay schedule, if configured
+    pub fn get_emission_schedule(env: Env) -> Option<EmissionSchedule> {
+        env.storage().instance()
+            .get(&RewardKey::EmissionSchedule)
+    }
+    
+    /// Get the current emission factor in basis points (10000 = no decay)
+    pub fn get_emission_factor(env: Env) -> u32 {
+        let schedule = match Self::get_emission_schedule(env.clone()) {
+            Some(schedule) if schedule.halving_interval > 0 => schedule,
+            _ => return 10000,
+        };
+        
+        let elapsed = env.ledger().timestamp().saturating_sub(schedule.start_time);
+        let halvings = elapsed / schedule.halving_interval;
+        
+        let factor = if halvings >= 32 { 0 } else { 10000u32 >> halvings };
+        factor.max(schedule.floor_bps)
+    }
+    
+    /// Get a user's unused fee discount credit
+    pub fn get_discount_credit(env: Env, user: Address) -> i128 {
+        env.storage().persistent()
+            .get(&SinkKey::DiscountCredit(user))
+            .unwrap_or(0)
+    }
+    
+    /// Get the fee credit granted per SaveCoin redeemed (basis points)
+    pub fn get_discount_rate(env: Env) -> u32 {
+        env.storage().instance()
+            .get(&SinkKey::DiscountRateBps)
+            .unwrap_or(10000)
+    }
+    
+    /// Get contracts allowed to spend discount credits
+    pub fn get_discount_consumers(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&SinkKey::DiscountConsumers)
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get a redemption pool by payout asset
+    pub fn get_redemption_pool(env: Env, asset: Address) -> Result<RedemptionPool, TokenError> {
+        env.storage().persistent()
+            .get(&SinkKey::RedemptionPool(asset))
+            .ok_or(TokenError::PoolNotFound)
+    }
+    
+    /// Get how many times a user has earned a badge
+    pub fn badge_balance(env: Env, user: Address, badge_id: u32) -> u32 {
+        env.storage().persistent()
+            .get(&RewardKey::BadgeBalance(user, badge_id))
+            .unwrap_or(0)
+    }
+    
+    /// Get every badge award a user has received
+    pub fn get_badges(env: Env, user: Address) -> Vec<BadgeAward> {
+        env.storage().persistent()
+            .get(&RewardKey::BadgeAwards(user))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get badge details
+    pub fn get_badge_info(env: Env, badge_id: u32) -> Result<BadgeInfo, TokenError> {
+        env.storage().persistent()
+            .get(&RewardKey::Badge(badge_id))
+            .ok_or(TokenError::BadgeNotFound)
+    }
+    
+    /// Get the address that referred a user, if any
+    pub fn get_referrer(env: Env, user: Address) -> Option<Address> {
+        env.storage().persistent()
+            .get(&RewardKey::Referrer(user))
+    }
+    
+    /// Get how many users a referrer has registered
+    pub fn get_referral_count(env: Env, referrer: Address) -> u32 {
+        env.storage().persistent()
+            .get(&RewardKey::ReferralCount(referrer))
+            .unwrap_or(0)
+    }
+    
+    /// Get the global emission cap for the current epoch (None if uncapped)
+    pub fn get_emission_cap(env: Env) -> Option<EmissionCap> {
+        let cap: Option<EmissionCap> = env.storage().instance()
+            .get(&RewardKey::EmissionCap);
+        cap.map(|cap| Self::roll_cap_epoch(&env, cap))
+    }
+    
+    /// Check whether a minter has already rewarded a dedupe key
+    pub fn is_rewarded(env: Env, minter: Address, dedupe_key: BytesN<32>) -> bool {
+        env.storage().persistent()
+            .has(&DataKey::MintReceipt(minter, dedupe_key))
+    }
+    
+    /// Get a merchant's limit and today's usage
+    pub fn get_merchant(env: Env, merchant: Address) -> Result<Merchant, TokenError> {
+        env.storage().persistent()
+            .get(&SinkKey::Merchant(merchant))
+            .ok_or(TokenError::NotMerchant)
+    }
+    
+    /// Get a spend receipt
+    pub fn get_spend_receipt(env: Env, receipt_id: u32) -> Option<SpendReceipt> {
+        env.storage().persistent().get(&SinkKey::SpendReceipt(receipt_id))
+    }
+    
+    /// Get the current airdrop round
+    pub fn get_airdrop(env: Env) -> Result<Airdrop, TokenError> {
+        env.storage().instance()
+            .get(&RewardKey::Airdrop)
+            .ok_or(TokenError::AirdropNotSet)
+    }
+    
+    /// Check if a user has claimed the current airdrop round
+    pub fn is_airdrop_claimed(env: Env, user: Address) -> bool {
+        match Self::get_airdrop(env.clone()) {
+            Ok(airdrop) => env.storage().persistent().has(&RewardKey::AirdropClaimed(airdrop.round, user)),
+            Err(_) => false,
+        }
+    }
+    
+    /// Get raffle details
+    pub fn get_raffle(env: Env, raffle_id: u32) -> Result<Raffle, TokenError> {
+        env.storage().persistent()
+            .get(&RewardKey::Raffle(raffle_id))
+            .ok_or(TokenError::RaffleNotFound)
+    }
+    
+    /// Get a user's staked balance
+    pub fn get_staked(env: Env, user: Address) -> i128 {
+        env.storage().persistent()
+            .get(&DataKey::Staked(user))
+            .unwrap_or(0)
+    }
+    
+    /// Get campaign details and stats
+    pub fn get_campaign(env: Env, campaign_id: u32) -> Result<Campaign, TokenError> {
+        env.storage().persistent()
+            .get(&RewardKey::Campaign(campaign_id))
+            .ok_or(TokenError::CampaignNotFound)
+    }
+    
+    /// Get the campaign multiplier currently applied to a reward type (10000 = none)
+    pub fn get_campaign_multiplier(env: Env, reward_type: RewardType) -> u32 {
+        match Self::best_campaign(&env, &reward_type) {
+            Some(campaign) => campaign.multiplier_bps,
+            None => 10000,
+        }
+    }
+    
+    /// Get authorized minters
+    pub fn get_minters(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&DataKey::MinterContracts)
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get a minter's emission budget for the current epoch (None if unlimited)
+    pub fn get_minter_budget(env: Env, minter: Address) -> Option<MinterBudget> {
+        let budget: Option<MinterBudget> = env.storage().persistent()
+            .get(&DataKey::MinterBudget(minter));
+        budget.map(|budget| Self::roll_budget_epoch(&env, budget))
+    }
+    
+    /// Get authorized freezers
+    pub fn get_freezers(env: Env) -> Vec<Address> {
+        env.storage().instance()
+            .get(&ComplianceKey::Freezers)
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Check if an account may earn rewards (minters can consult this before minting)
+    pub fn is_reward_eligible(env: Env, account: Address) -> bool {
+        !env.storage().persistent()
+            .get(&ComplianceKey::RewardDenied(account))
+            .unwrap_or(false)
+    }
+    
+    /// Check if an account is frozen
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        env.storage().persistent()
+            .get(&ComplianceKey::Frozen(account))
+            .unwrap_or(false)
+    }
+    
+    /// Get clawbacks applied to an address
+    pub fn get_clawback_history(env: Env, user: Address) -> Vec<ClawbackRecord> {
+        env.storage().persistent()
+            .get(&ComplianceKey::ClawbackHistory(user))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get the registered transfer hook, if any
+    pub fn get_transfer_hook(env: Env) -> Option<TransferHook> {
+        env.storage().instance()
+            .get(&DataKey::TransferHook)
+    }
+    
+    /// Get total rewards a user has earned over their lifetime
+    pub fn get_lifetime_rewards(env: Env, user: Address) -> i128 {
+        env.storage().persistent()
+            .get(&RewardKey::LifetimeRewards(user))
+            .unwrap_or(0)
+    }
+    
+    /// Get a user's loyalty tier based on lifetime rewards
+    pub fn get_tier(env: Env, user: Address) -> LoyaltyTier {
+        let lifetime = Self::get_lifetime_rewards(env.clone(), user);
+        Self::tier_for(&Self::get_tier_thresholds(env), lifetime)
+    }
+    
+    /// Get the top lifetime earners, highest first
+    pub fn get_leaderboard(env: Env, limit: u32) -> Vec<LeaderboardEntry> {
+        let leaderboard: Vec<LeaderboardEntry> = env.storage().persistent()
+            .get(&RewardKey::Leaderboard)
+            .unwrap_or(Vec::new(&env));
+        
+        let end = limit.min(leaderboard.len());
+        leaderboard.slice(0..end)
+    }
+    
+    /// Get loyalty tier thresholds
+    pub fn get_tier_thresholds(env: Env) -> TierThresholds {
+        env.storage().instance()
+            .get(&RewardKey::TierThresholds)
+            .unwrap_or(Self::default_tier_thresholds())
+    }
+    
+    /// Check if address is authorized minter
+    pub fn is_minter(env: Env, address: Address) -> bool {
+        let minters: Vec<Address> = env.storage().instance()
+            .get(&DataKey::MinterContracts)
+            .unwrap_or(Vec::new(&env));
+        minters.contains(&address)
+    }
+    
+    // ===== HELPER FUNCTIONS =====
+    
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), TokenError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(TokenError::NotAuthorized)?;
+        
+        if *admin != stored_admin {
+            return Err(TokenError::NotAuthorized);
+        }
+        
+        Ok(())
+    }
+    
+    /// Invoke the transfer hook for a phase ("before" or "after"), applying its failure policy
+    fn run_transfer_hook(
+        env: &Env,
+        phase: Symbol,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), TokenError> {
+        let mut hook = match Self::get_transfer_hook(env.clone()) {
+            Some(hook) if hook.enabled => hook,
+            _ => return Ok(()),
+        };
+        
+        let (enabled_for_phase, function) = if phase == symbol_short!("before") {
+            (hook.before, Symbol::new(env, "before_transfer"))
+        } else {
+            (hook.after, Symbol::new(env, "after_transfer"))
+        };
+        if !enabled_for_phase {
+            return Ok(());
+        }
+        
+        let args: Vec<Val> = vec![
+            env,
+            env.current_contract_address().into_val(env),
+            from.into_val(env),
+            to.into_val(env),
+            amount.into_val(env),
+        ];
+        
+        if hook.failure_policy == HookFailurePolicy::Revert {
+            // Any failure inside the hook traps and reverts the whole transfer
+            env.invoke_contract::<Val>(&hook.contract, &function, args);
+            return Ok(());
+        }
+        
+        // Skip policy: contain the failure, and switch the hook off if it keeps failing
+        let succeeded = matches!(
+            env.try_invoke_contract::<Val, soroban_sdk::Error>(&hook.contract, &function, args),
+            Ok(Ok(_))
+        );
+        
+        if succeeded {
+            if hook.consecutive_failures > 0 {
+                hook.consecutive_failures = 0;
+                env.storage().instance().set(&DataKey::TransferHook, &hook);
+            }
+        } else {
+            hook.consecutive_failures += 1;
+            if hook.consecutive_failures >= MAX_HOOK_FAILURES {
+                hook.enabled = false;
+            }
+            env.storage().instance().set(&DataKey::TransferHook, &hook);
+            
+            env.events().publish(
+                (symbol_short!("hook_fail"), phase),
+                (hook.contract, hook.consecutive_failures, hook.enabled)
+            );
+        }
+        
+        Ok(())
+    }
+    
+    fn require_freezer(env: &Env, freezer: &Address) -> Result<(), TokenError> {
+        freezer.require_auth();
+        
+        if !Self::get_freezers(env.clone()).contains(freezer) {
+            return Err(TokenError::NotFreezer);
+        }
+        
+        Ok(())
+    }
+    
+    fn require_not_frozen(env: &Env, account: &Address) -> Result<(), TokenError> {
+        if Self::is_frozen(env.clone(), account.clone()) {
+            return Err(TokenError::AccountFrozen);
+        }
+        Ok(())
+    }
+    
+    fn require_proposal_target(env: &Env, target: &Address, function: &Symbol) -> Result<(), TokenError> {
+        if !Self::is_proposal_target(env.clone(), target.clone(), function.clone())
+            || Self::is_asset_contract(env, target)
+        {
+            return Err(TokenError::TargetNotAllowed);
+        }
+        Ok(())
+    }
+    
+    /// Whether an address is this token, a redemption payout asset, or any contract answering the token interface
+    fn is_asset_contract(env: &Env, target: &Address) -> bool {
+        *target == env.current_contract_address()
+            || env.storage().persistent().has(&SinkKey::RedemptionPool(target.clone()))
+            || token::Client::new(env, target).try_decimals().is_ok()
+    }
+    
+    fn require_reward_eligible(env: &Env, account: &Address) -> Result<(), TokenError> {
+        if !Self::is_reward_eligible(env.clone(), account.clone()) {
+            return Err(TokenError::NotEligible);
+        }
+        Ok(())
+    }
+    
+    /// Reject a repeated (minter, dedupe key) pair, otherwise record it
+    fn claim_dedupe_key(env: &Env, minter: &Address, dedupe_key: BytesN<32>) -> Result<(), TokenError> {
+        let key = DataKey::MintReceipt(minter.clone(), dedupe_key);
+        if env.storage().persistent().has(&key) {
+            return Err(TokenError::AlreadyRewarded);
+        }
+        env.storage().persistent().set(&key, &true);
+        Ok(())
+    }
+    
+    fn require_minter(env: &Env, minter: &Address) -> Result<(), TokenError> {
+        let minters: Vec<Address> = env.storage().instance()
+            .get(&DataKey::MinterContracts)
+            .unwrap_or(Vec::new(env));
+        
+        if !minters.contains(minter) {
+            return Err(TokenError::NotMinter);
+        }
+        
+        Ok(())
+    }
+    
+    /// Append to the user's reward history and update distribution statistics
+    fn record_reward(
+        env: &Env,
+        to: &Address,
+        amount: i128,
+        reward_type: RewardType,
+        challenge_id: u32,
+        multiplier: u32,
+    ) {
+        let reward_record = RewardRecord {
+            recipient: to.clone(),
+            amount,
+            reward_type,
+            challenge_id,
+            timestamp: env.ledger().timestamp(),
+            multiplier,
+        };
+        
+        // Add to user's reward history
+        let index = Self::get_reward_count(env.clone(), to.clone());
+        env.storage().persistent().set(&DataKey::RewardRecord(to.clone(), index), &reward_record);
+        env.storage().persistent().set(&DataKey::RewardCount(to.clone()), &(index + 1));
+        
+        // Update statistics
+        let current_type_total: i128 = env.storage().persistent()
+            .get(&DataKey::RewardStats(reward_type))
+            .unwrap_or(0);
+        env.storage().persistent().set(&DataKey::RewardStats(reward_type), &(current_type_total + amount));
+        
+        let mut user_stats = Self::get_user_reward_breakdown(env.clone(), to.clone());
+        let user_type_total = user_stats.get(reward_type).unwrap_or(0);
+        user_stats.set(reward_type, user_type_total + amount);
+        env.storage().persistent().set(&DataKey::UserRewardStats(to.clone()), &user_stats);
+        
+        let total_rewards: i128 = env.storage().instance()
+            .get(&DataKey::TotalRewards)
+            .unwrap_or(0);
+        env.storage().instance().set(&DataKey::TotalRewards, &(total_rewards + amount));
+        
+        // Update lifetime total and announce tier promotions
+        let lifetime_before = Self::get_lifetime_rewards(env.clone(), to.clone());
+        let lifetime_after = lifetime_before + amount;
+        env.storage().persistent().set(&RewardKey::LifetimeRewards(to.clone()), &lifetime_after);
+        Self::update_leaderboard(env, to, lifetime_after);
+        
+        let thresholds = Self::get_tier_thresholds(env.clone());
+        let tier_before = Self::tier_for(&thresholds, lifetime_before);
+        let tier_after = Self::tier_for(&thresholds, lifetime_after);
+        if tier_after > tier_before {
+            env.events().publish(
+                (symbol_short!("tier_up"), to.clone()),
+                tier_after
+            );
+        }
+        
+        env.events().publish(
+            (symbol_short!("reward"), challenge_id),
+            (to.clone(), amount, reward_type)
+        );
+    }
+    
+    /// Mint the configured referral reward to a referee's referrer, at most once per referee
+    fn pay_referral_bonus(
+        env: &Env,
+        minter: &Address,
+        referee: &Address,
+        challenge_id: u32,
+    ) -> Result<(), TokenError> {
+        let referrer = match Self::get_referrer(env.clone(), referee.clone()) {
+            Some(referrer) => referrer,
+            None => return Ok(()),
+        };
+        
+        if env.storage().persistent().has(&RewardKey::ReferralPaid(referee.clone())) {
+            return Ok(());
+        }
+        
+        // A denied referrer forfeits the bonus without failing the referee's reward
+        if !Self::is_reward_eligible(env.clone(), referrer.clone()) {
+            return Ok(());
+        }
+        
+        let config = Self::get_reward_config(env.clone())?;
+        let bonus = config.referral_reward;
+        if bonus <= 0 {
+            return Ok(());
+        }
+        
+        Self::consume_minter_budget(env, minter, bonus)?;
+        
+        env.storage().persistent().set(&RewardKey::ReferralPaid(referee.clone()), &true);
+        
+        Self::issue_reward(env, minter, &referrer, bonus)?;
+        Self::record_reward(env, &referrer, bonus, RewardType::ReferralBonus, challenge_id, 10000);
+        
+        Ok(())
+    }
+    
+    /// Re-rank a user on the bounded top-earners leaderboard
+    fn update_leaderboard(env: &Env, user: &Address, lifetime_rewards: i128) {
+        let mut leaderboard: Vec<LeaderboardEntry> = env.storage().persistent()
+            .get(&RewardKey::Leaderboard)
+            .unwrap_or(Vec::new(env));
+        
+        // Skip the write entirely if the user can't make the board
+        let is_full = leaderboard.len() >= LEADERBOARD_SIZE;
+        if is_full {
+            if let Some(last) = leaderboard.last() {
+                if last.user != *user && lifetime_rewards <= last.lifetime_rewards {
+                    return;
+                }
+            }
+        }
+        
+        // Drop the user's old entry
+        for i in 0..leaderboard.len() {
+            if leaderboard.get(i).unwrap().user == *user {
+                leaderboard.remove(i);
+                break;
+            }
+        }
+        
+        // Insert after every entry with an equal or higher total
+        let mut position = leaderboard.len();
+        for i in 0..leaderboard.len() {
+            if leaderboard.get(i).unwrap().lifetime_rewards < lifetime_rewards {
+                position = i;
+                break;
+            }
+        }
+        leaderboard.insert(position, LeaderboardEntry {
+            user: user.clone(),
+            lifetime_rewards,
+        });
+        
+        while leaderboard.len() > LEADERBOARD_SIZE {
+            leaderboard.pop_back();
+        }
+        
+        env.storage().persistent().set(&RewardKey::Leaderboard, &leaderboard);
+    }
+    
+    fn tier_for(thresholds: &TierThresholds, lifetime: i128) -> LoyaltyTier {
+        if lifetime >= thresholds.gold {
+            LoyaltyTier::Gold
+        } else if lifetime >= thresholds.silver {
+            LoyaltyTier::Silver
+        } else if lifetime >= thresholds.bronze {
+            LoyaltyTier::Bronze
+        } else {
+            LoyaltyTier::None
+        }
+    }
+    
+    fn default_tier_thresholds() -> TierThresholds {
+        TierThresholds {
+            bronze: 100_0000000,      // 100 SaveCoin
+            silver: 1_000_0000000,    // 1,000 SaveCoin
+            gold: 10_000_0000000,     // 10,000 SaveCoin
+        }
+    }
+    
+    /// Sum the per-week bonus across each tier of the streak curve the streak reaches
+    fn streak_bonus(curve: &Vec<StreakTier>, streak_weeks: u32) -> i128 {
+        let mut bonus: i128 = 0;
+        
+        for i in 0..curve.len() {
+            let tier = curve.get(i).unwrap();
+            if streak_weeks < tier.from_week {
+                break;
+            }
+            
+            let tier_end = match curve.get(i + 1) {
+                Some(next) => (next.from_week - 1).min(streak_weeks),
+                None => streak_weeks,
+            };
+            bonus += (tier_end - tier.from_week + 1) as i128 * tier.bonus_per_week;
+        }
+        
+        bonus
+    }
+    
+    fn get_live_campaigns(env: &Env) -> Vec<u32> {
+        env.storage().instance()
+            .get(&RewardKey::LiveCampaigns)
+            .unwrap_or(Vec::new(env))
+    }
+    
+    /// The running campaign with the highest multiplier for a reward type
+    fn best_campaign(env: &Env, reward_type: &RewardType) -> Option<Campaign> {
+        let now = env.ledger().timestamp();
+        let mut best: Option<Campaign> = None;
+        
+        for id in Self::get_live_campaigns(env).iter() {
+            let campaign: Option<Campaign> = env.storage().persistent().get(&RewardKey::Campaign(id));
+            if let Some(campaign) = campaign {
+                let running = !campaign.canceled && campaign.start <= now && now < campaign.end;
+                let better = match &best {
+                    Some(current) => campaign.multiplier_bps > current.multiplier_bps,
+                    None => true,
+                };
+                if running && campaign.reward_type == *reward_type && better {
+                    best = Some(campaign);
+                }
+            }
+        }
+        
+        best
+    }
+    
+    /// Boost a reward by the best running campaign and record the campaign's stats
+    fn apply_campaign(env: &Env, reward_type: &RewardType, amount: i128) -> i128 {
+        let mut campaign = match Self::best_campaign(env, reward_type) {
+            Some(campaign) => campaign,
+            None => return amount,
+        };
+        
+        let boosted = (amount * campaign.multiplier_bps as i128) / 10000;
+        campaign.rewards_boosted += 1;
+        campaign.extra_minted += boosted - amount;
+        env.storage().persistent().set(&RewardKey::Campaign(campaign.id), &campaign);
+        
+        boosted
+    }
+    
+    /// Mint a reward up to the global epoch cap, queueing any excess as pending
+    fn issue_reward(env: &Env, minter: &Address, to: &Address, amount: i128) -> Result<i128, TokenError> {
+        let minted = Self::take_emission_allowance(env, amount);
+        let deferred = amount - minted;
+        
+        if minted > 0 {
+            Self::receive_balance(env, to, minted);
+            Self::adjust_supply(env, minted)?;
+            
+            env.events().publish(
+                (symbol_short!("mint"), minter.clone(), to.clone()),
+                minted
+            );
+        }
+        
+        if deferred > 0 {
+            let pending = Self::get_pending_rewards(env.clone(), to.clone());
+            env.storage().persistent().set(&DataKey::PendingRewards(to.clone()), &(pending + deferred));
+            
+            env.events().publish(
+                (symbol_short!("deferred"), to.clone()),
+                deferred
+            );
+        }
+        
+        Ok(minted)
+    }
+    
+    fn add_stake(env: &Env, user: &Address, amount: i128) {
+        let staked = Self::get_staked(env.clone(), user.clone());
+        env.storage().persistent().set(&DataKey::Staked(user.clone()), &(staked + amount));
+        
+        env.events().publish(
+            (symbol_short!("stake"), user.clone()),
+            amount
+        );
+    }
+    
+    /// Reserve up to `amount` of this epoch's global emission, returning what was granted
+    fn take_emission_allowance(env: &Env, amount: i128) -> i128 {
+        let mut cap = match Self::get_emission_cap(env.clone()) {
+            Some(cap) => cap,
+            None => return amount,
+        };
+        
+        let granted = amount.min(cap.cap_per_epoch - cap.minted_in_epoch).max(0);
+        if granted > 0 {
+            cap.minted_in_epoch += granted;
+            env.storage().instance().set(&RewardKey::EmissionCap, &cap);
+        }
+        granted
+    }
+    
+    fn roll_cap_epoch(env: &Env, mut cap: EmissionCap) -> EmissionCap {
+        let now = env.ledger().timestamp();
+        if now >= cap.epoch_start + cap.epoch_length {
+            let elapsed_epochs = (now - cap.epoch_start) / cap.epoch_length;
+            cap.epoch_start += elapsed_epochs * cap.epoch_length;
+            cap.minted_in_epoch = 0;
+        }
+        cap
+    }
+    
+    /// Move a budget forward to the epoch containing the current ledger time
+    fn roll_budget_epoch(env: &Env, mut budget: MinterBudget) -> MinterBudget {
+        let now = env.ledger().timestamp();
+        if now >= budget.epoch_start + budget.epoch_length {
+            let elapsed_epochs = (now - budget.epoch_start) / budget.epoch_length;
+            budget.epoch_start += elapsed_epochs * budget.epoch_length;
+            budget.minted_in_epoch = 0;
+        }
+        budget
+    }
+    
+    fn roll_merchant_day(env: &Env, mut merchant: Merchant) -> Merchant {
+        let now = env.ledger().timestamp();
+        if now >= merchant.day_start + DAY_IN_SECONDS {
+            let elapsed_days = (now - merchant.day_start) / DAY_IN_SECONDS;
+            merchant.day_start += elapsed_days * DAY_IN_SECONDS;
+            merchant.spent_today = 0;
+        }
+        merchant
+    }
+    
+    /// Read one raffle entry by its index
+    fn raffle_entry(env: &Env, raffle_id: u32, index: u32) -> RaffleEntry {
+        env.storage().persistent()
+            .get(&RewardKey::RaffleEntry(raffle_id, index))
+            .unwrap()
+    }
+    
+    /// Charge a mint against the minter's budget; minters without a budget are unlimited
+    fn consume_minter_budget(env: &Env, minter: &Address, amount: i128) -> Result<(), TokenError> {
+        let budget: Option<MinterBudget> = env.storage().persistent()
+            .get(&DataKey::MinterBudget(minter.clone()));
+        
+        if let Some(budget) = budget {
+            let mut budget = Self::roll_budget_epoch(env, budget);
+            if budget.minted_in_epoch + amount > budget.amount_per_epoch {
+                return Err(TokenError::MinterBudgetExceeded);
+            }
+            budget.minted_in_epoch += amount;
+            env.storage().persistent().set(&DataKey::MinterBudget(minter.clone()), &budget);
+        }
+        
+        Ok(())
+    }
+    
+    fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+        let allowance: Option<AllowanceValue> = env.storage().temporary()
+            .get(&DataKey::Allowance(from.clone(), spender.clone()));
+        
+        match allowance {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            Some(allowance) => AllowanceValue {
+                amount: 0,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+            None => AllowanceValue {
+                amount: 0,
+                expiration_ledger: 0,
+            },
+        }
+    }
+    
+    fn write_allowance(env: &Env, from: &Address, spender: &Address, amount: i128, expiration_ledger: u32) {
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        let allowance = AllowanceValue {
+            amount,
+            expiration_ledger,
+        };
+        env.storage().temporary().set(&key, &allowance);
+        
+        // Keep the entry alive exactly as long as the approval is valid
+        if amount > 0 {
+            let live_for = expiration_ledger - env.ledger().sequence();
+            env.storage().temporary().extend_ttl(&key, live_for, live_for);
+        }
+    }
+    
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) -> Result<(), TokenError> {
+        let allowance = Self::read_allowance(env, from, spender);
+        if allowance.amount < amount {
+            return Err(TokenError::InsufficientAllowance);
+        }
+        
+        if amount > 0 {
+            Self::write_allowance(env, from, spender, allowance.amount - amount, allowance.expiration_ledger);
+        }
+        
+        Ok(())
+    }
+    
+    fn spend_balance(env: &Env, addr: &Address, amount: i128) -> Result<(), TokenError> {
+        if Self::spendable_balance(env.clone(), addr.clone()) < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+        
+        let balance = Self::balance(env.clone(), addr.clone());
+        
+        Self::write_checkpoint(env, &DataKey::BalanceCheckpoints(addr.clone()), balance);
+        env.storage().persistent().set(&DataKey::Balance(addr.clone()), &(balance - amount));
+        
+        let delegatee = Self::get_delegate(env.clone(), addr.clone());
+        Self::move_votes(env, Some(&delegatee), None, amount);
+        Ok(())
+    }
+    
+    fn receive_balance(env: &Env, addr: &Address, amount: i128) {
+        let balance = Self::balance(env.clone(), addr.clone());
+        Self::write_checkpoint(env, &DataKey::BalanceCheckpoints(addr.clone()), balance);
+        env.storage().persistent().set(&DataKey::Balance(addr.clone()), &(balance + amount));
+        
+        let delegatee = Self::get_delegate(env.clone(), addr.clone());
+        Self::move_votes(env, None, Some(&delegatee), amount);
+    }
+    
+    fn adjust_supply(env: &Env, delta: i128) -> Result<(), TokenError> {
+        let mut metadata: TokenMetadata = env.storage().instance()
+            .get(&DataKey::Metadata)
+            .ok_or(TokenError::NotInitialized)?;
+        Self::write_checkpoint(env, &DataKey::SupplyCheckpoints, metadata.total_supply);
+        metadata.total_supply += delta;
+        
+        if delta > 0 && metadata.total_supply > Self::max_supply(env.clone()) {
+            return Err(TokenError::MaxSupplyExceeded);
+        }
+        
+        env.storage().instance().set(&DataKey::Metadata, &metadata);
+        Ok(())
+    }
+    
+    /// Current voting power of an address
+    fn voting_power(env: &Env, account: &Address) -> i128 {
+        Self::get_votes(env.clone(), account.clone())
+    }
+    
+    /// Voting power of an address as of a snapshot
+    fn voting_power_at(env: &Env, account: &Address, snapshot_id: u32) -> i128 {
+        let current = Self::get_votes(env.clone(), account.clone());
+        Self::value_at(env, &GovernanceKey::VoteCheckpoints(account.clone()), snapshot_id, current)
+    }
+    
+    /// Shift voting power between delegatees, checkpointing both sides
+    fn move_votes(env: &Env, from: Option<&Address>, to: Option<&Address>, amount: i128) {
+        if amount == 0 {
+            return;
+        }
+        
+        if let Some(from) = from {
+            let votes = Self::get_votes(env.clone(), from.clone());
+            Self::write_checkpoint(env, &GovernanceKey::VoteCheckpoints(from.clone()), votes);
+            env.storage().persistent().set(&GovernanceKey::Votes(from.clone()), &(votes - amount));
+        }
+        
+        if let Some(to) = to {
+            let votes = Self::get_votes(env.clone(), to.clone());
+            Self::write_checkpoint(env, &GovernanceKey::VoteCheckpoints(to.clone()), votes);
+            env.storage().persistent().set(&GovernanceKey::Votes(to.clone()), &(votes + amount));
+        }
+    }
+    
+    fn take_snapshot(env: &Env) -> u32 {
+        let snapshot_id = Self::get_current_snapshot_id(env.clone()) + 1;
+        env.storage().instance().set(&DataKey::CurrentSnapshotId, &snapshot_id);
+        
+        let info = SnapshotInfo {
+            id: snapshot_id,
+            ledger: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&DataKey::Snapshot(snapshot_id), &info);
+        
+        env.events().publish(
+            (symbol_short!("snapshot"), snapshot_id),
+            info.ledger
+        );
+        
+        snapshot_id
+    }
+    
+    fn require_snapshot(env: &Env, snapshot_id: u32) -> Result<(), TokenError> {
+        if snapshot_id == 0 || snapshot_id > Self::get_current_snapshot_id(env.clone()) {
+            return Err(TokenError::SnapshotNotFound);
+        }
+        Ok(())
+    }
+    
+    /// Save the pre-change value the first time it changes after the latest snapshot
+    fn write_checkpoint<K: IntoVal<Env, Val>>(env: &Env, key: &K, current_value: i128) {
+        let snapshot_id = Self::get_current_snapshot_id(env.clone());
+        if snapshot_id == 0 {
+            return;
+        }
+        
+        let mut checkpoints: Vec<Checkpoint> = env.storage().persistent()
+            .get(key)
+            .unwrap_or(Vec::new(env));
+        
+        let needs_checkpoint = match checkpoints.last() {
+            Some(last) => last.snapshot_id < snapshot_id,
+            None => true,
+        };
+        
+        if needs_checkpoint {
+            checkpoints.push_back(Checkpoint {
+                snapshot_id,
+                value: current_value,
+            });
+            env.storage().persistent().set(key, &checkpoints);
+        }
+    }
+    
+    /// Find the value at a snapshot: the first checkpoint at or after it, else the current value
+    fn value_at<K: IntoVal<Env, Val>>(env: &Env, key: &K, snapshot_id: u32, current_value: i128) -> i128 {
+        let checkpoints: Vec<Checkpoint> = env.storage().persistent()
+            .get(key)
+            .unwrap_or(Vec::new(env));
+        
+        let mut low: u32 = 0;
+        let mut high: u32 = checkpoints.len();
+        while low < high {
+            let mid = (low + high) / 2;
+            if checkpoints.get(mid).unwrap().snapshot_id < snapshot_id {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        
+        match checkpoints.get(low) {
+            Some(checkpoint) => checkpoint.value,
+            None => current_value,
+        }
+    }
+}
+
+mod test;
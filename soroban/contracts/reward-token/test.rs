@@ -0,0 +1,158 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger};
+
+struct Setup<'a> {
+    env: Env,
+    client: SaveCoinTokenClient<'a>,
+    admin: Address,
+    minter: Address,
+    user: Address,
+}
+
+fn setup<'a>(max_supply: i128) -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    let contract_id = env.register_contract(None, SaveCoinToken);
+    let client = SaveCoinTokenClient::new(&env, &contract_id);
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "SaveCoin"),
+        &String::from_str(&env, "SAVE"),
+        &7,
+        &max_supply,
+    );
+    client.add_minter(&admin, &minter);
+
+    Setup {
+        env,
+        client,
+        admin,
+        minter,
+        user,
+    }
+}
+
+fn mint(s: &Setup, amount: i128, multiplier: u32) -> Result<(), TokenError> {
+    match s.client.try_mint_reward(
+        &s.minter,
+        &s.user,
+        &amount,
+        &RewardType::WeeklyContribution,
+        &1,
+        &multiplier,
+        &None,
+        &false,
+    ) {
+        Ok(_) => Ok(()),
+        Err(Ok(error)) => Err(error),
+        Err(Err(_)) => panic!("mint_reward failed to convert its error"),
+    }
+}
+
+#[test]
+fn mint_reward_applies_multiplier() {
+    let s = setup(1_000_000);
+
+    mint(&s, 100, 15_000).unwrap();
+    assert_eq!(s.client.balance(&s.user), 150);
+    assert_eq!(s.client.total_supply(), 150);
+}
+
+#[test]
+fn mint_reward_rejects_non_minters_bad_amounts_and_denied_accounts() {
+    let s = setup(1_000_000);
+    let outsider = Address::generate(&s.env);
+
+    assert_eq!(
+        s.client.try_mint_reward(&outsider, &s.user, &100, &RewardType::WeeklyContribution, &1, &10_000, &None, &false),
+        Err(Ok(TokenError::NotMinter))
+    );
+    assert_eq!(mint(&s, 0, 10_000), Err(TokenError::InvalidAmount));
+
+    s.client.deny_rewards(&s.admin, &s.user);
+    assert_eq!(mint(&s, 100, 10_000), Err(TokenError::NotEligible));
+    assert_eq!(s.client.total_supply(), 0);
+}
+
+#[test]
+fn mint_reward_rejects_repeated_dedupe_key() {
+    let s = setup(1_000_000);
+    let key = Some(BytesN::from_array(&s.env, &[7; 32]));
+
+    s.client.mint_reward(&s.minter, &s.user, &100, &RewardType::StreakBonus, &1, &10_000, &key, &false);
+    assert_eq!(
+        s.client.try_mint_reward(&s.minter, &s.user, &100, &RewardType::StreakBonus, &1, &10_000, &key, &false),
+        Err(Ok(TokenError::AlreadyRewarded))
+    );
+    assert_eq!(s.client.balance(&s.user), 100);
+}
+
+#[test]
+fn mint_reward_stops_at_max_supply() {
+    let s = setup(1_000);
+
+    mint(&s, 1_000, 10_000).unwrap();
+    assert_eq!(mint(&s, 1, 10_000), Err(TokenError::MaxSupplyExceeded));
+    assert_eq!(s.client.total_supply(), 1_000);
+}
+
+#[test]
+fn minter_budget_caps_each_epoch() {
+    let s = setup(1_000_000);
+    s.client.set_minter_budget(&s.admin, &s.minter, &100, &500);
+
+    // The budget is charged after the multiplier
+    mint(&s, 40, 15_000).unwrap();
+    assert_eq!(s.client.get_minter_budget(&s.minter).unwrap().minted_in_epoch, 60);
+    assert_eq!(mint(&s, 30, 15_000), Err(TokenError::MinterBudgetExceeded));
+    mint(&s, 40, 10_000).unwrap();
+    assert_eq!(mint(&s, 1, 10_000), Err(TokenError::MinterBudgetExceeded));
+    assert_eq!(s.client.balance(&s.user), 100);
+
+    // A new epoch restores the full budget
+    s.env.ledger().set_timestamp(1_500);
+    mint(&s, 100, 10_000).unwrap();
+    assert_eq!(s.client.balance(&s.user), 200);
+
+    s.client.remove_minter_budget(&s.admin, &s.minter);
+    mint(&s, 1_000, 10_000).unwrap();
+    assert_eq!(s.client.balance(&s.user), 1_200);
+}
+
+#[test]
+fn emission_cap_defers_the_excess_until_a_later_epoch() {
+    let s = setup(1_000_000);
+    s.client.set_emission_cap(&s.admin, &100, &500);
+
+    mint(&s, 150, 10_000).unwrap();
+    assert_eq!(s.client.balance(&s.user), 100);
+    assert_eq!(s.client.get_pending_rewards(&s.user), 50);
+    assert_eq!(s.client.total_supply(), 100);
+
+    // Nothing left this epoch
+    assert_eq!(s.client.claim_rewards(&s.user), 0);
+
+    s.env.ledger().set_timestamp(1_500);
+    assert_eq!(s.client.claim_rewards(&s.user), 50);
+    assert_eq!(s.client.balance(&s.user), 150);
+    assert_eq!(s.client.get_pending_rewards(&s.user), 0);
+}
+
+#[test]
+fn auto_stake_locks_only_what_was_minted() {
+    let s = setup(1_000_000);
+    s.client.set_emission_cap(&s.admin, &100, &500);
+
+    s.client.mint_reward(&s.minter, &s.user, &150, &RewardType::ChallengeCompleted, &1, &10_000, &None, &true);
+    assert_eq!(s.client.get_staked(&s.user), 100);
+    assert_eq!(s.client.get_pending_rewards(&s.user), 50);
+}
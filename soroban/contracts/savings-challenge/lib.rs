@@ -2,18 +2,121 @@
 // StellarSave Savings Challenge Smart Contract
 
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short,
-    Address, Env, Map, String, Symbol, Vec, log
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token, vec, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec, log
 };
 
+// ===== EXTERNAL CONTRACTS =====
+
+/// Share-based yield vault that no-loss prize challenges park contributions in
+#[contractclient(name = "YieldVaultClient")]
+pub trait YieldVaultInterface {
+    /// Pull `amount` of the vault asset from `from`, returning shares minted
+    fn deposit(env: Env, from: Address, amount: i128) -> i128;
+    /// Burn `shares` and send the underlying assets to `to`, returning the amount sent
+    fn redeem(env: Env, to: Address, shares: i128) -> i128;
+}
+
+/// Exchange rate feed (the cross-border-yield contract) used to value other assets
+#[contractclient(name = "RateOracleClient")]
+pub trait RateOracleInterface {
+    /// Rate for a pair such as "XLM-USDC", scaled so RATE_SCALE is 1:1
+    fn get_exchange_rate(env: Env, currency_pair: String) -> i128;
+}
+
+/// Mirrors the SaveCoin reward types this contract mints with
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RewardType {
+    WeeklyContribution = 1,
+    MilestoneReached = 2,
+    ChallengeCompleted = 3,
+    StreakBonus = 4,
+    SocialCheer = 6,
+}
+
+/// Subset of the SaveCoin token interface used for rewards and achievement badges
+#[contractclient(name = "SaveCoinClient")]
+pub trait SaveCoinInterface {
+    fn calculate_reward(env: Env, contribution_amount: i128, reward_type: RewardType, streak_weeks: u32) -> i128;
+    fn mint_reward(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128,
+        reward_type: RewardType,
+        challenge_id: u32,
+        multiplier: u32,
+        dedupe_key: Option<BytesN<32>>,
+        auto_stake: bool,
+    );
+    fn mint_badge(env: Env, minter: Address, to: Address, badge_id: u32, challenge_id: u32) -> u32;
+    fn badge_balance(env: Env, user: Address, badge_id: u32) -> u32;
+    fn get_staked(env: Env, user: Address) -> i128;
+}
+
 // ===== DATA STRUCTURES =====
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Visibility {
+    Open,        // Anyone can join
+    InviteOnly,  // Joining requires an invite code from the creator
+    Closed,      // Participants fixed at creation
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChallengeMode {
+    Standard,    // Everyone saves toward the goal and gets their own money back
+    Rotating,    // ROSCA: each week's pot pays out to one participant in turn
+    NoLossPrize(u32), // Principal earns yield; the yield goes to this many consistent savers
+    Quorum(u32),      // Standard, but rewards unlock only once this many bps of participants reach their targets
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChallengeType {
+    Fixed,             // weekly_amount every week
+    Incremental(i128), // weekly_amount in week 1, then this much more each week (e.g. +5 XLM)
+    Percentage(u32),   // This many bps of each participant's declared weekly income, at least weekly_amount
+    Custom(Vec<i128>), // Amount due in each week, set by the creator
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChallengeStatus {
+    Active,      // Still running and before its deadline
+    Completed,   // Over, with the goal reached
+    Expired,     // Over short of the goal, or cancelled
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompletionTier {
+    Completed,   // Saved the whole personal target: full completion reward
+    Most,        // At least 80% of it: 60% of the reward
+    Half,        // At least 50% of it: 25% of the reward
+    Incomplete,  // Less than half: no completion reward
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SocialFeatures {
+    pub public_leaderboard: bool,    // Show participant rankings publicly
+    pub enable_cheering: bool,       // Allow participants to cheer each other
+    pub allow_group_milestone: bool, // Track group milestones
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SavingsChallenge {
     pub id: u32,
     pub creator: Address,
+    pub token: Address,           // Asset contributions are escrowed in
     pub name: String,
     pub description: String,
     pub goal_amount: i128,
@@ -21,10 +124,73 @@ pub struct SavingsChallenge {
     pub current_amount: i128,
     pub participants: Vec<Address>,
     pub created_at: u64,
+    pub start_time: u64,          // Weeks count from here; the creator can edit the plan until then
     pub deadline: u64,
     pub is_active: bool,
+    pub finalized: bool,          // Settled by close_challenge; payouts open only once this is set
     pub min_weekly_required: bool,
     pub allow_early_withdrawal: bool,
+    pub visibility: Visibility,
+    pub max_participants: u32,    // 0 = no cap
+    pub mode: ChallengeMode,
+    pub missed_week_penalty: i128, // Taken from skippers when min_weekly_required is set
+    pub streak_freezes: u32,      // Missed weeks each participant can skip without losing their streak
+    pub streak_gap: u32,          // Empty weeks allowed between counted weeks before a streak is at risk
+    pub previous_challenge: Option<u32>, // Challenge this one restarted
+    pub early_withdrawal_penalty_bps: u32, // Kept from early withdrawals for the group bonus pot
+    pub category: Symbol,         // e.g. emergency, school_fee
+    pub tags: Vec<Symbol>,
+    pub challenge_type: ChallengeType,
+    pub social_features: SocialFeatures,
+    pub reward_bps: u32,          // SaveCoin completion reward, in bps of the personal target
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Resolution {
+    Pending,     // Not ruled on yet: payouts stay frozen
+    Release,     // Dispute dismissed: payouts go ahead as normal
+    RefundAll,   // Everyone gets back what they put in, less any penalties already donated
+    Partial(u32), // Only this many bps of penalties, bonuses and prizes stand; donated penalties always stand
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub flagged_by: Address,
+    pub reason: String,
+    pub flagged_at: u64,
+    pub resolution: Resolution,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RotationState {
+    pub next_round: u32,           // Week number whose pot pays out next
+    pub pot: i128,                 // Contributions not yet paid out
+    pub received: Vec<Address>,    // Participants who have had their turn, in order
+    pub missed: Map<Address, u32>, // Rounds each participant failed to pay in full
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum YieldSplit {
+    ProRata,     // Yield follows each participant's contributions
+    PrizePot(u32), // Yield goes to this many randomly drawn consistent savers
+    BonusPot,    // Yield joins the bonus pot and is shared by its formula
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrizePool {
+    pub vault: Address,
+    pub split: YieldSplit,
+    pub shares: i128,              // Vault shares held for this challenge
+    pub principal: i128,           // Contributions deposited into the vault
+    pub prize: i128,               // Yield realized at finalization
+    pub shortfall: i128,           // Principal the vault failed to return, shared pro rata
+    pub winners: Vec<Address>,
+    pub settled: bool,
 }
 
 #[contracttype]
@@ -36,6 +202,35 @@ pub struct Contribution {
     pub week_number: u32,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptId {
+    pub challenge_id: u32,
+    pub sequence: u32,            // Position in the challenge's contribution log
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trophy {
+    pub challenge_id: u32,
+    pub name: String,             // The challenge's name
+    pub holder: Address,
+    pub started_at: u64,
+    pub finalized_at: u64,
+    pub amount_saved: i128,
+    pub rank: u32,                // By amount saved, 1 being the top saver
+    pub participant_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LifetimeStats {
+    pub total_saved: i128,        // Every contribution across all challenges, after fees, less early withdrawals and refunds
+    pub challenges_completed: u32, // Withdrawn from a finalized challenge with the personal target saved
+    pub longest_streak: u32,
+    pub rewards_earned: i128,     // SaveCoin minted through challenges
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ParticipantStats {
@@ -43,23 +238,394 @@ pub struct ParticipantStats {
     pub contribution_count: u32,
     pub last_contribution: u64,
     pub current_streak: u32,
+    pub freezes_used: u32,        // Streak freezes spent covering missed weeks
+    pub weeks_met: u32,           // Weeks where at least the amount due was paid in
+    pub last_week_met: u32,       // Most recent such week (0 if none)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WeeklyCompliance {
+    pub current_week: u32,
+    pub weeks_met: u32,           // Including the current week once it is paid in full
+    pub weeks_missed: u32,        // Finished weeks paid short of the amount due
+    pub current_week_paid: i128,
+    pub current_week_due: i128,   // Still needed this week to meet the amount due
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetEscrow {
+    pub amount: i128,             // Held in the contributed asset
+    pub value: i128,              // What it was credited as, in the challenge token
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFees {
+    pub fee_bps: u32,             // Taken from each contribution
+    pub payout_fee_bps: u32,      // Taken from each final payout
+    pub keeper_bounty_bps: u32,   // Share of a challenge's fees paid for finalizing it once expired
+    pub treasury: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sponsorship {
+    pub sponsor: Address,
+    pub match_bps: u32,           // Matched per contribution, up to the amount due each week
+    pub cap: i128,                // Escrowed up front in the challenge token
+    pub matched: i128,            // Paid into participants' bonuses so far
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BonusFormula {
+    pub amount_weight_bps: u32,   // Share of the pot split by total contributed
+    pub streak_weight_bps: u32,   // Share of the pot split by current streak
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondConfig {
+    pub amount: i128,             // Posted by the creator in the challenge token
+    pub grace_period: u64,        // Seconds after the deadline to finalize before the bond is slashed
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoContribution {
+    pub amount: i128,
+    pub interval: u64,            // Seconds between pulls
+    pub next_due: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeSummary {
+    pub id: u32,
+    pub name: String,
+    pub goal_amount: i128,
+    pub current_amount: i128,
+    pub participant_count: u32,
+    pub max_participants: u32,
+    pub weeks_remaining: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneStates {
+    pub first_contribution: bool,
+    pub halfway: bool,            // Half of the user's target saved
+    pub target_reached: bool,     // The amount due saved for every week of the challenge
+    pub year_streak: bool,        // 52 weeks in a row
+    pub goal_reached: bool,       // The group goal
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub description: Symbol,
+    pub target_amount: i128,
+    pub reached: bool,
+    pub reached_at: u64,
+    pub reward_bonus: u32,        // SaveCoin bonus when reached, in bps of target_amount
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserProgress {
+    pub current_amount: i128,
+    pub target_amount: i128,      // The amount due for every week of the challenge
+    pub last_deposit_time: u64,
+    pub streak_weeks: u32,
+    pub completed: bool,          // Personal target reached
+    pub milestones: Vec<Milestone>,
+    pub quorum: QuorumStatus,     // Outside Quorum challenges required_bps is 0 and met is always true
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuorumStatus {
+    pub required_bps: u32,
+    pub completed_count: u32,     // Participants who have reached their own target
+    pub participant_count: u32,
+    pub met: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeDashboard {
+    pub summary: ChallengeSummary,
+    pub current_week: u32,
+    pub is_participant: bool,
+    pub user_target: i128,
+    pub user_payout: i128,        // What the user would be paid if the challenge closed now
+    pub current_week_paid: i128,
+    pub stats: ParticipantStats,
+    pub milestones: MilestoneStates,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatrixRow {
+    pub participant: Address,
+    pub paid: Vec<i128>,          // Paid in per week, starting at the requested week
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompetitionMetric {
+    GoalPercent,  // Share of its own goal each challenge has saved, in bps
+    TotalSaved,   // Raw amount saved
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Competition {
+    pub challenge_a: u32,
+    pub challenge_b: u32,
+    pub metric: CompetitionMetric,
+    pub prize: i128,              // SaveCoin split among the winning challenge's participants
+    pub winner: Option<u32>,      // None until settled, or after a tie
+    pub settled: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseWindow {
+    pub from_week: u32,
+    pub until_week: u32,          // Inclusive
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HaltWindow {
+    pub from: u64,
+    pub until: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateLine {
+    pub participant: Address,
+    pub total_contributed: i128,
+    pub streak: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompletionSummary {
+    pub challenge_id: u32,
+    pub goal_amount: i128,
+    pub total_saved: i128,
+    pub finalized_at: u64,
+    pub lines: Vec<CertificateLine>, // In participant order
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Certificate {
+    pub hash: BytesN<32>,         // sha256 of the CompletionSummary's XDR
+    pub finalized_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TeamStanding {
+    pub team: Symbol,
+    pub total: i128,              // Saved by current members
+    pub members: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RankingEntry {
+    pub participant: Address,
+    pub value: i128,              // Total contributed or current streak, depending on the board
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Note {
+    pub author: Address,
+    pub message: String,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionProposal {
+    pub proposer: Address,
+    pub extra_weeks: u32,
+    pub votes: Vec<Address>,
+    pub applied: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoExtendRule {
+    pub threshold_bps: u32,       // Share of the goal the group must have saved at expiry
+    pub max_weeks: u32,           // Most weeks the deadline can be pushed out in total
+    pub weeks_used: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinRequirements {
+    pub min_stake: i128,          // SaveCoin the user must have staked (0 for none)
+    pub min_account_age: u32,     // Ledgers since the user was first seen by this contract (0 for none)
+    pub badge: Option<u32>,       // Badge the user must hold
+}
+
+/// Settings for create_challenge beyond the plan itself
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeOptions {
+    pub min_weekly_required: bool,
+    pub allow_early_withdrawal: bool,
+    pub visibility: Visibility,
+    pub max_participants: u32,    // 0 for no cap
+    pub mode: ChallengeMode,
+    pub start_time: u64,          // 0 to start now; otherwise up to MAX_ENROLLMENT_PERIOD ahead
+}
+
+const WEEK_IN_SECONDS: u64 = 7 * 24 * 60 * 60;
+const WEEK_OF_LEDGERS: u32 = 60 * 60 * 24 / 5 * 7;
+const MAX_NOTES: u32 = 20;               // Notes kept per challenge; older ones are overwritten
+const MAX_NOTE_LENGTH: u32 = 280;        // Bytes
+const NOTE_COOLDOWN: u64 = 60 * 60;      // One note per author per hour
+const LEADERBOARD_SIZE: u32 = 100;
+const MAX_PAGE_SIZE: u32 = 50;
+const MAX_TAGS: u32 = 5;
+const MAX_MATRIX_CELLS: u32 = 500;       // Participant-weeks read per matrix page
+const MAX_TEAMS: u32 = 20;
+const MAX_SPLIT_CHALLENGES: u32 = 10;    // Challenges one split contribution can fund
+const MAX_BATCH_DEPOSITS: u32 = 50;      // Deposits one batch can record
+const MAX_MEMO_LENGTH: u32 = 64;         // Bytes
+const MAX_PAUSE_WEEKS: u32 = 4;          // Weeks each participant can pause per challenge
+const MAX_WAITLIST: u32 = 50;
+const MAX_CO_ADMINS: u32 = 5;
+const MAX_SUPPORTERS: u32 = 5;           // Per participant per challenge
+const MAX_ENROLLMENT_PERIOD: u64 = 12 * WEEK_IN_SECONDS; // Longest a challenge can wait to start
+const MAX_TEAM_BONUS_BPS: u32 = 50000;   // Winning team rewards at most 5x
+const MAX_MILESTONE_BONUS_BPS: u32 = 500;  // Group milestone bonus at most 5% of its target
+const MOST_TIER_REWARD_BPS: u32 = 6000;
+const HALF_TIER_REWARD_BPS: u32 = 2500;
+const RATE_SCALE: i128 = 100_0000000;    // Oracle rate meaning 1:1
+const DEFAULT_EARLY_WITHDRAWAL_PENALTY_BPS: u32 = 1000; // 10%
+
+// Built-in SaveCoin achievement badges
+const BADGE_FIRST_CHALLENGE: u32 = 1;
+const BADGE_52_WEEK_STREAK: u32 = 2;
+const BADGE_CHALLENGE_COMPLETED: u32 = 3;
+
 // ===== STORAGE KEYS =====
+// Split across enums to keep each under the contracttype variant limit
+
+// Challenges, their indexes and contract-wide config
 #[contracttype]
 pub enum DataKey {
     NextChallengeId,
     Challenge(u32),
-    Contributions(u32),  // Challenge ID -> Vec<Contribution>
-    ParticipantStats(u32, Address), // Challenge ID, Participant -> Stats
-    UserChallenges(Address), // User -> Vec<u32> (challenge IDs)
+    Contribution(u32, u32),  // Challenge ID, Index -> Contribution
+    ContributionCount(u32),  // Challenge ID -> number of contributions
+    Invite(u32, BytesN<32>), // Challenge ID, sha256(code) -> unused invite
+    PublicChallenges,        // Vec<u32> of active open challenges
+    CategoryChallenges(Symbol), // Category -> Vec<u32> of active open challenges
+    TagChallenges(Symbol),   // Tag -> Vec<u32> of active open challenges
+    Rotation(u32),           // Challenge ID -> RotationState
+    WeekAssessed(u32, u32),  // Challenge ID, Week -> penalties applied
+    AutoContributors(u32),   // Challenge ID -> Vec<Address> with auto-contribute on
+    Extension(u32),          // Challenge ID -> ExtensionProposal (one per challenge)
+    AutoExtend(u32),         // Challenge ID -> AutoExtendRule
+    SaveCoinToken,           // SaveCoin contract that mints rewards and achievement badges
+    Arbiter,                 // Resolves challenge disputes
+    Dispute(u32),            // Challenge ID -> Dispute (one per challenge)
+    LastClosedWeek(u32),     // Challenge ID -> last week closed by a keeper
+    MissedWeek(u32, u32),    // Challenge ID, Week -> Vec<Address> who missed it
+    ContractPaused,          // When the current contract-wide halt began; absent while running
+    HaltWindows,             // Vec<HaltWindow> of past halts, whose weeks count as paused for everyone
+    NextCompetitionId,
+    Competition(u32),        // Competition ID -> Competition
+    ChallengeCompetition(u32), // Challenge ID -> the competition it is in (at most one)
+    Waitlist(u32),           // Challenge ID -> Vec<Address> waiting for a place, first in line first
+    Certificate(u32),        // Challenge ID -> Certificate issued at finalization
+    JoinRequirements(u32),   // Challenge ID -> JoinRequirements for joining it openly
+    CoAdmins(u32),           // Challenge ID -> Vec<Address> sharing the creator's moderation rights
+    Memo(u32, u32),          // Challenge ID, Index -> memo on a batched contribution
     Admin,
     ContractInfo,
 }
 
-// ===== ERRORS =====
+// State kept per participant in a challenge, or per user across challenges
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParticipantKey {
+    UserChallenges(Address), // User -> Vec<u32> (challenge IDs)
+    ParticipantStats(u32, Address), // Challenge ID, Participant -> Stats
+    Withdrawn(u32, Address), // Challenge ID, Participant -> share paid out
+    WeekPaid(u32, u32, Address), // Challenge ID, Week, Participant -> amount paid in
+    Penalty(u32, Address),   // Challenge ID, Participant -> total penalized
+    Bonus(u32, Address),     // Challenge ID, Participant -> share of penalties earned
+    AutoContribution(u32, Address), // Challenge ID, Participant -> AutoContribution
+    PrizeWinnings(u32, Address), // Challenge ID, Winner -> prize owed
+    AssetEscrow(u32, Address, Address), // Challenge ID, Participant, Token -> AssetEscrow
+    BonusWeight(u32, Address), // Challenge ID, Participant -> pot share in bps, fixed at finalization
+    BonusClaimed(u32, Address), // Challenge ID, Participant -> pot already claimed
+    UserMilestones(u32, Address), // Challenge ID, Participant -> Vec<Milestone> on their own savings
+    HeldRewards(u32, Address), // Challenge ID, Participant -> Map<BytesN<32>, i128> of rewards held until withdrawal, by dedupe key
+    TeamOf(u32, Address),    // Challenge ID, Participant -> team name
+    Pauses(u32, Address),    // Challenge ID, Participant -> Vec<PauseWindow>
+    DeclaredIncome(u32, Address), // Challenge ID, Participant -> weekly income for Percentage challenges
+    GroupBonusShare(u32, u32, Address), // Challenge ID, Milestone index, Participant -> unclaimed share
+    FirstSeen(Address),      // User -> ledger sequence of their first recorded activity
+    Supporters(u32, Address), // Challenge ID, Participant -> Vec<Address> allowed to follow their progress
+    Lifetime(Address),       // User -> LifetimeStats across every challenge
+}
+
+// Rankings, notes, cheers, teams and group milestones
+#[contracttype]
+pub enum SocialKey {
+    AmountRanking(u32),      // Challenge ID -> Vec<RankingEntry> by total contributed
+    StreakRanking(u32),      // Challenge ID -> Vec<RankingEntry> by current streak
+    Note(u32, u32),          // Challenge ID, Slot -> Note (ring buffer of MAX_NOTES)
+    NoteCount(u32),          // Challenge ID -> notes ever posted
+    LastNote(u32, Address),  // Challenge ID, Author -> time of last note
+    GroupMilestones(u32),    // Challenge ID -> Vec<Milestone> on the group total
+    LastCheer(u32, Address, Address), // Challenge ID, Supporter, Target -> day of last cheer
+    CheerCount(u32, Address), // Challenge ID, Target -> cheers received
+    CheerReward,             // SaveCoin minted to a cheered participant
+    Teams(u32),              // Challenge ID -> Vec<Symbol> of team names
+    TeamBonus(u32),          // Challenge ID -> completion reward multiplier for the winning team, in bps
+    WinningTeam(u32),        // Challenge ID -> team with the highest total at finalization
+}
+
+// Fees, bonds, sponsorships, bonus pots, prize vaults and donations
+#[contracttype]
+pub enum FinanceKey {
+    PlatformFees,
+    ChallengeFees(u32),      // Challenge ID -> platform fees held
+    YieldVault(Address),     // Token -> yield vault for no-loss prize challenges
+    PrizePool(u32),          // Challenge ID -> PrizePool
+    RateOracle,              // Exchange rate feed for multi-asset challenges
+    AcceptedAssets(u32),     // Challenge ID -> Map<Address, String> of extra token -> oracle pair
+    AccruedFees(Address),    // Token -> protocol fees ready to withdraw to the treasury
+    BondConfig,              // Creator bond required for new challenges
+    Bond(u32),               // Challenge ID -> creator bond held
+    Sponsor(u32),            // Challenge ID -> Sponsorship
+    BonusPot(u32),           // Challenge ID -> everything ever paid into the bonus pot
+    BonusFormula(u32),       // Challenge ID -> BonusFormula
+    GroupBonusCap,           // Most SaveCoin a creator can set as a challenge's group bonus
+    Charity(u32),            // Challenge ID -> address penalties and forfeits are donated to
+    Donated(u32),            // Challenge ID -> total donated to its charity
+    PendingDonation(u32),    // Challenge ID -> donations held until the prize pool redeems
+    GroupBonus(u32),         // Challenge ID -> SaveCoin shared out when each group milestone is reached
+}
+
+// ===== ERRORS =====
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
 pub enum SavingsError {
     NotAuthorized = 1,
     ChallengeNotFound = 2,
@@ -71,6 +637,36 @@ pub enum SavingsError {
     AlreadyFinalized = 8,
     GoalNotReached = 9,
     ContributionTooEarly = 10,
+    ChallengeStillActive = 11,
+    AlreadyWithdrawn = 12,
+    NothingToWithdraw = 13,
+    ChallengeClosed = 14,
+    ChallengeFull = 15,
+    AlreadyParticipant = 16,
+    InvalidInvite = 17,
+    NotRotating = 18,
+    RoundNotOver = 19,
+    VaultNotConfigured = 20,
+    AlreadyAssessed = 21,
+    AutoPullFailed = 22,
+    NotExpired = 23,
+    MessageTooLong = 24,
+    RateLimited = 25,
+    ExtensionUsed = 26,
+    AlreadyVoted = 27,
+    NoExtensionProposed = 28,
+    OracleNotConfigured = 29,
+    AssetNotAccepted = 30,
+    EarlyWithdrawalDisabled = 31,
+    ChallengeDisputed = 32,
+    NoDispute = 33,
+    CheeringDisabled = 34,
+    AlreadyLinked = 35,
+    ChallengeNotStarted = 36,
+    ChallengeStarted = 37,
+    JoinRequirementsNotMet = 38,
+    ContractPaused = 39,
+    QuorumNotMet = 40,
 }
 
 // ===== CONTRACT IMPLEMENTATION =====
@@ -106,17 +702,26 @@ impl SavingsChallengeContract {
     pub fn create_challenge(
         env: Env,
         creator: Address,
+        token: Address,
         name: String,
         description: String,
         goal_amount: i128,
         weekly_amount: i128,
         participants: Vec<Address>,
         duration_weeks: u32,
-        min_weekly_required: bool,
-        allow_early_withdrawal: bool,
+        options: ChallengeOptions,
     ) -> Result<u32, SavingsError> {
         creator.require_auth();
         
+        let ChallengeOptions {
+            min_weekly_required,
+            allow_early_withdrawal,
+            visibility,
+            max_participants,
+            mode,
+            start_time,
+        } = options;
+        
         // Validate parameters
         if goal_amount <= 0 || weekly_amount <= 0 {
             return Err(SavingsError::InvalidParameters);
@@ -126,7 +731,12 @@ impl SavingsChallengeContract {
             return Err(SavingsError::InvalidParameters);
         }
         
-        if participants.is_empty() {
+        // Closed challenges can't gain members later, so they need them up front
+        if participants.is_empty() && visibility == Visibility::Closed {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        if max_participants > 0 && participants.len() > max_participants {
             return Err(SavingsError::InvalidParameters);
         }
         
@@ -134,369 +744,6098 @@ impl SavingsChallengeContract {
             return Err(SavingsError::InvalidParameters);
         }
         
-        // Get next challenge ID
-        let challenge_id: u32 = env.storage().instance()
-            .get(&DataKey::NextChallengeId)
-            .unwrap_or(1);
+        // Zero starts now; a later start opens an enrollment window first
+        let current_time = env.ledger().timestamp();
+        let start_time = if start_time == 0 { current_time } else { start_time };
+        if start_time < current_time || start_time > current_time + MAX_ENROLLMENT_PERIOD {
+            return Err(SavingsError::InvalidParameters);
+        }
         
         // Calculate deadline (duration_weeks * 7 * 24 * 60 * 60)
-        let current_time = env.ledger().timestamp();
-        let deadline = current_time + (duration_weeks as u64 * 7 * 24 * 60 * 60);
+        let deadline = start_time + (duration_weeks as u64 * 7 * 24 * 60 * 60);
         
         // Create challenge
         let challenge = SavingsChallenge {
-            id: challenge_id,
-            creator: creator.clone(),
-            name: name.clone(),
+            id: 0, // Assigned when stored
+            creator,
+            token,
+            name,
             description,
             goal_amount,
             weekly_amount,
             current_amount: 0,
-            participants: participants.clone(),
+            participants,
             created_at: current_time,
+            start_time,
             deadline,
             is_active: true,
+            finalized: false,
             min_weekly_required,
             allow_early_withdrawal,
+            visibility,
+            max_participants,
+            mode,
+            missed_week_penalty: 0,
+            streak_freezes: 0,
+            streak_gap: 0,
+            previous_challenge: None,
+            early_withdrawal_penalty_bps: DEFAULT_EARLY_WITHDRAWAL_PENALTY_BPS,
+            category: symbol_short!("general"),
+            tags: Vec::new(&env),
+            challenge_type: ChallengeType::Fixed,
+            social_features: SocialFeatures {
+                public_leaderboard: true,
+                enable_cheering: true,
+                allow_group_milestone: true,
+            },
+            reward_bps: 0,
         };
         
-        // Store challenge
-        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        Self::insert_challenge(&env, challenge)
+    }
+    
+    /// Start a new run of a finished challenge with the same settings and participants (creator only)
+    pub fn restart_challenge(
+        env: Env,
+        creator: Address,
+        old_id: u32,
+    ) -> Result<u32, SavingsError> {
+        creator.require_auth();
         
-        // Initialize contributions storage
-        let empty_contributions: Vec<Contribution> = Vec::new(&env);
-        env.storage().persistent().set(&DataKey::Contributions(challenge_id), &empty_contributions);
+        let old: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(old_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
         
-        // Add challenge to participants' challenge lists
-        for participant in participants.iter() {
-            Self::add_challenge_to_user(&env, &participant, challenge_id);
-            
-            // Initialize participant stats
-            let stats = ParticipantStats {
-                total_contributed: 0,
-                contribution_count: 0,
-                last_contribution: 0,
-                current_streak: 0,
-            };
-            env.storage().persistent().set(
-                &DataKey::ParticipantStats(challenge_id, participant), 
-                &stats
-            );
+        if old.creator != creator {
+            return Err(SavingsError::NotAuthorized);
         }
         
-        // Update next challenge ID
-        env.storage().instance().set(&DataKey::NextChallengeId, &(challenge_id + 1));
-        
-        // Emit event
-        env.events().publish(
-            (symbol_short!("created"), challenge_id), 
-            (creator, name, goal_amount, participants.len())
-        );
+        if old.is_active {
+            return Err(SavingsError::ChallengeStillActive);
+        }
         
-        log!(&env, "Challenge {} created by {} with goal {}", challenge_id, creator, goal_amount);
+        let current_time = env.ledger().timestamp();
+        let challenge = SavingsChallenge {
+            id: 0, // Assigned when stored
+            current_amount: 0,
+            created_at: current_time,
+            start_time: current_time,
+            deadline: current_time + (old.deadline - old.start_time),
+            is_active: true,
+            finalized: false,
+            previous_challenge: Some(old_id),
+            ..old
+        };
         
-        Ok(challenge_id)
+        Self::insert_challenge(&env, challenge)
     }
     
-    /// Make a contribution to a challenge
-    pub fn contribute(
+    /// Join an open challenge after it was created
+    pub fn join_challenge(
         env: Env,
         challenge_id: u32,
-        contributor: Address,
-        amount: i128,
+        user: Address,
     ) -> Result<(), SavingsError> {
-        contributor.require_auth();
+        user.require_auth();
         
-        if amount <= 0 {
-            return Err(SavingsError::InsufficientAmount);
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.visibility != Visibility::Open {
+            return Err(SavingsError::ChallengeClosed);
+        }
+        
+        Self::require_not_paused(&env)?;
+        Self::check_join_requirements(&env, challenge_id, &user)?;
+        
+        Self::admit_participant(&env, challenge, &user)
+    }
+    
+    /// Join an invite-only challenge by redeeming a single-use invite code
+    pub fn join_with_invite(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+        code: Bytes,
+    ) -> Result<(), SavingsError> {
+        user.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.visibility != Visibility::InviteOnly {
+            return Err(SavingsError::ChallengeClosed);
+        }
+        
+        Self::require_not_paused(&env)?;
+        
+        let code_hash = BytesN::from_array(&env, &env.crypto().sha256(&code).to_array());
+        let invite_key = DataKey::Invite(challenge_id, code_hash);
+        if !env.storage().persistent().has(&invite_key) {
+            return Err(SavingsError::InvalidInvite);
+        }
+        
+        Self::admit_participant(&env, challenge, &user)?;
+        
+        // Invites are single-use
+        env.storage().persistent().remove(&invite_key);
+        
+        Ok(())
+    }
+    
+    /// Gate open joining on a SaveCoin stake, account age or badge; None removes the gates (creator only)
+    pub fn set_join_requirements(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        requirements: Option<JoinRequirements>,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        match &requirements {
+            Some(requirements) => {
+                if requirements.min_stake < 0 {
+                    return Err(SavingsError::InvalidParameters);
+                }
+                env.storage().persistent().set(&DataKey::JoinRequirements(challenge_id), requirements);
+            },
+            None => env.storage().persistent().remove(&DataKey::JoinRequirements(challenge_id)),
+        }
+        
+        env.events().publish(
+            (symbol_short!("gates_set"), challenge_id), 
+            requirements
+        );
+        
+        Ok(())
+    }
+    
+    /// Start the account-age clock for a user who hasn't created or joined anything yet
+    pub fn register_account(env: Env, user: Address) -> u32 {
+        user.require_auth();
+        
+        Self::record_first_seen(&env, &user)
+    }
+    
+    /// Set the amount taken from a participant for each missed week (creator only, before start)
+    pub fn set_missed_week_penalty(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        penalty: i128,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if penalty < 0 || penalty > challenge.weekly_amount {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        challenge.missed_week_penalty = penalty;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("pen_set"), challenge_id), 
+            penalty
+        );
+        
+        Ok(())
+    }
+    
+    /// Set how many missed weeks each participant can cover without breaking their streak (creator only, before start)
+    pub fn set_streak_freezes(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        freezes: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        challenge.streak_freezes = freezes;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("frz_set"), challenge_id), 
+            freezes
+        );
+        
+        Ok(())
+    }
+    
+    /// Set how many empty weeks can pass between counted weeks before a streak needs a freeze (creator only, before start)
+    pub fn set_streak_gap(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        gap_weeks: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        challenge.streak_gap = gap_weeks;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("gap_set"), challenge_id), 
+            gap_weeks
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the share of an early withdrawal kept for the group bonus pot (creator only, before start)
+    pub fn set_early_withdrawal_penalty(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        penalty_bps: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if penalty_bps > 10000 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        challenge.early_withdrawal_penalty_bps = penalty_bps;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("ew_pen"), challenge_id), 
+            penalty_bps
+        );
+        
+        Ok(())
+    }
+    
+    /// Register sha256 hashes of invite codes for a challenge (creator only)
+    pub fn add_invite_codes(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        code_hashes: Vec<BytesN<32>>,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        for code_hash in code_hashes.iter() {
+            env.storage().persistent().set(&DataKey::Invite(challenge_id, code_hash), &true);
+        }
+        
+        env.events().publish(
+            (symbol_short!("invites"), challenge_id), 
+            code_hashes.len()
+        );
+        
+        Ok(())
+    }
+    
+    /// Eject a participant other than the creator, refunding their share (creator or co-admin) or
+    /// splitting it among the rest (creator only)
+    pub fn remove_participant(
+        env: Env,
+        challenge_id: u32,
+        moderator: Address,
+        user: Address,
+        refund: bool,
+    ) -> Result<i128, SavingsError> {
+        moderator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !Self::is_moderator(&env, &challenge, &moderator) {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        // Co-admins can only remove with a refund, and nobody can remove the creator
+        if user == challenge.creator || (!refund && moderator != challenge.creator) {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        let position = challenge.participants.first_index_of(&user)
+            .ok_or(SavingsError::NotParticipant)?;
+        
+        // Vault-held funds can't be paid out until the prize pool settles
+        let share = Self::payout_share(&env, &challenge, &user);
+        if refund && share > 0 && env.storage().persistent().has(&FinanceKey::PrizePool(challenge_id)) {
+            return Err(SavingsError::InvalidParameters);
+        }
+        if refund {
+            Self::require_not_frozen(&env, challenge_id)?;
+        }
+        
+        challenge.participants.remove(position);
+        
+        if refund {
+            challenge.current_amount -= share;
+            if share > 0 {
+                Self::transfer_share(&env, &challenge, &user, share, 0);
+            }
+            let refunded: Option<ParticipantStats> = env.storage().persistent()
+                .get(&ParticipantKey::ParticipantStats(challenge_id, user.clone()));
+            if let Some(refunded) = refunded {
+                Self::reduce_lifetime_saved(&env, &user, refunded.total_contributed);
+            }
+        } else if share > 0 && !challenge.participants.is_empty() {
+            // Forfeited savings stay with the other savers, with extra-asset escrow handed over as-is
+            let moved = Self::forfeit_asset_escrow(&env, &challenge, &user);
+            Self::add_to_bonus_pot(&env, challenge_id, share - moved);
+        }
+        
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        // Nothing further is owed to a removed participant
+        env.storage().persistent().set(&ParticipantKey::Withdrawn(challenge_id, user.clone()), &true);
+        env.storage().persistent().remove(&ParticipantKey::AutoContribution(challenge_id, user.clone()));
+        env.storage().persistent().remove(&ParticipantKey::HeldRewards(challenge_id, user.clone()));
+        Self::remove_challenge_from_user(&env, &user, challenge_id);
+        Self::remove_from_ranking(&env, SocialKey::AmountRanking(challenge_id), &user);
+        Self::remove_from_ranking(&env, SocialKey::StreakRanking(challenge_id), &user);
+        
+        env.events().publish(
+            (symbol_short!("removed"), challenge_id), 
+            (moderator, user.clone(), refund, share)
+        );
+        
+        // A place freed before the start goes to the waitlist
+        if env.ledger().timestamp() < challenge.start_time {
+            Self::promote_from_waitlist(&env, challenge)?;
+        }
+        
+        log!(&env, "Participant {} removed from challenge {}", user, challenge_id);
+        
+        Ok(if refund { share } else { 0 })
+    }
+    
+    /// Share moderation (removing participants, adding milestones, finalizing) with a co-admin (creator only)
+    pub fn add_co_admin(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        co_admin: Address,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        let mut co_admins = Self::get_co_admins(env.clone(), challenge_id);
+        if co_admin == creator || co_admins.contains(&co_admin) || co_admins.len() >= MAX_CO_ADMINS {
+            return Err(SavingsError::InvalidParameters);
+        }
+        co_admins.push_back(co_admin.clone());
+        env.storage().persistent().set(&DataKey::CoAdmins(challenge_id), &co_admins);
+        
+        env.events().publish(
+            (symbol_short!("coadm_add"), challenge_id), 
+            co_admin
+        );
+        
+        Ok(())
+    }
+    
+    /// Take a co-admin's moderation rights away (creator only)
+    pub fn remove_co_admin(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        co_admin: Address,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        let mut co_admins = Self::get_co_admins(env.clone(), challenge_id);
+        let position = co_admins.first_index_of(&co_admin)
+            .ok_or(SavingsError::InvalidParameters)?;
+        co_admins.remove(position);
+        env.storage().persistent().set(&DataKey::CoAdmins(challenge_id), &co_admins);
+        
+        env.events().publish(
+            (symbol_short!("coadm_rm"), challenge_id), 
+            co_admin
+        );
+        
+        Ok(())
+    }
+    
+    /// Name an address apps should show a participant's progress to in a private challenge; contract
+    /// state is public on the ledger, so this is a display preference, not access control (the participant only)
+    pub fn add_supporter(
+        env: Env,
+        participant: Address,
+        challenge_id: u32,
+        supporter: Address,
+    ) -> Result<(), SavingsError> {
+        participant.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.participants.contains(&participant) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        let mut supporters = Self::get_supporters(env.clone(), challenge_id, participant.clone());
+        if supporter == participant || supporters.contains(&supporter) || supporters.len() >= MAX_SUPPORTERS {
+            return Err(SavingsError::InvalidParameters);
+        }
+        supporters.push_back(supporter.clone());
+        env.storage().persistent().set(&ParticipantKey::Supporters(challenge_id, participant.clone()), &supporters);
+        
+        env.events().publish(
+            (symbol_short!("sup_add"), challenge_id), 
+            (participant, supporter)
+        );
+        
+        Ok(())
+    }
+    
+    /// Stop a supporter following a participant's progress (the participant only)
+    pub fn remove_supporter(
+        env: Env,
+        participant: Address,
+        challenge_id: u32,
+        supporter: Address,
+    ) -> Result<(), SavingsError> {
+        participant.require_auth();
+        
+        let mut supporters = Self::get_supporters(env.clone(), challenge_id, participant.clone());
+        let position = supporters.first_index_of(&supporter)
+            .ok_or(SavingsError::InvalidParameters)?;
+        supporters.remove(position);
+        env.storage().persistent().set(&ParticipantKey::Supporters(challenge_id, participant.clone()), &supporters);
+        
+        env.events().publish(
+            (symbol_short!("sup_rm"), challenge_id), 
+            (participant, supporter)
+        );
+        
+        Ok(())
+    }
+    
+    /// Leave a challenge before it starts; the first waitlisted user takes the place
+    pub fn leave_challenge(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<(), SavingsError> {
+        user.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        // Nobody can have contributed yet, so there is nothing to refund
+        let position = challenge.participants.first_index_of(&user)
+            .ok_or(SavingsError::NotParticipant)?;
+        challenge.participants.remove(position);
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.storage().persistent().remove(&ParticipantKey::ParticipantStats(challenge_id, user.clone()));
+        env.storage().persistent().remove(&ParticipantKey::AutoContribution(challenge_id, user.clone()));
+        Self::remove_challenge_from_user(&env, &user, challenge_id);
+        
+        env.events().publish(
+            (symbol_short!("left"), challenge_id), 
+            user
+        );
+        
+        Self::promote_from_waitlist(&env, challenge)
+    }
+    
+    /// Call off a challenge before it starts or before anyone has contributed, handing back the
+    /// creator's bond and the sponsor's escrow (creator only)
+    pub fn cancel_challenge(
+        env: Env,
+        creator: Address,
+        challenge_id: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        let contributions: u32 = env.storage().persistent()
+            .get(&DataKey::ContributionCount(challenge_id))
+            .unwrap_or(0);
+        if env.ledger().timestamp() >= challenge.start_time && contributions > 0 {
+            return Err(SavingsError::ChallengeStarted);
+        }
+        
+        challenge.is_active = false;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        Self::update_public_index(&env, &challenge);
+        env.storage().persistent().remove(&DataKey::Waitlist(challenge_id));
+        
+        let token_client = token::Client::new(&env, &challenge.token);
+        
+        let bond: i128 = env.storage().persistent()
+            .get(&FinanceKey::Bond(challenge_id))
+            .unwrap_or(0);
+        if bond > 0 {
+            env.storage().persistent().remove(&FinanceKey::Bond(challenge_id));
+            token_client.transfer(&env.current_contract_address(), &creator, &bond);
+        }
+        
+        // With nothing contributed nothing was matched, but only the unused cap is still held
+        if let Some(mut sponsorship) = Self::get_sponsorship(env.clone(), challenge_id) {
+            let unused = sponsorship.cap - sponsorship.matched;
+            if unused > 0 {
+                sponsorship.cap = sponsorship.matched;
+                env.storage().persistent().set(&FinanceKey::Sponsor(challenge_id), &sponsorship);
+                token_client.transfer(&env.current_contract_address(), &sponsorship.sponsor, &unused);
+            }
+        }
+        
+        env.events().publish(
+            (symbol_short!("cancelled"), challenge_id), 
+            (creator, bond)
+        );
+        
+        Ok(())
+    }
+    
+    /// Queue for a place in a full open challenge that hasn't started yet
+    pub fn join_waitlist(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<u32, SavingsError> {
+        user.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.visibility != Visibility::Open {
+            return Err(SavingsError::ChallengeClosed);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if challenge.participants.contains(&user) {
+            return Err(SavingsError::AlreadyParticipant);
+        }
+        
+        // Promotion skips join_challenge, so the gates apply on the way in
+        Self::require_not_paused(&env)?;
+        Self::check_join_requirements(&env, challenge_id, &user)?;
+        
+        // Only a full challenge has a queue; otherwise just join
+        if challenge.max_participants == 0 || challenge.participants.len() < challenge.max_participants {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let mut waitlist = Self::get_waitlist(env.clone(), challenge_id);
+        if waitlist.contains(&user) {
+            return Err(SavingsError::AlreadyParticipant);
+        }
+        if waitlist.len() >= MAX_WAITLIST {
+            return Err(SavingsError::ChallengeFull);
+        }
+        waitlist.push_back(user.clone());
+        env.storage().persistent().set(&DataKey::Waitlist(challenge_id), &waitlist);
+        
+        let position = waitlist.len();
+        env.events().publish(
+            (symbol_short!("waitlist"), challenge_id), 
+            (user, position)
+        );
+        
+        Ok(position)
+    }
+    
+    /// Give up a place on a challenge's waitlist
+    pub fn leave_waitlist(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<(), SavingsError> {
+        user.require_auth();
+        
+        let mut waitlist = Self::get_waitlist(env.clone(), challenge_id);
+        let position = waitlist.first_index_of(&user)
+            .ok_or(SavingsError::NotParticipant)?;
+        waitlist.remove(position);
+        env.storage().persistent().set(&DataKey::Waitlist(challenge_id), &waitlist);
+        
+        env.events().publish(
+            (symbol_short!("wl_left"), challenge_id), 
+            user
+        );
+        
+        Ok(())
+    }
+    
+    /// Change who can join a challenge (creator only)
+    pub fn set_visibility(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        visibility: Visibility,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        challenge.visibility = visibility;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        Self::update_public_index(&env, &challenge);
+        
+        env.events().publish(
+            (symbol_short!("visible"), challenge_id), 
+            visibility
+        );
+        
+        Ok(())
+    }
+    
+    /// Escrow `cap` to match participants' regular contributions at `match_bps` (sponsor signs)
+    pub fn register_sponsor(
+        env: Env,
+        challenge_id: u32,
+        sponsor: Address,
+        match_bps: u32,
+        cap: i128,
+    ) -> Result<(), SavingsError> {
+        sponsor.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        // Matches are paid with the final shares, which rotating challenges don't have
+        if challenge.mode == ChallengeMode::Rotating
+            || match_bps == 0
+            || match_bps > 10000
+            || cap <= 0
+            || env.storage().persistent().has(&FinanceKey::Sponsor(challenge_id))
+        {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        token::Client::new(&env, &challenge.token).transfer(
+            &sponsor,
+            &env.current_contract_address(),
+            &cap
+        );
+        
+        let sponsorship = Sponsorship {
+            sponsor: sponsor.clone(),
+            match_bps,
+            cap,
+            matched: 0,
+        };
+        env.storage().persistent().set(&FinanceKey::Sponsor(challenge_id), &sponsorship);
+        
+        env.events().publish(
+            (symbol_short!("sponsor"), challenge_id), 
+            (sponsor, match_bps, cap)
+        );
+        
+        Ok(())
+    }
+    
+    /// Set how the bonus pot is weighted between total saved and streak length (creator only, before start)
+    pub fn set_bonus_formula(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        amount_weight_bps: u32,
+        streak_weight_bps: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if amount_weight_bps + streak_weight_bps != 10000 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let formula = BonusFormula {
+            amount_weight_bps,
+            streak_weight_bps,
+        };
+        env.storage().persistent().set(&FinanceKey::BonusFormula(challenge_id), &formula);
+        
+        env.events().publish(
+            (symbol_short!("bonus_fml"), challenge_id), 
+            (amount_weight_bps, streak_weight_bps)
+        );
+        
+        Ok(())
+    }
+    
+    /// Let the deadline move out a week at a time, up to max_weeks, while the group is above
+    /// threshold_bps of the goal at expiry; zero max_weeks removes the rule (creator only, before the start)
+    pub fn set_auto_extend(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        threshold_bps: u32,
+        max_weeks: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if max_weeks == 0 {
+            env.storage().persistent().remove(&DataKey::AutoExtend(challenge_id));
+        } else {
+            if threshold_bps == 0 || threshold_bps > 10000 || max_weeks > 52 {
+                return Err(SavingsError::InvalidParameters);
+            }
+            let rule = AutoExtendRule {
+                threshold_bps,
+                max_weeks,
+                weeks_used: 0,
+            };
+            env.storage().persistent().set(&DataKey::AutoExtend(challenge_id), &rule);
+        }
+        
+        env.events().publish(
+            (symbol_short!("auto_set"), challenge_id), 
+            (threshold_bps, max_weeks)
+        );
+        
+        Ok(())
+    }
+    
+    /// Change the weekly amount and length of a challenge during its enrollment window (creator only)
+    pub fn update_schedule(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        weekly_amount: i128,
+        duration_weeks: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if weekly_amount <= 0 || duration_weeks == 0 || duration_weeks > 104 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        // A custom schedule has to be set again to match a new length
+        if let ChallengeType::Custom(schedule) = &challenge.challenge_type {
+            if schedule.len() != duration_weeks {
+                return Err(SavingsError::InvalidParameters);
+            }
+        }
+        
+        challenge.weekly_amount = weekly_amount;
+        challenge.deadline = challenge.start_time + (duration_weeks as u64 * WEEK_IN_SECONDS);
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("schedule"), challenge_id), 
+            (weekly_amount, duration_weeks)
+        );
+        
+        Ok(())
+    }
+    
+    /// Set how a challenge's weekly amounts are scheduled (creator only)
+    pub fn set_challenge_type(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        challenge_type: ChallengeType,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        // Every week of the schedule has to ask for something
+        let total_weeks = ((challenge.deadline - challenge.start_time) / WEEK_IN_SECONDS) as u32;
+        let valid = match &challenge_type {
+            ChallengeType::Fixed => true,
+            ChallengeType::Incremental(step) => challenge.weekly_amount + step * (total_weeks - 1) as i128 > 0,
+            ChallengeType::Percentage(bps) => *bps > 0 && *bps <= 10000,
+            ChallengeType::Custom(schedule) => {
+                schedule.len() == total_weeks && schedule.iter().all(|amount| amount > 0)
+            },
+        };
+        if !valid {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        challenge.challenge_type = challenge_type.clone();
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("type_set"), challenge_id), 
+            challenge_type
+        );
+        
+        Ok(())
+    }
+    
+    /// Declare the weekly income a Percentage challenge takes its cut from (once per participant, before start)
+    pub fn declare_income(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+        weekly_income: i128,
+    ) -> Result<(), SavingsError> {
+        user.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.participants.contains(&user) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        // Fixed up front so the amount due can't be lowered mid-challenge
+        let key = ParticipantKey::DeclaredIncome(challenge_id, user.clone());
+        if weekly_income <= 0 || env.storage().persistent().has(&key) {
+            return Err(SavingsError::InvalidParameters);
+        }
+        env.storage().persistent().set(&key, &weekly_income);
+        
+        env.events().publish(
+            (symbol_short!("income"), challenge_id), 
+            (user, weekly_income)
+        );
+        
+        Ok(())
+    }
+    
+    /// Get the weekly income a participant declared, if any
+    pub fn get_declared_income(env: Env, challenge_id: u32, user: Address) -> Option<i128> {
+        env.storage().persistent().get(&ParticipantKey::DeclaredIncome(challenge_id, user))
+    }
+    
+    /// Turn a challenge's leaderboard, cheering and group milestones on or off (creator only, before start)
+    pub fn set_social_features(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        social_features: SocialFeatures,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        challenge.social_features = social_features.clone();
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("social"), challenge_id), 
+            social_features
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the base SaveCoin completion reward in bps of the personal target (creator only, before start)
+    pub fn set_completion_reward(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        reward_bps: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if reward_bps > 10000 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        challenge.reward_bps = reward_bps;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        env.events().publish(
+            (symbol_short!("rwd_set"), challenge_id), 
+            reward_bps
+        );
+        
+        Ok(())
+    }
+    
+    /// Add a group milestone to a challenge (creator or co-admin)
+    pub fn create_milestone(
+        env: Env,
+        challenge_id: u32,
+        moderator: Address,
+        description: Symbol,
+        target_amount: i128,
+        reward_bonus: u32,
+    ) -> Result<(), SavingsError> {
+        moderator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !Self::is_moderator(&env, &challenge, &moderator) {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if target_amount <= 0 || reward_bonus > MAX_MILESTONE_BONUS_BPS {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let mut milestones = Self::get_group_milestones(env.clone(), challenge_id);
+        milestones.push_back(Milestone {
+            description: description.clone(),
+            target_amount,
+            reached: false,
+            reached_at: 0,
+            reward_bonus,
+        });
+        env.storage().persistent().set(&SocialKey::GroupMilestones(challenge_id), &milestones);
+        
+        env.events().publish(
+            (symbol_short!("mile_add"), challenge_id), 
+            (description, target_amount)
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the SaveCoin shared among participants when each group milestone is reached (creator only, before start)
+    pub fn set_group_bonus(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        bonus: i128,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        // The bonus is minted, not funded by the creator, so the admin bounds it
+        if bonus < 0 || bonus > Self::get_group_bonus_cap(env.clone()) {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        env.storage().persistent().set(&FinanceKey::GroupBonus(challenge_id), &bonus);
+        
+        env.events().publish(
+            (symbol_short!("grp_set"), challenge_id), 
+            bonus
+        );
+        
+        Ok(())
+    }
+    
+    /// Put a participant on a named team, creating the team if needed (creator only, before start)
+    pub fn assign_team(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        participant: Address,
+        team: Symbol,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        // Teams are fixed once saving starts so nobody can be moved onto the winning side
+        Self::require_not_started(&env, &challenge)?;
+        
+        if !challenge.participants.contains(&participant) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        let mut teams = Self::get_teams(env.clone(), challenge_id);
+        if !teams.contains(&team) {
+            if teams.len() >= MAX_TEAMS {
+                return Err(SavingsError::InvalidParameters);
+            }
+            teams.push_back(team.clone());
+            env.storage().persistent().set(&SocialKey::Teams(challenge_id), &teams);
+        }
+        
+        env.storage().persistent().set(&ParticipantKey::TeamOf(challenge_id, participant.clone()), &team);
+        
+        env.events().publish(
+            (symbol_short!("team"), challenge_id), 
+            (participant, team)
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the completion reward multiplier for the team that saves the most (creator only, before start)
+    pub fn set_team_bonus(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        multiplier_bps: u32,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        if !(10000..=MAX_TEAM_BONUS_BPS).contains(&multiplier_bps) {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        env.storage().persistent().set(&SocialKey::TeamBonus(challenge_id), &multiplier_bps);
+        
+        env.events().publish(
+            (symbol_short!("team_bns"), challenge_id), 
+            multiplier_bps
+        );
+        
+        Ok(())
+    }
+    
+    /// Donate a challenge's penalties and forfeits to a charity instead of the bonus pot; None switches back (creator only, before start)
+    pub fn set_charity(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        charity: Option<Address>,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        match &charity {
+            Some(charity) => env.storage().persistent().set(&FinanceKey::Charity(challenge_id), charity),
+            None => env.storage().persistent().remove(&FinanceKey::Charity(challenge_id)),
+        }
+        
+        env.events().publish(
+            (symbol_short!("charity"), challenge_id), 
+            charity
+        );
+        
+        Ok(())
+    }
+    
+    /// Set a challenge's category and tags for discovery (creator only)
+    pub fn set_category(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        category: Symbol,
+        tags: Vec<Symbol>,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if tags.len() > MAX_TAGS {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        // Drop the old entries before re-indexing under the new ones
+        Self::set_discoverable(&env, &challenge, false);
+        
+        challenge.category = category.clone();
+        challenge.tags = tags.clone();
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        Self::update_public_index(&env, &challenge);
+        
+        env.events().publish(
+            (symbol_short!("category"), challenge_id), 
+            (category, tags)
+        );
+        
+        Ok(())
+    }
+    
+    /// Accept extra assets, each with the oracle pair that prices it in the challenge token (creator only, before start)
+    pub fn set_accepted_assets(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        assets: Map<Address, String>,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::require_not_started(&env, &challenge)?;
+        
+        // Rotating pots and yield vaults only work in the challenge token
+        if !matches!(challenge.mode, ChallengeMode::Standard | ChallengeMode::Quorum(_))
+            || env.storage().persistent().has(&FinanceKey::PrizePool(challenge_id))
+            || assets.contains_key(challenge.token.clone())
+        {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        if !env.storage().instance().has(&FinanceKey::RateOracle) {
+            return Err(SavingsError::OracleNotConfigured);
+        }
+        
+        // Assets already escrowed must stay accepted so they can be paid back
+        for asset in Self::get_accepted_assets(env.clone(), challenge_id).keys().iter() {
+            if !assets.contains_key(asset) {
+                return Err(SavingsError::InvalidParameters);
+            }
+        }
+        
+        env.storage().persistent().set(&FinanceKey::AcceptedAssets(challenge_id), &assets);
+        
+        env.events().publish(
+            (symbol_short!("assets"), challenge_id), 
+            assets.keys()
+        );
+        
+        Ok(())
+    }
+    
+    /// Park a standard challenge's escrow in the token's yield vault until finalization (creator only)
+    pub fn enable_yield_routing(
+        env: Env,
+        challenge_id: u32,
+        creator: Address,
+        split: YieldSplit,
+    ) -> Result<(), SavingsError> {
+        creator.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != creator {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        // Only opt in before any money is escrowed, so everything in the vault is accounted for
+        if !matches!(challenge.mode, ChallengeMode::Standard | ChallengeMode::Quorum(_))
+            || env.storage().persistent().has(&FinanceKey::PrizePool(challenge_id))
+            || Self::get_contribution_count(env.clone(), challenge_id) > 0
+            || !Self::get_accepted_assets(env.clone(), challenge_id).is_empty()
+            || split == YieldSplit::PrizePot(0)
+        {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let vault: Address = env.storage().instance()
+            .get(&FinanceKey::YieldVault(challenge.token.clone()))
+            .ok_or(SavingsError::VaultNotConfigured)?;
+        
+        let prize_pool = PrizePool {
+            vault: vault.clone(),
+            split,
+            shares: 0,
+            principal: 0,
+            prize: 0,
+            shortfall: 0,
+            winners: Vec::new(&env),
+            settled: false,
+        };
+        env.storage().persistent().set(&FinanceKey::PrizePool(challenge_id), &prize_pool);
+        
+        env.events().publish(
+            (symbol_short!("yield_on"), challenge_id), 
+            (vault, split)
+        );
+        
+        Ok(())
+    }
+    
+    /// Make a contribution to a challenge, returning its receipt
+    pub fn contribute(
+        env: Env,
+        challenge_id: u32,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<ReceiptId, SavingsError> {
+        contributor.require_auth();
+        
+        Self::record_contribution(&env, challenge_id, &contributor, amount, false)
+    }
+    
+    /// Fund several challenges that share a token with a single transfer; all of them are credited or none
+    pub fn contribute_split(
+        env: Env,
+        contributor: Address,
+        splits: Vec<(u32, i128)>,
+    ) -> Result<i128, SavingsError> {
+        contributor.require_auth();
+        
+        if splits.is_empty() || splits.len() > MAX_SPLIT_CHALLENGES {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        // Check every part before moving any money
+        let mut split_token: Option<Address> = None;
+        let mut total: i128 = 0;
+        for (challenge_id, amount) in splits.iter() {
+            if amount <= 0 {
+                return Err(SavingsError::InsufficientAmount);
+            }
+            
+            let challenge = Self::open_challenge_for(&env, challenge_id, &contributor)?;
+            match &split_token {
+                Some(split_token) if *split_token != challenge.token => return Err(SavingsError::InvalidParameters),
+                Some(_) => {},
+                None => split_token = Some(challenge.token),
+            }
+            total += amount;
+        }
+        
+        token::Client::new(&env, &split_token.unwrap()).transfer(
+            &contributor,
+            &env.current_contract_address(),
+            &total
+        );
+        
+        // Reload each challenge as it is credited, since two parts may fund the same one
+        for (challenge_id, amount) in splits.iter() {
+            let challenge = Self::open_challenge_for(&env, challenge_id, &contributor)?;
+            Self::credit_escrowed(&env, challenge, &contributor, amount)?;
+        }
+        
+        env.events().publish(
+            (symbol_short!("split"), contributor), 
+            (splits.len(), total)
+        );
+        
+        Ok(total)
+    }
+    
+    /// Record several small deposits, each with an optional memo, as separate contributions behind a single
+    /// transfer; all of them are credited or none
+    pub fn contribute_batch(
+        env: Env,
+        contributor: Address,
+        challenge_id: u32,
+        deposits: Vec<(i128, String)>,
+    ) -> Result<Vec<ReceiptId>, SavingsError> {
+        contributor.require_auth();
+        
+        if deposits.is_empty() || deposits.len() > MAX_BATCH_DEPOSITS {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let mut total: i128 = 0;
+        for (amount, memo) in deposits.iter() {
+            if amount <= 0 {
+                return Err(SavingsError::InsufficientAmount);
+            }
+            if memo.len() > MAX_MEMO_LENGTH {
+                return Err(SavingsError::MessageTooLong);
+            }
+            total += amount;
+        }
+        
+        let challenge = Self::open_challenge_for(&env, challenge_id, &contributor)?;
+        token::Client::new(&env, &challenge.token).transfer(
+            &contributor,
+            &env.current_contract_address(),
+            &total
+        );
+        
+        // Each deposit is credited on the challenge as the previous one left it
+        let mut receipts = Vec::new(&env);
+        for (amount, memo) in deposits.iter() {
+            let challenge = Self::open_challenge_for(&env, challenge_id, &contributor)?;
+            let receipt = Self::credit_escrowed(&env, challenge, &contributor, amount)?;
+            if !memo.is_empty() {
+                env.storage().persistent().set(&DataKey::Memo(challenge_id, receipt.sequence), &memo);
+            }
+            receipts.push_back(receipt);
+        }
+        
+        env.events().publish(
+            (symbol_short!("batch"), challenge_id), 
+            (contributor, deposits.len(), total)
+        );
+        
+        Ok(receipts)
+    }
+    
+    /// Contribute in one of the challenge's extra accepted assets, valued in the challenge token
+    pub fn contribute_asset(
+        env: Env,
+        challenge_id: u32,
+        contributor: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        contributor.require_auth();
+        
+        if amount <= 0 {
+            return Err(SavingsError::InsufficientAmount);
+        }
+        
+        let challenge = Self::open_challenge_for(&env, challenge_id, &contributor)?;
+        
+        if asset == challenge.token {
+            Self::record_contribution(&env, challenge_id, &contributor, amount, false)?;
+            return Ok(amount);
+        }
+        
+        let pair = Self::get_accepted_assets(env.clone(), challenge_id)
+            .get(asset.clone())
+            .ok_or(SavingsError::AssetNotAccepted)?;
+        let oracle: Address = env.storage().instance()
+            .get(&FinanceKey::RateOracle)
+            .ok_or(SavingsError::OracleNotConfigured)?;
+        let rate = RateOracleClient::new(&env, &oracle).get_exchange_rate(&pair);
+        if rate <= 0 {
+            return Err(SavingsError::OracleNotConfigured);
+        }
+        
+        let asset_client = token::Client::new(&env, &asset);
+        asset_client.transfer(&contributor, &env.current_contract_address(), &amount);
+        
+        // The platform fee is taken in the contributed asset
+        let fee = match Self::get_platform_fees(env.clone()) {
+            Some(fees) => (amount * fees.fee_bps as i128) / 10000,
+            None => 0,
+        };
+        Self::accrue_fee(&env, &asset, fee);
+        
+        let gross = (amount * rate) / RATE_SCALE;
+        let value = ((amount - fee) * rate) / RATE_SCALE;
+        if value <= 0 {
+            return Err(SavingsError::InsufficientAmount);
+        }
+        
+        // Keep the asset itself in escrow so it can be returned as-is
+        let escrow_key = ParticipantKey::AssetEscrow(challenge_id, contributor.clone(), asset.clone());
+        let mut escrow: AssetEscrow = env.storage().persistent()
+            .get(&escrow_key)
+            .unwrap_or(AssetEscrow { amount: 0, value: 0 });
+        escrow.amount += amount - fee;
+        escrow.value += value;
+        env.storage().persistent().set(&escrow_key, &escrow);
+        
+        env.events().publish(
+            (symbol_short!("asset_in"), challenge_id), 
+            (contributor.clone(), asset, amount, value)
+        );
+        
+        Self::credit_contribution(&env, challenge, &contributor, gross, value)?;
+        
+        Ok(value)
+    }
+    
+    /// Schedule recurring contributions pulled from the user's token allowance to this contract
+    pub fn enable_auto_contribute(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+        amount: i128,
+        interval: u64,
+    ) -> Result<(), SavingsError> {
+        user.require_auth();
+        
+        if amount <= 0 || interval == 0 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.participants.contains(&user) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        let schedule = AutoContribution {
+            amount,
+            interval,
+            next_due: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&ParticipantKey::AutoContribution(challenge_id, user.clone()), &schedule);
+        
+        let mut contributors: Vec<Address> = env.storage().persistent()
+            .get(&DataKey::AutoContributors(challenge_id))
+            .unwrap_or(Vec::new(&env));
+        if !contributors.contains(&user) {
+            contributors.push_back(user.clone());
+            env.storage().persistent().set(&DataKey::AutoContributors(challenge_id), &contributors);
+        }
+        
+        env.events().publish(
+            (symbol_short!("auto_on"), challenge_id), 
+            (user, amount, interval)
+        );
+        
+        Ok(())
+    }
+    
+    /// Stop recurring contributions
+    pub fn disable_auto_contribute(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<(), SavingsError> {
+        user.require_auth();
+        
+        env.storage().persistent().remove(&ParticipantKey::AutoContribution(challenge_id, user.clone()));
+        
+        let mut contributors: Vec<Address> = env.storage().persistent()
+            .get(&DataKey::AutoContributors(challenge_id))
+            .unwrap_or(Vec::new(&env));
+        if let Some(position) = contributors.first_index_of(&user) {
+            contributors.remove(position);
+            env.storage().persistent().set(&DataKey::AutoContributors(challenge_id), &contributors);
+        }
+        
+        env.events().publish(
+            (symbol_short!("auto_off"), challenge_id), 
+            user
+        );
+        
+        Ok(())
+    }
+    
+    /// Pull every scheduled contribution that is due (callable by anyone), returning how many succeeded
+    pub fn pull_due_contributions(env: Env, challenge_id: u32) -> Result<u32, SavingsError> {
+        let contributors: Vec<Address> = env.storage().persistent()
+            .get(&DataKey::AutoContributors(challenge_id))
+            .unwrap_or(Vec::new(&env));
+        
+        let now = env.ledger().timestamp();
+        let mut pulled = 0u32;
+        
+        for user in contributors.iter() {
+            let key = ParticipantKey::AutoContribution(challenge_id, user.clone());
+            let schedule: Option<AutoContribution> = env.storage().persistent().get(&key);
+            let mut schedule = match schedule {
+                Some(schedule) if schedule.next_due <= now => schedule,
+                _ => continue,
+            };
+            
+            match Self::record_contribution(&env, challenge_id, &user, schedule.amount, true) {
+                Ok(_) => {
+                    // Skip past any periods missed while nobody called this
+                    let periods_due = (now - schedule.next_due) / schedule.interval + 1;
+                    schedule.next_due += periods_due * schedule.interval;
+                    env.storage().persistent().set(&key, &schedule);
+                    pulled += 1;
+                },
+                Err(_) => {
+                    env.events().publish(
+                        (symbol_short!("auto_fail"), challenge_id), 
+                        user
+                    );
+                },
+            }
+        }
+        
+        Ok(pulled)
+    }
+    
+    /// Post a short encouragement note to a challenge's board
+    pub fn post_note(
+        env: Env,
+        challenge_id: u32,
+        author: Address,
+        message: String,
+    ) -> Result<(), SavingsError> {
+        author.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.creator != author && !challenge.participants.contains(&author) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        if message.is_empty() || message.len() > MAX_NOTE_LENGTH {
+            return Err(SavingsError::MessageTooLong);
+        }
+        
+        let now = env.ledger().timestamp();
+        let last_key = SocialKey::LastNote(challenge_id, author.clone());
+        let last_note: Option<u64> = env.storage().persistent().get(&last_key);
+        if let Some(last_note) = last_note {
+            if now < last_note + NOTE_COOLDOWN {
+                return Err(SavingsError::RateLimited);
+            }
+        }
+        env.storage().persistent().set(&last_key, &now);
+        
+        // Overwrite the oldest slot once the buffer is full
+        let count: u32 = env.storage().persistent()
+            .get(&SocialKey::NoteCount(challenge_id))
+            .unwrap_or(0);
+        let note = Note {
+            author: author.clone(),
+            message,
+            timestamp: now,
+        };
+        env.storage().persistent().set(&SocialKey::Note(challenge_id, count % MAX_NOTES), &note);
+        env.storage().persistent().set(&SocialKey::NoteCount(challenge_id), &(count + 1));
+        
+        env.events().publish(
+            (symbol_short!("note"), challenge_id), 
+            author
+        );
+        
+        Ok(())
+    }
+    
+    /// Propose pushing the deadline out; the proposer's vote is counted
+    pub fn propose_extension(
+        env: Env,
+        challenge_id: u32,
+        proposer: Address,
+        extra_weeks: u32,
+    ) -> Result<(), SavingsError> {
+        proposer.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        if !challenge.participants.contains(&proposer) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        if extra_weeks == 0 || extra_weeks > 52 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        // A challenge can only ever be extended once
+        let existing: Option<ExtensionProposal> = env.storage().persistent()
+            .get(&DataKey::Extension(challenge_id));
+        if let Some(existing) = existing {
+            if existing.applied {
+                return Err(SavingsError::ExtensionUsed);
+            }
+        }
+        
+        let proposal = ExtensionProposal {
+            proposer: proposer.clone(),
+            extra_weeks,
+            votes: Vec::new(&env),
+            applied: false,
+        };
+        env.storage().persistent().set(&DataKey::Extension(challenge_id), &proposal);
+        
+        env.events().publish(
+            (symbol_short!("ext_prop"), challenge_id), 
+            (proposer.clone(), extra_weeks)
+        );
+        
+        Self::record_extension_vote(&env, challenge_id, &proposer)
+    }
+    
+    /// Vote for the pending extension; a majority of participants applies it
+    pub fn vote_extension(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<(), SavingsError> {
+        user.require_auth();
+        
+        Self::record_extension_vote(&env, challenge_id, &user)
+    }
+    
+    /// Finalize a completed challenge
+    pub fn finalize_challenge(
+        env: Env,
+        challenge_id: u32,
+        finalizer: Address,
+    ) -> Result<(), SavingsError> {
+        finalizer.require_auth();
+        
+        // Get challenge
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        // Check if finalizer is authorized (creator, co-admin or participant)
+        if !Self::is_moderator(&env, &challenge, &finalizer) && !challenge.participants.contains(&finalizer) {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if !challenge.is_active {
+            return Err(SavingsError::AlreadyFinalized);
+        }
+        
+        // A rotation closes itself once every turn has been paid out
+        if challenge.mode == ChallengeMode::Rotating {
+            return Err(SavingsError::ChallengeStillActive);
+        }
+        
+        let current_time = env.ledger().timestamp();
+        let goal_reached = challenge.current_amount >= challenge.goal_amount;
+        let time_expired = current_time > challenge.deadline;
+        
+        // Can only finalize if goal reached or time expired
+        if !goal_reached && !time_expired {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        // A group close to its goal gets more time instead
+        if !goal_reached && Self::auto_extend(&env, &mut challenge) {
+            return Ok(());
+        }
+        
+        Self::close_challenge(&env, &mut challenge)
+    }
+    
+    /// Flag a challenge for the arbiter, freezing its payouts until resolved (participants only)
+    pub fn flag_challenge(
+        env: Env,
+        challenge_id: u32,
+        participant: Address,
+        reason: String,
+    ) -> Result<(), SavingsError> {
+        participant.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.participants.contains(&participant) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        if reason.len() > MAX_NOTE_LENGTH {
+            return Err(SavingsError::MessageTooLong);
+        }
+        
+        if env.storage().persistent().has(&DataKey::Dispute(challenge_id)) {
+            return Err(SavingsError::ChallengeDisputed);
+        }
+        
+        let dispute = Dispute {
+            flagged_by: participant.clone(),
+            reason: reason.clone(),
+            flagged_at: env.ledger().timestamp(),
+            resolution: Resolution::Pending,
+        };
+        env.storage().persistent().set(&DataKey::Dispute(challenge_id), &dispute);
+        
+        env.events().publish(
+            (symbol_short!("flagged"), challenge_id), 
+            (participant, reason)
+        );
+        
+        Ok(())
+    }
+    
+    /// Rule on a flagged challenge and unfreeze its payouts (arbiter only)
+    pub fn resolve_dispute(
+        env: Env,
+        challenge_id: u32,
+        arbiter: Address,
+        resolution: Resolution,
+    ) -> Result<(), SavingsError> {
+        arbiter.require_auth();
+        
+        let stored_arbiter: Address = env.storage().instance()
+            .get(&DataKey::Arbiter)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if arbiter != stored_arbiter {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        let mut dispute = Self::get_dispute(env.clone(), challenge_id).ok_or(SavingsError::NoDispute)?;
+        if dispute.resolution != Resolution::Pending {
+            return Err(SavingsError::NoDispute);
+        }
+        
+        match resolution {
+            Resolution::Pending => return Err(SavingsError::InvalidParameters),
+            Resolution::Partial(bps) if bps > 10000 => return Err(SavingsError::InvalidParameters),
+            _ => {}
+        }
+        
+        dispute.resolution = resolution;
+        env.storage().persistent().set(&DataKey::Dispute(challenge_id), &dispute);
+        
+        // A refund ends the challenge so everyone can take their money back
+        if resolution == Resolution::RefundAll {
+            let mut challenge: SavingsChallenge = env.storage().persistent()
+                .get(&DataKey::Challenge(challenge_id))
+                .ok_or(SavingsError::ChallengeNotFound)?;
+            
+            // Matches are bonuses, which the ruling voids; rotations never pay bonuses out at all,
+            // and nothing else has been paid out before finalization
+            if !challenge.finalized || challenge.mode == ChallengeMode::Rotating {
+                Self::refund_sponsorship(&env, &challenge);
+            }
+            if challenge.mode == ChallengeMode::Rotating {
+                Self::refund_rotation_pot(&env, &challenge)?;
+            }
+            
+            if !challenge.finalized {
+                Self::close_challenge(&env, &mut challenge)?;
+            }
+        }
+        
+        env.events().publish(
+            (symbol_short!("resolved"), challenge_id), 
+            (arbiter, resolution)
+        );
+        
+        Ok(())
+    }
+    
+    /// Finalize a challenge past its deadline (callable by anyone), paying the caller a bounty from its fees
+    pub fn finalize_expired(
+        env: Env,
+        challenge_id: u32,
+        caller: Address,
+    ) -> Result<i128, SavingsError> {
+        caller.require_auth();
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.is_active {
+            return Err(SavingsError::AlreadyFinalized);
+        }
+        
+        // Unpaid turns keep a rotation open past its deadline
+        if challenge.mode == ChallengeMode::Rotating {
+            return Err(SavingsError::ChallengeStillActive);
+        }
+        
+        if env.ledger().timestamp() <= challenge.deadline {
+            return Err(SavingsError::NotExpired);
+        }
+        
+        // No bounty for a challenge that moved its deadline instead of closing
+        if Self::auto_extend(&env, &mut challenge) {
+            return Ok(0);
+        }
+        
+        Self::close_challenge(&env, &mut challenge)?;
+        
+        let held: i128 = env.storage().persistent()
+            .get(&FinanceKey::ChallengeFees(challenge_id))
+            .unwrap_or(0);
+        let bounty = match Self::get_platform_fees(env.clone()) {
+            Some(fees) => (held * fees.keeper_bounty_bps as i128) / 10000,
+            None => 0,
+        };
+        
+        if bounty > 0 {
+            env.storage().persistent().set(&FinanceKey::ChallengeFees(challenge_id), &(held - bounty));
+            token::Client::new(&env, &challenge.token).transfer(
+                &env.current_contract_address(),
+                &caller,
+                &bounty
+            );
+        }
+        
+        env.events().publish(
+            (symbol_short!("keeper"), challenge_id), 
+            (caller, bounty)
+        );
+        
+        Ok(bounty)
+    }
+    
+    /// Release a finalized challenge's remaining fees to the protocol's accrued fees (callable by anyone)
+    pub fn sweep_fees(env: Env, challenge_id: u32) -> Result<i128, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        // Keep fees in place while a keeper bounty may still be owed
+        if !challenge.finalized {
+            return Err(SavingsError::ChallengeStillActive);
+        }
+        
+        let held: i128 = env.storage().persistent()
+            .get(&FinanceKey::ChallengeFees(challenge_id))
+            .unwrap_or(0);
+        
+        if held > 0 {
+            env.storage().persistent().set(&FinanceKey::ChallengeFees(challenge_id), &0i128);
+            Self::accrue_fee(&env, &challenge.token, held);
+        }
+        
+        Ok(held)
+    }
+    
+    /// Close the next finished week (callable by anyone): record who missed it and settle their streaks
+    pub fn mark_week_closed(env: Env, challenge_id: u32, week: u32) -> Result<u32, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        // Weeks close strictly in order so freezes are spent the same way every time
+        let last_closed: u32 = env.storage().persistent()
+            .get(&DataKey::LastClosedWeek(challenge_id))
+            .unwrap_or(0);
+        if week <= last_closed {
+            return Err(SavingsError::AlreadyAssessed);
+        }
+        if week != last_closed + 1 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let week_end = challenge.start_time + (week as u64 * WEEK_IN_SECONDS);
+        if week_end > challenge.deadline + WEEK_IN_SECONDS {
+            return Err(SavingsError::InvalidParameters);
+        }
+        if env.ledger().timestamp() < week_end {
+            return Err(SavingsError::RoundNotOver);
+        }
+        
+        env.storage().persistent().set(&DataKey::LastClosedWeek(challenge_id), &week);
+        
+        let mut missed: Vec<Address> = Vec::new(&env);
+        for participant in challenge.participants.iter() {
+            let paid: i128 = env.storage().persistent()
+                .get(&ParticipantKey::WeekPaid(challenge_id, week, participant.clone()))
+                .unwrap_or(0);
+            let missed_week = if challenge.min_weekly_required {
+                paid < Self::amount_due(&env, &challenge, &participant, week)
+            } else {
+                paid == 0
+            };
+            // A declared pause or a contract-wide halt covers the week
+            if !missed_week || Self::is_paused_in(&env, &challenge, &participant, week) {
+                continue;
+            }
+            
+            let stats_key = ParticipantKey::ParticipantStats(challenge_id, participant.clone());
+            let mut stats = Self::get_participant_stats(env.clone(), challenge_id, participant.clone())?;
+            // Freezes only get spent protecting a streak that has started and run out of gap
+            if stats.current_streak > 0
+                && week.saturating_sub(Self::last_counted_week(&challenge, &stats)) > challenge.streak_gap
+            {
+                if stats.freezes_used < challenge.streak_freezes {
+                    stats.freezes_used += 1;
+                } else {
+                    stats.current_streak = 0;
+                    Self::update_ranking(&env, SocialKey::StreakRanking(challenge_id), &participant, 0);
+                }
+                env.storage().persistent().set(&stats_key, &stats);
+            }
+            
+            env.events().publish(
+                (symbol_short!("missed"), challenge_id), 
+                (participant.clone(), week)
+            );
+            missed.push_back(participant);
+        }
+        
+        let missed_count = missed.len();
+        env.storage().persistent().set(&DataKey::MissedWeek(challenge_id, week), &missed);
+        
+        env.events().publish(
+            (symbol_short!("wk_closed"), challenge_id), 
+            (week, missed_count)
+        );
+        
+        Ok(missed_count)
+    }
+    
+    /// Pay a finished week's pot to the next participant in the rotation (callable by anyone)
+    pub fn payout_rotation(env: Env, challenge_id: u32) -> Result<Address, SavingsError> {
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if challenge.mode != ChallengeMode::Rotating {
+            return Err(SavingsError::NotRotating);
+        }
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        Self::require_not_frozen(&env, challenge_id)?;
+        
+        let mut rotation = Self::get_rotation(env.clone(), challenge_id)?;
+        let round = rotation.next_round;
+        let round_end = challenge.start_time + (round as u64 * WEEK_IN_SECONDS);
+        if env.ledger().timestamp() < round_end {
+            return Err(SavingsError::RoundNotOver);
+        }
+        
+        // Record anyone who didn't pay in full this round
+        for participant in challenge.participants.iter() {
+            let paid: i128 = env.storage().persistent()
+                .get(&ParticipantKey::WeekPaid(challenge_id, round, participant.clone()))
+                .unwrap_or(0);
+            
+            if paid < Self::amount_due(&env, &challenge, &participant, round) {
+                let missed = rotation.missed.get(participant.clone()).unwrap_or(0);
+                rotation.missed.set(participant.clone(), missed + 1);
+                
+                env.events().publish(
+                    (symbol_short!("missed"), challenge_id), 
+                    (participant, round)
+                );
+            }
+        }
+        
+        // Next in order who hasn't received yet; members behind on payments wait their turn
+        let mut recipient: Option<Address> = None;
+        let mut fallback: Option<Address> = None;
+        for participant in challenge.participants.iter() {
+            if rotation.received.contains(&participant) {
+                continue;
+            }
+            if fallback.is_none() {
+                fallback = Some(participant.clone());
+            }
+            if rotation.missed.get(participant.clone()).unwrap_or(0) == 0 {
+                recipient = Some(participant);
+                break;
+            }
+        }
+        let recipient = recipient.or(fallback).ok_or(SavingsError::InvalidParameters)?;
+        
+        let payout = rotation.pot;
+        if payout > 0 {
+            token::Client::new(&env, &challenge.token).transfer(
+                &env.current_contract_address(),
+                &recipient,
+                &payout
+            );
+        }
+        
+        rotation.pot = 0;
+        rotation.received.push_back(recipient.clone());
+        rotation.next_round += 1;
+        env.storage().persistent().set(&DataKey::Rotation(challenge_id), &rotation);
+        
+        env.events().publish(
+            (symbol_short!("payout"), challenge_id), 
+            (recipient.clone(), round, payout)
+        );
+        
+        // Every participant has had a turn
+        if rotation.received.len() >= challenge.participants.len() {
+            Self::close_challenge(&env, &mut challenge)?;
+            
+            env.events().publish(
+                (symbol_short!("rot_done"), challenge_id), 
+                round
+            );
+        }
+        
+        log!(&env, "Rotation round {} of challenge {} paid {} to {}", round, challenge_id, payout, recipient);
+        
+        Ok(recipient)
+    }
+    
+    /// Penalize participants who missed a finished week and pay it to those who didn't (callable by anyone)
+    pub fn assess_week(env: Env, challenge_id: u32, week: u32) -> Result<i128, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        if !challenge.min_weekly_required || challenge.missed_week_penalty <= 0 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        // Rotating pots are already paid out, so there is nothing to take a penalty from
+        if challenge.mode == ChallengeMode::Rotating {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let week_end = challenge.start_time + (week as u64 * WEEK_IN_SECONDS);
+        if week == 0 || week_end > challenge.deadline + WEEK_IN_SECONDS {
+            return Err(SavingsError::InvalidParameters);
+        }
+        if env.ledger().timestamp() < week_end {
+            return Err(SavingsError::RoundNotOver);
+        }
+        
+        let assessed_key = DataKey::WeekAssessed(challenge_id, week);
+        if env.storage().persistent().has(&assessed_key) {
+            return Err(SavingsError::AlreadyAssessed);
+        }
+        env.storage().persistent().set(&assessed_key, &true);
+        
+        let mut savers: Vec<Address> = Vec::new(&env);
+        let mut skippers: Vec<Address> = Vec::new(&env);
+        for participant in challenge.participants.iter() {
+            // Someone who already took their share out neither pays into nor draws from the pot
+            if env.storage().persistent().has(&ParticipantKey::Withdrawn(challenge_id, participant.clone())) {
+                continue;
+            }
+            let paid: i128 = env.storage().persistent()
+                .get(&ParticipantKey::WeekPaid(challenge_id, week, participant.clone()))
+                .unwrap_or(0);
+            if paid >= Self::amount_due(&env, &challenge, &participant, week) {
+                savers.push_back(participant);
+            } else if !Self::is_paused_in(&env, &challenge, &participant, week) {
+                skippers.push_back(participant);
+            }
+        }
+        
+        // Nobody to reward: leave everyone's balance alone
+        if savers.is_empty() {
+            return Ok(0);
+        }
+        
+        // A penalty can't take more than the participant still has in the challenge
+        let mut pot: i128 = 0;
+        for skipper in skippers.iter() {
+            let remaining = Self::payout_share(&env, &challenge, &skipper);
+            let penalty = challenge.missed_week_penalty.min(remaining);
+            if penalty <= 0 {
+                continue;
+            }
+            
+            let penalized: i128 = env.storage().persistent()
+                .get(&ParticipantKey::Penalty(challenge_id, skipper.clone()))
+                .unwrap_or(0);
+            env.storage().persistent().set(&ParticipantKey::Penalty(challenge_id, skipper.clone()), &(penalized + penalty));
+            pot += penalty;
+            
+            env.events().publish(
+                (symbol_short!("penalty"), challenge_id), 
+                (skipper, week, penalty)
+            );
+        }
+        
+        Self::route_forfeit(&env, &challenge, pot);
+        
+        env.events().publish(
+            (symbol_short!("assessed"), challenge_id), 
+            (week, skippers.len(), pot)
+        );
+        
+        Ok(pot)
+    }
+    
+    /// Pause saving for a few weeks from the next one (or the first, before the start): paused weeks are
+    /// never penalized, recorded as missed, or held against a streak. Capped at MAX_PAUSE_WEEKS per challenge.
+    pub fn pause_participation(
+        env: Env,
+        user: Address,
+        challenge_id: u32,
+        weeks: u32,
+    ) -> Result<PauseWindow, SavingsError> {
+        user.require_auth();
+        
+        let challenge = Self::open_challenge_for(&env, challenge_id, &user)?;
+        
+        let mut pauses = Self::get_pauses(env.clone(), challenge_id, user.clone());
+        let used: u32 = pauses.iter()
+            .map(|pause| pause.until_week - pause.from_week + 1)
+            .sum();
+        if weeks == 0 || used + weeks > MAX_PAUSE_WEEKS {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        // The current week is already due, so a pause can't excuse it after the fact
+        let now = env.ledger().timestamp();
+        let from_week = if now < challenge.start_time {
+            1
+        } else {
+            ((now - challenge.start_time) / WEEK_IN_SECONDS) as u32 + 2
+        };
+        if let Some(last) = pauses.last() {
+            if last.until_week >= from_week {
+                return Err(SavingsError::InvalidParameters);
+            }
+        }
+        
+        let pause = PauseWindow {
+            from_week,
+            until_week: from_week + weeks - 1,
+        };
+        pauses.push_back(pause.clone());
+        env.storage().persistent().set(&ParticipantKey::Pauses(challenge_id, user.clone()), &pauses);
+        
+        env.events().publish(
+            (symbol_short!("paused"), challenge_id), 
+            (user, pause.from_week, pause.until_week)
+        );
+        
+        Ok(pause)
+    }
+    
+    /// Take savings out of a running challenge, leaving a penalty for the group bonus pot
+    pub fn early_withdraw(
+        env: Env,
+        user: Address,
+        challenge_id: u32,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        user.require_auth();
+        
+        if amount <= 0 {
+            return Err(SavingsError::InsufficientAmount);
+        }
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.allow_early_withdrawal {
+            return Err(SavingsError::EarlyWithdrawalDisabled);
+        }
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        if !challenge.participants.contains(&user) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        Self::require_not_frozen(&env, challenge_id)?;
+        
+        // Rotating pots are already paid out and vault-held funds are locked until settlement
+        if challenge.mode == ChallengeMode::Rotating
+            || env.storage().persistent().has(&FinanceKey::PrizePool(challenge_id))
+        {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let stats_key = ParticipantKey::ParticipantStats(challenge_id, user.clone());
+        let mut stats = Self::get_participant_stats(env.clone(), challenge_id, user.clone())?;
+        
+        // Only principal escrowed in the challenge token, less penalties, can come out early
+        let (penalized, _) = Self::get_penalty_and_bonus(env.clone(), challenge_id, user.clone());
+        let mut available = stats.total_contributed - penalized;
+        for asset in Self::get_accepted_assets(env.clone(), challenge_id).keys().iter() {
+            if let Some(escrow) = Self::get_asset_escrow(env.clone(), challenge_id, user.clone(), asset) {
+                available -= escrow.value;
+            }
+        }
+        if amount > available {
+            return Err(SavingsError::InsufficientAmount);
+        }
+        
+        // The penalty feeds the bonus pot; with nobody else there is no one to share it
+        let others = challenge.participants.len() as i128 - 1;
+        let penalty = if others > 0 {
+            (amount * challenge.early_withdrawal_penalty_bps as i128) / 10000
+        } else {
+            0
+        };
+        let payout = amount - penalty;
+        
+        stats.total_contributed -= amount;
+        env.storage().persistent().set(&stats_key, &stats);
+        Self::update_ranking(&env, SocialKey::AmountRanking(challenge_id), &user, stats.total_contributed);
+        Self::reduce_lifetime_saved(&env, &user, amount);
+        Self::scale_held_rewards(&env, challenge_id, &user, stats.total_contributed, stats.total_contributed + amount);
+        
+        challenge.current_amount -= payout;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        Self::route_forfeit(&env, &challenge, penalty);
+        
+        if payout > 0 {
+            token::Client::new(&env, &challenge.token).transfer(
+                &env.current_contract_address(),
+                &user,
+                &payout
+            );
+        }
+        
+        env.events().publish(
+            (symbol_short!("early_wd"), challenge_id), 
+            (user.clone(), amount, penalty)
+        );
+        
+        log!(&env, "Participant {} withdrew {} early from challenge {}", user, payout, challenge_id);
+        
+        Ok(payout)
+    }
+    
+    /// Withdraw a participant's share of a finalized challenge
+    pub fn withdraw_share(
+        env: Env,
+        challenge_id: u32,
+        participant: Address,
+    ) -> Result<i128, SavingsError> {
+        participant.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.finalized {
+            return Err(SavingsError::ChallengeStillActive);
+        }
+        
+        if !challenge.participants.contains(&participant) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        let withdrawn_key = ParticipantKey::Withdrawn(challenge_id, participant.clone());
+        if env.storage().persistent().has(&withdrawn_key) {
+            return Err(SavingsError::AlreadyWithdrawn);
+        }
+        
+        Self::require_not_frozen(&env, challenge_id)?;
+        
+        let share = Self::payout_share(&env, &challenge, &participant);
+        if share <= 0 {
+            return Err(SavingsError::NothingToWithdraw);
+        }
+        
+        // Mark paid before transferring out
+        env.storage().persistent().set(&withdrawn_key, &true);
+        
+        let payout_fee_bps = match Self::get_platform_fees(env.clone()) {
+            Some(fees) => fees.payout_fee_bps,
+            None => 0,
+        };
+        let share = share - Self::transfer_share(&env, &challenge, &participant, share, payout_fee_bps);
+        
+        // Savers who see a successful challenge through earn completion badges
+        if challenge.current_amount >= challenge.goal_amount {
+            Self::award_completion_badges(&env, challenge_id, &participant);
+        }
+        
+        // Savers earn the completion reward scaled by how much of their target they reached
+        let stats = Self::get_participant_stats(env.clone(), challenge_id, participant.clone())?;
+        let tier = Self::completion_tier(&env, &challenge, &participant);
+        let reward_scale_bps = match tier {
+            CompletionTier::Completed => 10000,
+            CompletionTier::Most => MOST_TIER_REWARD_BPS,
+            CompletionTier::Half => HALF_TIER_REWARD_BPS,
+            CompletionTier::Incomplete => 0,
+        };
+        let quorum_met = Self::quorum_status(&env, &challenge).is_none_or(|quorum| quorum.met);
+        if reward_scale_bps > 0 && quorum_met {
+            Self::issue_completion_reward(&env, &challenge, &participant, &stats, reward_scale_bps);
+        }
+        if quorum_met {
+            Self::pay_deferred_milestone_bonuses(&env, &challenge, &participant, stats.total_contributed);
+        }
+        Self::release_held_rewards(&env, &challenge, &participant, quorum_met);
+        
+        // Tiers and trophies are settled here, one participant at a time, rather than all at finalization
+        if tier == CompletionTier::Completed {
+            let mut lifetime = Self::get_user_lifetime_stats(env.clone(), participant.clone());
+            lifetime.challenges_completed += 1;
+            env.storage().persistent().set(&ParticipantKey::Lifetime(participant.clone()), &lifetime);
+            
+            let finalized_at = Self::get_certificate(env.clone(), challenge_id)
+                .map_or(challenge.deadline, |certificate| certificate.finalized_at);
+            env.events().publish(
+                (symbol_short!("trophy"), challenge_id), 
+                Self::trophy(&env, &challenge, &participant, finalized_at)
+            );
+        }
+        
+        env.events().publish(
+            (symbol_short!("tier"), challenge_id), 
+            (participant.clone(), tier)
+        );
+        
+        env.events().publish(
+            (symbol_short!("withdrawn"), challenge_id), 
+            (participant.clone(), share)
+        );
+        
+        log!(&env, "Participant {} withdrew {} from challenge {}", participant, share, challenge_id);
+        
+        Ok(share)
+    }
+    
+    /// Claim a participant's share of a finalized challenge's bonus pot, including anything added since
+    pub fn claim_bonus(
+        env: Env,
+        challenge_id: u32,
+        participant: Address,
+    ) -> Result<i128, SavingsError> {
+        participant.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.finalized {
+            return Err(SavingsError::ChallengeStillActive);
+        }
+        
+        Self::require_not_frozen(&env, challenge_id)?;
+        
+        // A refund ruling voids the pot along with every other adjustment
+        let ruling = Self::get_dispute(env.clone(), challenge_id).map(|dispute| dispute.resolution);
+        let scale_bps: i128 = match ruling {
+            Some(Resolution::RefundAll) => 0,
+            Some(Resolution::Partial(bps)) => bps as i128,
+            _ => 10000,
+        };
+        
+        let weight: u32 = env.storage().persistent()
+            .get(&ParticipantKey::BonusWeight(challenge_id, participant.clone()))
+            .unwrap_or(0);
+        let earned = (Self::get_bonus_pot(env.clone(), challenge_id) * weight as i128 / 10000) * scale_bps / 10000;
+        
+        let claimed_key = ParticipantKey::BonusClaimed(challenge_id, participant.clone());
+        let claimed: i128 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+        let amount = earned - claimed;
+        if amount <= 0 {
+            return Err(SavingsError::NothingToWithdraw);
+        }
+        
+        env.storage().persistent().set(&claimed_key, &earned);
+        token::Client::new(&env, &challenge.token).transfer(
+            &env.current_contract_address(),
+            &participant,
+            &amount
+        );
+        
+        env.events().publish(
+            (symbol_short!("bonus"), challenge_id), 
+            (participant, amount)
+        );
+        
+        Ok(amount)
+    }
+    
+    /// Claim a participant's share of a reached group milestone's bonus, minted in SaveCoin
+    pub fn claim_group_bonus(
+        env: Env,
+        challenge_id: u32,
+        participant: Address,
+        milestone_index: u32,
+    ) -> Result<i128, SavingsError> {
+        participant.require_auth();
+        
+        let share_key = ParticipantKey::GroupBonusShare(challenge_id, milestone_index, participant.clone());
+        let share: i128 = env.storage().persistent().get(&share_key).unwrap_or(0);
+        if share <= 0 {
+            return Err(SavingsError::NothingToWithdraw);
+        }
+        
+        // A quorum challenge holds its rewards until it ends with enough participants on target
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        if let Some(quorum) = Self::quorum_status(&env, &challenge) {
+            if !challenge.finalized {
+                return Err(SavingsError::ChallengeStillActive);
+            }
+            if !quorum.met {
+                return Err(SavingsError::QuorumNotMet);
+            }
+        }
+        
+        // A solo saver could reach a milestone and pull the money straight back out, so they claim
+        // only after finalization and only if the challenge still holds the milestone's target
+        if challenge.participants.len() < 2 {
+            if !challenge.finalized {
+                return Err(SavingsError::ChallengeStillActive);
+            }
+            let milestone = Self::get_group_milestones(env.clone(), challenge_id)
+                .get(milestone_index)
+                .ok_or(SavingsError::InvalidParameters)?;
+            if challenge.current_amount < milestone.target_amount {
+                return Err(SavingsError::NothingToWithdraw);
+            }
+        }
+        
+        let savecoin: Address = env.storage().instance()
+            .get(&DataKey::SaveCoinToken)
+            .ok_or(SavingsError::InvalidParameters)?;
+        
+        env.storage().persistent().remove(&share_key);
+        
+        let mut key_data = Bytes::from_array(&env, &challenge_id.to_be_bytes());
+        key_data.append(&participant.clone().to_xdr(&env));
+        key_data.extend_from_array(&(RewardType::MilestoneReached as u32).to_be_bytes());
+        key_data.append(&symbol_short!("group").to_xdr(&env));
+        key_data.extend_from_array(&milestone_index.to_be_bytes());
+        let dedupe_key: BytesN<32> = env.crypto().sha256(&key_data).into();
+        
+        SaveCoinClient::new(&env, &savecoin).mint_reward(
+            &env.current_contract_address(),
+            &participant,
+            &share,
+            &RewardType::MilestoneReached,
+            &challenge_id,
+            &10000,
+            &Some(dedupe_key),
+            &false
+        );
+        Self::record_reward(&env, &participant, share);
+        
+        env.events().publish(
+            (symbol_short!("grp_claim"), challenge_id), 
+            (participant, milestone_index, share)
+        );
+        
+        Ok(share)
+    }
+    
+    /// Cheer another participant on, once a day per target, tipping them SaveCoin if a cheer reward is set
+    pub fn cheer(
+        env: Env,
+        challenge_id: u32,
+        supporter: Address,
+        target: Address,
+    ) -> Result<(), SavingsError> {
+        supporter.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        if !challenge.social_features.enable_cheering {
+            return Err(SavingsError::CheeringDisabled);
+        }
+        
+        if supporter == target {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        if !challenge.participants.contains(&supporter) || !challenge.participants.contains(&target) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        // One cheer per supporter and target each day
+        let today = env.ledger().timestamp() / (24 * 60 * 60);
+        let cheer_key = SocialKey::LastCheer(challenge_id, supporter.clone(), target.clone());
+        let last_cheer: Option<u64> = env.storage().persistent().get(&cheer_key);
+        if last_cheer == Some(today) {
+            return Err(SavingsError::RateLimited);
+        }
+        env.storage().persistent().set(&cheer_key, &today);
+        
+        let count = Self::get_cheer_count(env.clone(), challenge_id, target.clone()) + 1;
+        env.storage().persistent().set(&SocialKey::CheerCount(challenge_id, target.clone()), &count);
+        
+        // Tip the target if a cheer reward is configured; the dedupe key stops double tips
+        let reward: i128 = env.storage().instance().get(&SocialKey::CheerReward).unwrap_or(0);
+        let savecoin: Option<Address> = env.storage().instance().get(&DataKey::SaveCoinToken);
+        if let Some(savecoin) = savecoin.filter(|_| reward > 0) {
+            let mut key_data = Bytes::from_array(&env, &challenge_id.to_be_bytes());
+            key_data.append(&supporter.clone().to_xdr(&env));
+            key_data.append(&target.clone().to_xdr(&env));
+            key_data.extend_from_array(&today.to_be_bytes());
+            let dedupe_key: BytesN<32> = env.crypto().sha256(&key_data).into();
+            
+            let minted = SaveCoinClient::new(&env, &savecoin).try_mint_reward(
+                &env.current_contract_address(),
+                &target,
+                &reward,
+                &RewardType::SocialCheer,
+                &challenge_id,
+                &10000,
+                &Some(dedupe_key),
+                &false
+            );
+            if minted.is_err() {
+                log!(&env, "Cheer reward could not be minted for {}", target);
+            } else {
+                Self::record_reward(&env, &target, reward);
+            }
+        }
+        
+        env.events().publish(
+            (symbol_short!("cheer"), challenge_id), 
+            (supporter, target, count)
+        );
+        
+        Ok(())
+    }
+    
+    // ===== QUERY FUNCTIONS =====
+    
+    /// Get challenge details
+    pub fn get_challenge(env: Env, challenge_id: u32) -> Result<SavingsChallenge, SavingsError> {
+        env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)
+    }
+    
+    /// Get the most recent challenge contributions (last 50)
+    pub fn get_contributions(env: Env, challenge_id: u32) -> Result<Vec<Contribution>, SavingsError> {
+        let count = Self::get_contribution_count(env.clone(), challenge_id);
+        let start = count.saturating_sub(MAX_PAGE_SIZE);
+        Self::get_contributions_page(env, challenge_id, start, MAX_PAGE_SIZE)
+    }
+    
+    /// Get the contribution a receipt refers to
+    pub fn get_receipt(env: Env, id: ReceiptId) -> Option<Contribution> {
+        env.storage().persistent().get(&DataKey::Contribution(id.challenge_id, id.sequence))
+    }
+    
+    /// Get the memo left on a batched contribution, if any
+    pub fn get_memo(env: Env, id: ReceiptId) -> Option<String> {
+        env.storage().persistent().get(&DataKey::Memo(id.challenge_id, id.sequence))
+    }
+    
+    /// Get a page of challenge contributions, oldest first
+    pub fn get_contributions_page(
+        env: Env,
+        challenge_id: u32,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Contribution>, SavingsError> {
+        // Verify challenge exists
+        let _challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        let count = Self::get_contribution_count(env.clone(), challenge_id);
+        let end = count.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        
+        let mut page = Vec::new(&env);
+        for index in start..end {
+            let contribution: Option<Contribution> = env.storage().persistent()
+                .get(&DataKey::Contribution(challenge_id, index));
+            if let Some(contribution) = contribution {
+                page.push_back(contribution);
+            }
+        }
+        
+        Ok(page)
+    }
+    
+    /// Get the number of contributions made to a challenge
+    pub fn get_contribution_count(env: Env, challenge_id: u32) -> u32 {
+        env.storage().persistent()
+            .get(&DataKey::ContributionCount(challenge_id))
+            .unwrap_or(0)
+    }
+    
+    /// Re-derive a participant's current streak from their weekly payments, applying the
+    /// challenge's gap, freezes and the participant's pauses in week order
+    pub fn get_streak(env: Env, challenge_id: u32, user: Address) -> Result<u32, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        let now = env.ledger().timestamp().min(challenge.deadline);
+        if now < challenge.start_time {
+            return Ok(0);
+        }
+        let current_week = ((now - challenge.start_time) / WEEK_IN_SECONDS) as u32 + 1;
+        
+        let mut streak: u32 = 0;
+        let mut last_counted: u32 = 0;
+        let mut freezes_used: u32 = 0;
+        for week in 1..=current_week {
+            let paid: i128 = env.storage().persistent()
+                .get(&ParticipantKey::WeekPaid(challenge_id, week, user.clone()))
+                .unwrap_or(0);
+            let counted = if challenge.min_weekly_required {
+                paid >= Self::amount_due(&env, &challenge, &user, week)
+            } else {
+                paid > 0
+            };
+            if counted {
+                streak += 1;
+                last_counted = week;
+                continue;
+            }
+            
+            // The current week can still be paid, so it never breaks a streak
+            if week == current_week
+                || streak == 0
+                || week - last_counted <= challenge.streak_gap
+                || Self::is_paused_in(&env, &challenge, &user, week)
+            {
+                continue;
+            }
+            if freezes_used < challenge.streak_freezes {
+                freezes_used += 1;
+            } else {
+                streak = 0;
+            }
+        }
+        
+        Ok(streak)
+    }
+    
+    /// Get participant statistics for a challenge
+    pub fn get_participant_stats(
+        env: Env, 
+        challenge_id: u32, 
+        participant: Address
+    ) -> Result<ParticipantStats, SavingsError> {
+        // Verify challenge exists
+        let _challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        let stats: ParticipantStats = env.storage().persistent()
+            .get(&ParticipantKey::ParticipantStats(challenge_id, participant))
+            .unwrap_or(ParticipantStats {
+                total_contributed: 0,
+                contribution_count: 0,
+                last_contribution: 0,
+                current_streak: 0,
+                freezes_used: 0,
+                weeks_met: 0,
+                last_week_met: 0,
+            });
+        
+        Ok(stats)
+    }
+    
+    /// Get how a participant is keeping up with the amount due, week by week
+    pub fn get_weekly_compliance(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<WeeklyCompliance, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.participants.contains(&user) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        let stats = Self::get_participant_stats(env.clone(), challenge_id, user.clone())?;
+        
+        // Past the deadline the last week is the current one
+        let now = env.ledger().timestamp().min(challenge.deadline);
+        let current_week = (now.saturating_sub(challenge.start_time) / WEEK_IN_SECONDS) as u32 + 1;
+        
+        let current_week_paid: i128 = env.storage().persistent()
+            .get(&ParticipantKey::WeekPaid(challenge_id, current_week, user.clone()))
+            .unwrap_or(0);
+        let current_week_due = (Self::amount_due(&env, &challenge, &user, current_week) - current_week_paid).max(0);
+        
+        let finished_weeks_met = if stats.last_week_met == current_week {
+            stats.weeks_met - 1
+        } else {
+            stats.weeks_met
+        };
+        
+        Ok(WeeklyCompliance {
+            current_week,
+            weeks_met: stats.weeks_met,
+            weeks_missed: (current_week - 1).saturating_sub(finished_weeks_met),
+            current_week_paid,
+            current_week_due,
+        })
+    }
+    
+    /// Get everything a challenge page shows for one user in a single consistent read
+    pub fn get_challenge_dashboard(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<ChallengeDashboard, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        let stats = Self::get_participant_stats(env.clone(), challenge_id, user.clone())?;
+        
+        // Past the deadline the last week is the current one
+        let now = env.ledger().timestamp().min(challenge.deadline);
+        let current_week = (now.saturating_sub(challenge.start_time) / WEEK_IN_SECONDS) as u32 + 1;
+        let user_target = Self::personal_target(&env, &challenge, &user);
+        
+        let current_week_paid: i128 = env.storage().persistent()
+            .get(&ParticipantKey::WeekPaid(challenge_id, current_week, user.clone()))
+            .unwrap_or(0);
+        
+        let milestones = MilestoneStates {
+            first_contribution: stats.contribution_count > 0,
+            halfway: stats.total_contributed * 2 >= user_target && stats.total_contributed > 0,
+            target_reached: stats.total_contributed >= user_target && stats.total_contributed > 0,
+            year_streak: stats.current_streak >= 52,
+            goal_reached: challenge.current_amount >= challenge.goal_amount,
+        };
+        
+        Ok(ChallengeDashboard {
+            summary: Self::summarize(&env, &challenge),
+            current_week,
+            is_participant: challenge.participants.contains(&user),
+            user_target,
+            user_payout: Self::payout_share(&env, &challenge, &user),
+            current_week_paid,
+            stats,
+            milestones,
+        })
+    }
+    
+    /// Get who paid what in each of `num_weeks` weeks from `start_week`, one row per participant.
+    /// Pages are cut short so a read stays within MAX_MATRIX_CELLS.
+    pub fn get_weekly_matrix(
+        env: Env,
+        challenge_id: u32,
+        start_week: u32,
+        num_weeks: u32,
+    ) -> Result<Vec<MatrixRow>, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if start_week == 0 || challenge.participants.is_empty() {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let total_weeks = ((challenge.deadline - challenge.start_time) / WEEK_IN_SECONDS) as u32;
+        let max_weeks = (MAX_MATRIX_CELLS / challenge.participants.len()).max(1);
+        let end_week = start_week
+            .saturating_add(num_weeks.min(max_weeks))
+            .min(total_weeks + 1);
+        
+        let mut rows = Vec::new(&env);
+        for participant in challenge.participants.iter() {
+            let mut paid = Vec::new(&env);
+            for week in start_week..end_week {
+                let amount: i128 = env.storage().persistent()
+                    .get(&ParticipantKey::WeekPaid(challenge_id, week, participant.clone()))
+                    .unwrap_or(0);
+                paid.push_back(amount);
+            }
+            rows.push_back(MatrixRow { participant, paid });
+        }
+        
+        Ok(rows)
+    }
+    
+    /// Get who missed a week a keeper has closed
+    pub fn get_missed_week(env: Env, challenge_id: u32, week: u32) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&DataKey::MissedWeek(challenge_id, week))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get the deadline extension proposal, if any
+    pub fn get_extension(env: Env, challenge_id: u32) -> Option<ExtensionProposal> {
+        env.storage().persistent().get(&DataKey::Extension(challenge_id))
+    }
+    
+    /// Get the co-admins sharing a challenge's moderation rights
+    pub fn get_co_admins(env: Env, challenge_id: u32) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&DataKey::CoAdmins(challenge_id))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get the gates on openly joining a challenge, if it has any
+    pub fn get_join_requirements(env: Env, challenge_id: u32) -> Option<JoinRequirements> {
+        env.storage().persistent().get(&DataKey::JoinRequirements(challenge_id))
+    }
+    
+    /// Get the ledger sequence a user was first seen at, if they have been
+    pub fn get_first_seen(env: Env, user: Address) -> Option<u32> {
+        env.storage().persistent().get(&ParticipantKey::FirstSeen(user))
+    }
+    
+    /// Get a challenge's auto-extend rule and how much of it has been used, if it has one
+    pub fn get_auto_extend(env: Env, challenge_id: u32) -> Option<AutoExtendRule> {
+        env.storage().persistent().get(&DataKey::AutoExtend(challenge_id))
+    }
+    
+    /// Get the latest notes on a challenge's board, oldest first
+    pub fn get_notes(env: Env, challenge_id: u32) -> Vec<Note> {
+        let count: u32 = env.storage().persistent()
+            .get(&SocialKey::NoteCount(challenge_id))
+            .unwrap_or(0);
+        
+        let mut notes = Vec::new(&env);
+        for index in count.saturating_sub(MAX_NOTES)..count {
+            let note: Option<Note> = env.storage().persistent()
+                .get(&SocialKey::Note(challenge_id, index % MAX_NOTES));
+            if let Some(note) = note {
+                notes.push_back(note);
+            }
+        }
+        notes
+    }
+    
+    /// Get participants ranked by total contributed
+    pub fn get_leaderboard(env: Env, challenge_id: u32, limit: u32) -> Vec<RankingEntry> {
+        if !Self::leaderboard_visible(&env, challenge_id) {
+            return Vec::new(&env);
+        }
+        Self::read_ranking(&env, SocialKey::AmountRanking(challenge_id), limit)
+    }
+    
+    /// Get participants ranked by current streak
+    pub fn get_streak_leaderboard(env: Env, challenge_id: u32, limit: u32) -> Vec<RankingEntry> {
+        if !Self::leaderboard_visible(&env, challenge_id) {
+            return Vec::new(&env);
+        }
+        Self::read_ranking(&env, SocialKey::StreakRanking(challenge_id), limit)
+    }
+    
+    /// Get challenges for a user
+    pub fn get_user_challenges(env: Env, user: Address) -> Vec<u32> {
+        env.storage().persistent()
+            .get(&ParticipantKey::UserChallenges(user))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get a user's savings record across every challenge they have been in
+    pub fn get_user_lifetime_stats(env: Env, user: Address) -> LifetimeStats {
+        env.storage().persistent()
+            .get(&ParticipantKey::Lifetime(user))
+            .unwrap_or(LifetimeStats {
+                total_saved: 0,
+                challenges_completed: 0,
+                longest_streak: 0,
+                rewards_earned: 0,
+            })
+    }
+    
+    /// Get summaries of a user's challenges in one status, a page at a time
+    pub fn get_user_challenges_filtered(
+        env: Env,
+        user: Address,
+        status: ChallengeStatus,
+        start: u32,
+        limit: u32,
+    ) -> Vec<ChallengeSummary> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut page = Vec::new(&env);
+        let mut matched: u32 = 0;
+        
+        for challenge_id in Self::get_user_challenges(env.clone(), user).iter() {
+            if page.len() >= limit {
+                break;
+            }
+            let challenge: Option<SavingsChallenge> = env.storage().persistent()
+                .get(&DataKey::Challenge(challenge_id));
+            let challenge = match challenge {
+                Some(challenge) if Self::challenge_status(&env, &challenge) == status => challenge,
+                _ => continue,
+            };
+            
+            // start counts matching challenges, so pages line up whatever the filter
+            if matched >= start {
+                page.push_back(Self::summarize(&env, &challenge));
+            }
+            matched += 1;
+        }
+        
+        page
+    }
+    
+    /// Get challenge progress information
+    pub fn get_challenge_progress(env: Env, challenge_id: u32) -> Result<(i128, i128, u32, bool), SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        let current_time = env.ledger().timestamp();
+        let weeks_elapsed = (current_time.saturating_sub(challenge.start_time) / (7 * 24 * 60 * 60)) as u32;
+        let is_expired = current_time > challenge.deadline;
+        
+        Ok((
+            challenge.current_amount,
+            challenge.goal_amount,
+            weeks_elapsed,
+            is_expired
+        ))
+    }
+    
+    /// Get rotation progress for a rotating challenge
+    pub fn get_rotation(env: Env, challenge_id: u32) -> Result<RotationState, SavingsError> {
+        env.storage().persistent()
+            .get(&DataKey::Rotation(challenge_id))
+            .ok_or(SavingsError::NotRotating)
+    }
+    
+    /// Get how much a participant has lost to missed-week penalties and earned from others'
+    pub fn get_penalty_and_bonus(env: Env, challenge_id: u32, participant: Address) -> (i128, i128) {
+        let penalty: i128 = env.storage().persistent()
+            .get(&ParticipantKey::Penalty(challenge_id, participant.clone()))
+            .unwrap_or(0);
+        let bonus: i128 = env.storage().persistent()
+            .get(&ParticipantKey::Bonus(challenge_id, participant))
+            .unwrap_or(0);
+        (penalty, bonus)
+    }
+    
+    /// Get the protocol fees accrued in a token and not yet withdrawn
+    pub fn get_accrued_fees(env: Env, token: Address) -> i128 {
+        env.storage().persistent()
+            .get(&FinanceKey::AccruedFees(token))
+            .unwrap_or(0)
+    }
+    
+    /// Get the total paid into a challenge's bonus pot
+    pub fn get_bonus_pot(env: Env, challenge_id: u32) -> i128 {
+        env.storage().persistent()
+            .get(&FinanceKey::BonusPot(challenge_id))
+            .unwrap_or(0)
+    }
+    
+    /// Get how a challenge's bonus pot is weighted (all by total saved unless set)
+    pub fn get_bonus_formula(env: Env, challenge_id: u32) -> BonusFormula {
+        env.storage().persistent()
+            .get(&FinanceKey::BonusFormula(challenge_id))
+            .unwrap_or(BonusFormula {
+                amount_weight_bps: 10000,
+                streak_weight_bps: 0,
+            })
+    }
+    
+    /// Get a participant's progress toward their own target, with their milestones
+    pub fn get_user_progress(
+        env: Env,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<UserProgress, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        Self::user_progress(&env, &challenge, &user)
+    }
+    
+    /// Get a participant's progress in a private challenge as its members, moderators or the
+    /// participant's supporters. Like the rest of the contract's state, the same figures can be read
+    /// through the other queries or the ledger itself; this only lets apps honor who the participant
+    /// chose to share with.
+    pub fn view_user_progress(
+        env: Env,
+        viewer: Address,
+        challenge_id: u32,
+        user: Address,
+    ) -> Result<UserProgress, SavingsError> {
+        viewer.require_auth();
+        
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        let allowed = challenge.visibility == Visibility::Open
+            || challenge.participants.contains(&viewer)
+            || Self::is_moderator(&env, &challenge, &viewer)
+            || Self::get_supporters(env.clone(), challenge_id, user.clone()).contains(&viewer);
+        if !allowed {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        Self::user_progress(&env, &challenge, &user)
+    }
+    
+    /// Get who a participant has let follow their progress
+    pub fn get_supporters(env: Env, challenge_id: u32, participant: Address) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&ParticipantKey::Supporters(challenge_id, participant))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get a participant's milestones on their own savings
+    pub fn get_user_milestones(env: Env, challenge_id: u32, user: Address) -> Result<Vec<Milestone>, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        Ok(Self::user_milestones(&env, &challenge, &user))
+    }
+    
+    /// Get a challenge's milestones on the group total
+    pub fn get_group_milestones(env: Env, challenge_id: u32) -> Vec<Milestone> {
+        env.storage().persistent()
+            .get(&SocialKey::GroupMilestones(challenge_id))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get the most SaveCoin a creator can set as a group bonus (0 when the admin hasn't allowed any)
+    pub fn get_group_bonus_cap(env: Env) -> i128 {
+        env.storage().instance().get(&FinanceKey::GroupBonusCap).unwrap_or(0)
+    }
+    
+    /// Get the SaveCoin a challenge shares out per group milestone, if the creator set one
+    pub fn get_group_bonus(env: Env, challenge_id: u32) -> Option<i128> {
+        env.storage().persistent().get(&FinanceKey::GroupBonus(challenge_id))
+    }
+    
+    /// Get a participant's unclaimed share of a group milestone's bonus
+    pub fn get_group_bonus_share(env: Env, challenge_id: u32, participant: Address, milestone_index: u32) -> i128 {
+        env.storage().persistent()
+            .get(&ParticipantKey::GroupBonusShare(challenge_id, milestone_index, participant))
+            .unwrap_or(0)
+    }
+    
+    /// Get a challenge's team names in the order they were created
+    pub fn get_teams(env: Env, challenge_id: u32) -> Vec<Symbol> {
+        env.storage().persistent()
+            .get(&SocialKey::Teams(challenge_id))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get the team a participant is on, if any
+    pub fn get_team(env: Env, challenge_id: u32, participant: Address) -> Option<Symbol> {
+        env.storage().persistent().get(&ParticipantKey::TeamOf(challenge_id, participant))
+    }
+    
+    /// Get teams ranked by what their current members have saved
+    pub fn get_team_standings(env: Env, challenge_id: u32) -> Result<Vec<TeamStanding>, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        Ok(Self::team_standings(&env, &challenge))
+    }
+    
+    /// Get the completion reward multiplier for the winning team (10000 = no bonus)
+    pub fn get_team_bonus(env: Env, challenge_id: u32) -> u32 {
+        env.storage().persistent()
+            .get(&SocialKey::TeamBonus(challenge_id))
+            .unwrap_or(10000)
+    }
+    
+    /// Get the team that won a finalized challenge, if there was a clear winner
+    pub fn get_winning_team(env: Env, challenge_id: u32) -> Option<Symbol> {
+        env.storage().persistent().get(&SocialKey::WinningTeam(challenge_id))
+    }
+    
+    /// Get a challenge-vs-challenge competition
+    pub fn get_competition(env: Env, competition_id: u32) -> Option<Competition> {
+        env.storage().persistent().get(&DataKey::Competition(competition_id))
+    }
+    
+    /// Get the competition a challenge is in, if any
+    pub fn get_challenge_competition(env: Env, challenge_id: u32) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::ChallengeCompetition(challenge_id))
+    }
+    
+    /// Get both sides' current score on the competition's metric
+    pub fn get_competition_progress(env: Env, competition_id: u32) -> Result<(i128, i128), SavingsError> {
+        let competition = Self::get_competition(env.clone(), competition_id)
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        let score_a = Self::competition_score(&env, competition.challenge_a, competition.metric)?;
+        let score_b = Self::competition_score(&env, competition.challenge_b, competition.metric)?;
+        Ok((score_a, score_b))
+    }
+    
+    /// Get the completion certificate anchored when a challenge was finalized
+    pub fn get_certificate(env: Env, challenge_id: u32) -> Option<Certificate> {
+        env.storage().persistent().get(&DataKey::Certificate(challenge_id))
+    }
+    
+    /// Get the completion tier a participant finished a challenge in, once it is finalized
+    pub fn get_completion_tier(env: Env, challenge_id: u32, participant: Address) -> Option<CompletionTier> {
+        let challenge: SavingsChallenge = env.storage().persistent().get(&DataKey::Challenge(challenge_id))?;
+        if !challenge.finalized || !challenge.participants.contains(&participant) {
+            return None;
+        }
+        Some(Self::completion_tier(&env, &challenge, &participant))
+    }
+    
+    /// Get the trophy a participant earned by completing a finalized challenge, for wallets to display
+    pub fn get_trophy_metadata(env: Env, challenge_id: u32, user: Address) -> Option<Trophy> {
+        let challenge: SavingsChallenge = env.storage().persistent().get(&DataKey::Challenge(challenge_id))?;
+        if Self::get_completion_tier(env.clone(), challenge_id, user.clone()) != Some(CompletionTier::Completed) {
+            return None;
+        }
+        
+        let finalized_at = Self::get_certificate(env.clone(), challenge_id)
+            .map_or(challenge.deadline, |certificate| certificate.finalized_at);
+        Some(Self::trophy(&env, &challenge, &user, finalized_at))
+    }
+    
+    /// Get the users waiting for a place in a challenge, first in line first
+    pub fn get_waitlist(env: Env, challenge_id: u32) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&DataKey::Waitlist(challenge_id))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Get every pause a participant has declared in a challenge
+    pub fn get_pauses(env: Env, challenge_id: u32, user: Address) -> Vec<PauseWindow> {
+        env.storage().persistent()
+            .get(&ParticipantKey::Pauses(challenge_id, user))
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Check whether a participant's current week is covered by their own pause or a contract-wide halt
+    pub fn is_paused(env: Env, challenge_id: u32, user: Address) -> Result<bool, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        let current_week = (env.ledger().timestamp().saturating_sub(challenge.start_time) / WEEK_IN_SECONDS) as u32 + 1;
+        Ok(Self::is_paused_in(&env, &challenge, &user, current_week))
+    }
+    
+    /// Get the number of cheers a participant has received
+    pub fn get_cheer_count(env: Env, challenge_id: u32, user: Address) -> u32 {
+        env.storage().persistent()
+            .get(&SocialKey::CheerCount(challenge_id, user))
+            .unwrap_or(0)
+    }
+    
+    /// Get the charity a challenge donates penalties and forfeits to, if any
+    pub fn get_charity(env: Env, challenge_id: u32) -> Option<Address> {
+        env.storage().persistent().get(&FinanceKey::Charity(challenge_id))
+    }
+    
+    /// Get the total a challenge has donated to its charity
+    pub fn get_donated(env: Env, challenge_id: u32) -> i128 {
+        env.storage().persistent()
+            .get(&FinanceKey::Donated(challenge_id))
+            .unwrap_or(0)
+    }
+    
+    /// Get a challenge's sponsor match, if any
+    pub fn get_sponsorship(env: Env, challenge_id: u32) -> Option<Sponsorship> {
+        env.storage().persistent().get(&FinanceKey::Sponsor(challenge_id))
+    }
+    
+    /// Get the dispute raised on a challenge, if any
+    pub fn get_dispute(env: Env, challenge_id: u32) -> Option<Dispute> {
+        env.storage().persistent().get(&DataKey::Dispute(challenge_id))
+    }
+    
+    /// Get the creator bond settings
+    pub fn get_bond_config(env: Env) -> Option<BondConfig> {
+        env.storage().instance().get(&FinanceKey::BondConfig)
+    }
+    
+    /// Get the creator bond still held for a challenge
+    pub fn get_bond(env: Env, challenge_id: u32) -> i128 {
+        env.storage().persistent()
+            .get(&FinanceKey::Bond(challenge_id))
+            .unwrap_or(0)
+    }
+    
+    /// Get the platform fee configuration
+    pub fn get_platform_fees(env: Env) -> Option<PlatformFees> {
+        env.storage().instance().get(&FinanceKey::PlatformFees)
+    }
+    
+    /// Get a participant's recurring contribution schedule
+    pub fn get_auto_contribution(env: Env, challenge_id: u32, user: Address) -> Option<AutoContribution> {
+        env.storage().persistent().get(&ParticipantKey::AutoContribution(challenge_id, user))
+    }
+    
+    /// Get the extra assets a challenge accepts and the oracle pair pricing each
+    pub fn get_accepted_assets(env: Env, challenge_id: u32) -> Map<Address, String> {
+        env.storage().persistent()
+            .get(&FinanceKey::AcceptedAssets(challenge_id))
+            .unwrap_or(Map::new(&env))
+    }
+    
+    /// Get what a participant has escrowed in one of a challenge's extra assets
+    pub fn get_asset_escrow(env: Env, challenge_id: u32, participant: Address, asset: Address) -> Option<AssetEscrow> {
+        env.storage().persistent().get(&ParticipantKey::AssetEscrow(challenge_id, participant, asset))
+    }
+    
+    /// Get the vault position of a no-loss prize or yield-routed challenge
+    pub fn get_prize_pool(env: Env, challenge_id: u32) -> Option<PrizePool> {
+        env.storage().persistent().get(&FinanceKey::PrizePool(challenge_id))
+    }
+    
+    /// Browse active open challenges
+    pub fn list_public_challenges(env: Env, start: u32, limit: u32) -> Vec<ChallengeSummary> {
+        Self::summarize_listing(&env, DataKey::PublicChallenges, start, limit)
+    }
+    
+    /// List a page of active open challenges in a category
+    pub fn list_challenges_by_category(env: Env, category: Symbol, start: u32, limit: u32) -> Vec<ChallengeSummary> {
+        Self::summarize_listing(&env, DataKey::CategoryChallenges(category), start, limit)
+    }
+    
+    /// List a page of active open challenges carrying a tag
+    pub fn list_challenges_by_tag(env: Env, tag: Symbol, start: u32, limit: u32) -> Vec<ChallengeSummary> {
+        Self::summarize_listing(&env, DataKey::TagChallenges(tag), start, limit)
+    }
+    
+    /// Summarize a page of the challenges in a discovery index
+    fn summarize_listing(env: &Env, key: DataKey, start: u32, limit: u32) -> Vec<ChallengeSummary> {
+        let index: Vec<u32> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(index.len());
+        let mut page = Vec::new(env);
+        
+        for i in start..end {
+            let challenge_id = index.get(i).unwrap();
+            let challenge: Option<SavingsChallenge> = env.storage().persistent()
+                .get(&DataKey::Challenge(challenge_id));
+            
+            if let Some(challenge) = challenge {
+                page.push_back(Self::summarize(env, &challenge));
+            }
+        }
+        
+        page
+    }
+    
+    /// Where a challenge stands: running, over with its goal met, or over without it
+    fn challenge_status(env: &Env, challenge: &SavingsChallenge) -> ChallengeStatus {
+        if challenge.is_active && env.ledger().timestamp() <= challenge.deadline {
+            ChallengeStatus::Active
+        } else if challenge.current_amount >= challenge.goal_amount {
+            ChallengeStatus::Completed
+        } else {
+            ChallengeStatus::Expired
+        }
+    }
+    
+    /// Build the listing summary of a challenge
+    fn summarize(env: &Env, challenge: &SavingsChallenge) -> ChallengeSummary {
+        let current_time = env.ledger().timestamp();
+        let weeks_remaining = if challenge.deadline > current_time {
+            (challenge.deadline - current_time).div_ceil(WEEK_IN_SECONDS) as u32
+        } else {
+            0
+        };
+        
+        ChallengeSummary {
+            id: challenge.id,
+            name: challenge.name.clone(),
+            goal_amount: challenge.goal_amount,
+            current_amount: challenge.current_amount,
+            participant_count: challenge.participants.len(),
+            max_participants: challenge.max_participants,
+            weeks_remaining,
+        }
+    }
+    
+    /// Check if a participant has withdrawn their share
+    pub fn has_withdrawn(env: Env, challenge_id: u32, participant: Address) -> bool {
+        env.storage().persistent().has(&ParticipantKey::Withdrawn(challenge_id, participant))
+    }
+    
+    /// Check if user is participant in challenge
+    pub fn is_participant(env: Env, challenge_id: u32, user: Address) -> Result<bool, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        Ok(challenge.participants.contains(&user))
+    }
+    
+    // ===== ADMIN FUNCTIONS =====
+    
+    /// Set the contribution and payout fees, the keeper's share, and where the rest goes (admin only)
+    pub fn set_platform_fees(
+        env: Env,
+        admin: Address,
+        fee_bps: u32,
+        payout_fee_bps: u32,
+        keeper_bounty_bps: u32,
+        treasury: Address,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        // Fees are capped at 5% of a contribution or payout
+        if fee_bps > 500 || payout_fee_bps > 500 || keeper_bounty_bps > 10000 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let fees = PlatformFees {
+            fee_bps,
+            payout_fee_bps,
+            keeper_bounty_bps,
+            treasury,
+        };
+        env.storage().instance().set(&FinanceKey::PlatformFees, &fees);
+        
+        env.events().publish(
+            (symbol_short!("fees_set"), admin), 
+            (fee_bps, payout_fee_bps, keeper_bounty_bps)
+        );
+        
+        Ok(())
+    }
+    
+    /// Send the protocol fees accrued in a token to the treasury (admin only)
+    pub fn withdraw_fees(
+        env: Env,
+        admin: Address,
+        token: Address,
+    ) -> Result<i128, SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        let fees = Self::get_platform_fees(env.clone()).ok_or(SavingsError::InvalidParameters)?;
+        let accrued = Self::get_accrued_fees(env.clone(), token.clone());
+        if accrued <= 0 {
+            return Err(SavingsError::NothingToWithdraw);
+        }
+        
+        env.storage().persistent().set(&FinanceKey::AccruedFees(token.clone()), &0i128);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &fees.treasury,
+            &accrued
+        );
+        
+        env.events().publish(
+            (symbol_short!("fees_out"), token), 
+            (fees.treasury, accrued)
+        );
+        
+        Ok(accrued)
+    }
+    
+    /// Set the yield vault no-loss prize challenges use for a token (admin only)
+    pub fn set_yield_vault(
+        env: Env,
+        admin: Address,
+        token: Address,
+        vault: Address,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        env.storage().instance().set(&FinanceKey::YieldVault(token.clone()), &vault);
+        
+        env.events().publish(
+            (symbol_short!("vault_set"), token), 
+            vault
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the exchange rate feed multi-asset challenges value contributions with (admin only)
+    pub fn set_rate_oracle(
+        env: Env,
+        admin: Address,
+        oracle: Address,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        env.storage().instance().set(&FinanceKey::RateOracle, &oracle);
+        
+        env.events().publish(
+            (symbol_short!("oracle"), admin), 
+            oracle
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the refundable bond creators post for new challenges and the finalization grace period (admin only)
+    pub fn set_bond_config(
+        env: Env,
+        admin: Address,
+        amount: i128,
+        grace_period: u64,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if amount < 0 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        let config = BondConfig {
+            amount,
+            grace_period,
+        };
+        env.storage().instance().set(&FinanceKey::BondConfig, &config);
+        
+        env.events().publish(
+            (symbol_short!("bond_set"), admin), 
+            (amount, grace_period)
+        );
+        
+        Ok(())
+    }
+    
+    /// Set who resolves challenge disputes (admin only)
+    pub fn set_arbiter(
+        env: Env,
+        admin: Address,
+        arbiter: Address,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        env.storage().instance().set(&DataKey::Arbiter, &arbiter);
+        
+        env.events().publish(
+            (symbol_short!("arbiter"), admin), 
+            arbiter
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the SaveCoin contract that mints rewards and badges; this contract must be one of its minters (admin only)
+    pub fn set_savecoin_token(
+        env: Env,
+        admin: Address,
+        savecoin: Address,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        env.storage().instance().set(&DataKey::SaveCoinToken, &savecoin);
+        
+        env.events().publish(
+            (symbol_short!("savecoin"), admin), 
+            savecoin
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the SaveCoin tip minted to a cheered participant; zero turns it off (admin only)
+    pub fn set_cheer_reward(
+        env: Env,
+        admin: Address,
+        amount: i128,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if amount < 0 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        if amount == 0 {
+            env.storage().instance().remove(&SocialKey::CheerReward);
+        } else {
+            env.storage().instance().set(&SocialKey::CheerReward, &amount);
+        }
+        
+        env.events().publish(
+            (symbol_short!("cheer_rwd"), admin), 
+            amount
+        );
+        
+        Ok(())
+    }
+    
+    /// Set the most SaveCoin a creator can offer as a group bonus; zero turns group bonuses off (admin only)
+    pub fn set_group_bonus_cap(
+        env: Env,
+        admin: Address,
+        cap: i128,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if cap < 0 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        if cap == 0 {
+            env.storage().instance().remove(&FinanceKey::GroupBonusCap);
+        } else {
+            env.storage().instance().set(&FinanceKey::GroupBonusCap, &cap);
+        }
+        
+        env.events().publish(
+            (symbol_short!("grp_cap"), admin), 
+            cap
+        );
+        
+        Ok(())
+    }
+    
+    /// Pit two active challenges against each other for a SaveCoin prize (admin only)
+    pub fn link_competition(
+        env: Env,
+        admin: Address,
+        challenge_a: u32,
+        challenge_b: u32,
+        metric: CompetitionMetric,
+        prize: i128,
+    ) -> Result<u32, SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        if challenge_a == challenge_b || prize < 0 {
+            return Err(SavingsError::InvalidParameters);
+        }
+        
+        for challenge_id in [challenge_a, challenge_b] {
+            let challenge: SavingsChallenge = env.storage().persistent()
+                .get(&DataKey::Challenge(challenge_id))
+                .ok_or(SavingsError::ChallengeNotFound)?;
+            
+            if !challenge.is_active {
+                return Err(SavingsError::ChallengeInactive);
+            }
+            
+            if env.storage().persistent().has(&DataKey::ChallengeCompetition(challenge_id)) {
+                return Err(SavingsError::AlreadyLinked);
+            }
+        }
+        
+        let competition_id: u32 = env.storage().instance()
+            .get(&DataKey::NextCompetitionId)
+            .unwrap_or(1);
+        env.storage().instance().set(&DataKey::NextCompetitionId, &(competition_id + 1));
+        
+        let competition = Competition {
+            challenge_a,
+            challenge_b,
+            metric,
+            prize,
+            winner: None,
+            settled: false,
+        };
+        env.storage().persistent().set(&DataKey::Competition(competition_id), &competition);
+        env.storage().persistent().set(&DataKey::ChallengeCompetition(challenge_a), &competition_id);
+        env.storage().persistent().set(&DataKey::ChallengeCompetition(challenge_b), &competition_id);
+        
+        env.events().publish(
+            (symbol_short!("compete"), competition_id), 
+            (challenge_a, challenge_b, metric, prize)
+        );
+        
+        Ok(competition_id)
+    }
+    
+    /// Emergency pause/unpause (admin only)
+    pub fn set_challenge_active(
+        env: Env,
+        challenge_id: u32,
+        active: bool,
+        admin: Address
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        // A settled challenge's payouts are already open; reviving it would let it take money again
+        if challenge.finalized {
+            return Err(SavingsError::AlreadyFinalized);
+        }
+        
+        challenge.is_active = active;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        Self::update_public_index(&env, &challenge);
+        
+        env.events().publish(
+            (symbol_short!("admin_set"), challenge_id), 
+            (admin, active)
+        );
+        
+        Ok(())
+    }
+    
+    /// Halt or resume creating, joining and contributing across every challenge; finalizing and
+    /// withdrawing keep working, and weeks a halt overlaps count as paused for everyone (admin only)
+    pub fn set_contract_paused(
+        env: Env,
+        admin: Address,
+        paused: bool,
+    ) -> Result<(), SavingsError> {
+        admin.require_auth();
+        
+        let stored_admin: Address = env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::NotAuthorized)?;
+        
+        if admin != stored_admin {
+            return Err(SavingsError::NotAuthorized);
+        }
+        
+        // Remember when the halt began so the weeks it covers can be exempted afterwards
+        let halted_since: Option<u64> = env.storage().instance().get(&DataKey::ContractPaused);
+        match (paused, halted_since) {
+            (true, None) => env.storage().instance().set(&DataKey::ContractPaused, &env.ledger().timestamp()),
+            (false, Some(from)) => {
+                env.storage().instance().remove(&DataKey::ContractPaused);
+                let mut windows = Self::get_halt_windows(env.clone());
+                windows.push_back(HaltWindow { from, until: env.ledger().timestamp() });
+                env.storage().persistent().set(&DataKey::HaltWindows, &windows);
+            },
+            _ => {},
+        }
+        
+        env.events().publish(
+            (symbol_short!("halted"), admin), 
+            paused
+        );
+        
+        Ok(())
+    }
+    
+    /// Check whether creating, joining and contributing are halted
+    pub fn is_contract_paused(env: Env) -> bool {
+        env.storage().instance().has(&DataKey::ContractPaused)
+    }
+    
+    /// Get every finished contract-wide halt; weeks they overlap are never penalized or held against a streak
+    pub fn get_halt_windows(env: Env) -> Vec<HaltWindow> {
+        env.storage().persistent()
+            .get(&DataKey::HaltWindows)
+            .unwrap_or(Vec::new(&env))
+    }
+    
+    /// Extend the TTL of a challenge and a page of its participants' entries, returning where the next
+    /// page starts; anyone can call this to keep a quiet challenge from being archived. Individual
+    /// contribution records are not extended.
+    pub fn extend_challenge_ttl(env: Env, challenge_id: u32, start: u32, limit: u32) -> Result<u32, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        Self::bump_instance(&env);
+        for key in [
+            DataKey::Challenge(challenge_id),
+            DataKey::ContributionCount(challenge_id),
+            DataKey::Rotation(challenge_id),
+            DataKey::Dispute(challenge_id),
+            DataKey::Certificate(challenge_id),
+        ] {
+            Self::bump_entry(&env, &key);
+        }
+        for key in [
+            SocialKey::GroupMilestones(challenge_id),
+            SocialKey::AmountRanking(challenge_id),
+            SocialKey::StreakRanking(challenge_id),
+            SocialKey::Teams(challenge_id),
+            SocialKey::TeamBonus(challenge_id),
+            SocialKey::WinningTeam(challenge_id),
+        ] {
+            Self::bump_entry(&env, &key);
+        }
+        for key in [
+            FinanceKey::PrizePool(challenge_id),
+            FinanceKey::AcceptedAssets(challenge_id),
+            FinanceKey::Sponsor(challenge_id),
+            FinanceKey::Bond(challenge_id),
+            FinanceKey::BonusPot(challenge_id),
+            FinanceKey::BonusFormula(challenge_id),
+            FinanceKey::ChallengeFees(challenge_id),
+            FinanceKey::Charity(challenge_id),
+            FinanceKey::PendingDonation(challenge_id),
+            FinanceKey::GroupBonus(challenge_id),
+        ] {
+            Self::bump_entry(&env, &key);
+        }
+        
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(challenge.participants.len());
+        for i in start..end {
+            Self::bump_participant(&env, challenge_id, &challenge.participants.get(i).unwrap());
+        }
+        
+        Ok(end)
+    }
+    
+    /// Get contract information
+    pub fn get_contract_info(env: Env) -> (String, String) {
+        env.storage().instance()
+            .get(&DataKey::ContractInfo)
+            .unwrap_or((
+                String::from_str(&env, "StellarSave Challenge Contract"),
+                String::from_str(&env, "1.0.0")
+            ))
+    }
+    
+    // ===== HELPER FUNCTIONS =====
+    
+    /// Escrow a contribution and update the challenge, weekly and participant records
+    fn record_contribution(
+        env: &Env,
+        challenge_id: u32,
+        contributor: &Address,
+        amount: i128,
+        via_allowance: bool,
+    ) -> Result<ReceiptId, SavingsError> {
+        if amount <= 0 {
+            return Err(SavingsError::InsufficientAmount);
+        }
+        
+        let challenge = Self::open_challenge_for(env, challenge_id, contributor)?;
+        
+        // Escrow the contribution until the challenge is finalized
+        let token_client = token::Client::new(env, &challenge.token);
+        if via_allowance {
+            // Keeper pulls must fail softly so one short allowance doesn't block the batch
+            let pulled = token_client.try_transfer_from(
+                &env.current_contract_address(),
+                contributor,
+                &env.current_contract_address(),
+                &amount
+            );
+            if !matches!(pulled, Ok(Ok(()))) {
+                return Err(SavingsError::AutoPullFailed);
+            }
+        } else {
+            token_client.transfer(
+                contributor,
+                &env.current_contract_address(),
+                &amount
+            );
+        }
+        
+        Self::credit_escrowed(env, challenge, contributor, amount)
+    }
+    
+    /// Credit a contribution already escrowed in the challenge token, holding back the platform fee
+    fn credit_escrowed(
+        env: &Env,
+        challenge: SavingsChallenge,
+        contributor: &Address,
+        amount: i128,
+    ) -> Result<ReceiptId, SavingsError> {
+        let challenge_id = challenge.id;
+        
+        // Hold back the platform fee; only the rest counts toward the challenge
+        let fee = match Self::get_platform_fees(env.clone()) {
+            Some(fees) => (amount * fees.fee_bps as i128) / 10000,
+            None => 0,
+        };
+        if fee > 0 {
+            let held: i128 = env.storage().persistent()
+                .get(&FinanceKey::ChallengeFees(challenge_id))
+                .unwrap_or(0);
+            env.storage().persistent().set(&FinanceKey::ChallengeFees(challenge_id), &(held + fee));
+        }
+        let gross = amount;
+        let amount = gross - fee;
+        
+        // No-loss prize and yield-routed challenges put the escrow to work in the yield vault
+        if env.storage().persistent().has(&FinanceKey::PrizePool(challenge_id)) {
+            Self::deposit_to_vault(env, challenge_id, &challenge.token, amount)?;
+        }
+        
+        Self::credit_contribution(env, challenge, contributor, gross, amount)
+    }
+    
+    /// Load a challenge that is still taking contributions from this participant
+    fn open_challenge_for(
+        env: &Env,
+        challenge_id: u32,
+        contributor: &Address,
+    ) -> Result<SavingsChallenge, SavingsError> {
+        Self::require_not_paused(env)?;
+        
+        // Get challenge
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        // Validate challenge state
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        if env.ledger().timestamp() < challenge.start_time {
+            return Err(SavingsError::ChallengeNotStarted);
+        }
+        
+        if env.ledger().timestamp() > challenge.deadline
+            && (challenge.current_amount >= challenge.goal_amount || !Self::auto_extend(env, &mut challenge))
+        {
+            return Err(SavingsError::ChallengeExpired);
+        }
+        
+        // Check if contributor is a participant
+        if !challenge.participants.contains(contributor) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        Ok(challenge)
+    }
+    
+    /// Credit an escrowed contribution (`gross` before fees, `amount` after) to the challenge records
+    fn credit_contribution(
+        env: &Env,
+        mut challenge: SavingsChallenge,
+        contributor: &Address,
+        gross: i128,
+        amount: i128,
+    ) -> Result<ReceiptId, SavingsError> {
+        let challenge_id = challenge.id;
+        let current_time = env.ledger().timestamp();
+        
+        // Calculate week number
+        let weeks_elapsed = (current_time - challenge.start_time) / (7 * 24 * 60 * 60);
+        let week_number = weeks_elapsed as u32 + 1;
+        
+        // Create contribution record
+        let contribution = Contribution {
+            contributor: contributor.clone(),
+            amount,
+            timestamp: current_time,
+            week_number,
+        };
+        
+        // Append under its own key so a contribution never rewrites the whole history
+        let index = Self::get_contribution_count(env.clone(), challenge_id);
+        env.storage().persistent().set(&DataKey::Contribution(challenge_id, index), &contribution);
+        env.storage().persistent().set(&DataKey::ContributionCount(challenge_id), &(index + 1));
+        
+        // Update challenge amount
+        challenge.current_amount += amount;
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        
+        // Track what each participant paid in per week (before fees, so fees can't cause a miss)
+        let paid_key = ParticipantKey::WeekPaid(challenge_id, week_number, contributor.clone());
+        let paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+        env.storage().persistent().set(&paid_key, &(paid + gross));
+        
+        // Rotating challenges pool the money for this week's recipient
+        if challenge.mode == ChallengeMode::Rotating {
+            let mut rotation = Self::get_rotation(env.clone(), challenge_id)?;
+            rotation.pot += amount;
+            env.storage().persistent().set(&DataKey::Rotation(challenge_id), &rotation);
+        }
+        
+        // A sponsor matches regular saving: only what counts toward this week's amount due
+        let due = Self::amount_due(env, &challenge, contributor, week_number);
+        let matchable = amount.min((due - paid).max(0));
+        if matchable > 0 {
+            Self::apply_sponsor_match(env, challenge_id, contributor, matchable);
+        }
+        
+        // Update participant stats
+        let mut stats: ParticipantStats = env.storage().persistent()
+            .get(&ParticipantKey::ParticipantStats(challenge_id, contributor.clone()))
+            .unwrap_or(ParticipantStats {
+                total_contributed: 0,
+                contribution_count: 0,
+                last_contribution: 0,
+                current_streak: 0,
+                freezes_used: 0,
+                weeks_met: 0,
+                last_week_met: 0,
+            });
+        
+        // Partial top-ups add up; the week is met once they reach the amount due
+        let week_met = paid < due && paid + gross >= due;
+        if paid + gross < due {
+            env.events().publish(
+                (symbol_short!("short"), challenge_id), 
+                (contributor.clone(), week_number, paid + gross, due)
+            );
+        }
+        
+        // Update streak by week: consecutive weeks extend it, and freezes can cover missed weeks.
+        // When min_weekly_required is set, only weeks paid in full count.
+        let previous_streak = stats.current_streak;
+        let counted_before = if challenge.min_weekly_required {
+            stats.weeks_met > 0
+        } else {
+            stats.contribution_count > 0
+        };
+        if challenge.min_weekly_required && !week_met {
+            // Still short this week: the streak moves once it is paid in full
+        } else if !counted_before {
+            stats.current_streak = 1;
+        } else {
+            let last_week = Self::last_counted_week(&challenge, &stats);
+            // Weeks a keeper already closed have been settled against the streak, and the
+            // first streak_gap empty weeks after the last counted one are allowed
+            let last_closed: u32 = env.storage().persistent()
+                .get(&DataKey::LastClosedWeek(challenge_id))
+                .unwrap_or(0);
+            let settled_week = last_closed.max(last_week + challenge.streak_gap);
+            let missed_weeks = week_number.saturating_sub(settled_week + 1)
+                - Self::paused_weeks_between(env, &challenge, contributor, settled_week, week_number);
+            let freezes_left = challenge.streak_freezes.saturating_sub(stats.freezes_used);
+            
+            if week_number == last_week {
+                // Already counted this week
+            } else if missed_weeks == 0 {
+                stats.current_streak += 1;
+            } else if missed_weeks <= freezes_left {
+                stats.freezes_used += missed_weeks;
+                stats.current_streak += 1;
+                
+                env.events().publish(
+                    (symbol_short!("freeze"), challenge_id), 
+                    (contributor.clone(), missed_weeks, freezes_left - missed_weeks)
+                );
+            } else {
+                stats.current_streak = 1; // Reset streak
+            }
+        }
+        
+        if week_met {
+            stats.weeks_met += 1;
+            stats.last_week_met = week_number;
+        }
+        
+        stats.total_contributed += amount;
+        stats.contribution_count += 1;
+        stats.last_contribution = current_time;
+        
+        // A full year of weekly saving is a milestone worth a badge
+        if stats.current_streak == 52 && previous_streak != 52 {
+            Self::award_badge(env, contributor, BADGE_52_WEEK_STREAK, challenge_id);
+        }
+        
+        // SaveCoin rewards: once per counted week, once per streak step, and at half and all of the personal target
+        let week_counted = if challenge.min_weekly_required { week_met } else { paid == 0 };
+        if week_counted {
+            Self::issue_reward(env, contributor, &challenge, RewardType::WeeklyContribution, gross, stats.current_streak, week_number);
+        }
+        if stats.current_streak > previous_streak && stats.current_streak > 1 {
+            Self::issue_reward(env, contributor, &challenge, RewardType::StreakBonus, gross, stats.current_streak, week_number);
+        }
+        let target = Self::personal_target(env, &challenge, contributor);
+        let saved_before = stats.total_contributed - amount;
+        for (slot, threshold) in [(1u32, target / 2), (2u32, target)] {
+            if threshold > 0 && saved_before < threshold && stats.total_contributed >= threshold {
+                Self::issue_reward(env, contributor, &challenge, RewardType::MilestoneReached, gross, stats.current_streak, slot);
+            }
+        }
+        
+        env.storage().persistent().set(
+            &ParticipantKey::ParticipantStats(challenge_id, contributor.clone()), 
+            &stats
+        );
+        
+        let mut lifetime = Self::get_user_lifetime_stats(env.clone(), contributor.clone());
+        lifetime.total_saved += amount;
+        lifetime.longest_streak = lifetime.longest_streak.max(stats.current_streak);
+        env.storage().persistent().set(&ParticipantKey::Lifetime(contributor.clone()), &lifetime);
+        
+        Self::check_user_milestones(env, &challenge, contributor, stats.total_contributed);
+        if challenge.social_features.allow_group_milestone {
+            Self::check_group_milestones(env, &challenge);
+        }
+        
+        Self::update_ranking(env, SocialKey::AmountRanking(challenge_id), contributor, stats.total_contributed);
+        Self::update_ranking(env, SocialKey::StreakRanking(challenge_id), contributor, stats.current_streak as i128);
+        
+        // Active savers keep their challenge and their own entries alive
+        Self::bump_instance(env);
+        Self::bump_entry(env, &DataKey::Challenge(challenge_id));
+        Self::bump_participant(env, challenge_id, contributor);
+        
+        // Emit event
+        env.events().publish(
+            (symbol_short!("contrib"), challenge_id), 
+            (contributor.clone(), amount, challenge.current_amount)
+        );
+        
+        log!(env, "Contribution {} to challenge {} by {}", amount, challenge_id, contributor);
+        
+        // Check if goal is reached
+        if challenge.current_amount >= challenge.goal_amount {
+            env.events().publish(
+                (symbol_short!("goal_met"), challenge_id), 
+                challenge.current_amount
+            );
+            log!(env, "Challenge {} goal reached!", challenge_id);
+        }
+        
+        Ok(ReceiptId {
+            challenge_id,
+            sequence: index,
+        })
+    }
+    
+    fn read_ranking(env: &Env, key: SocialKey, limit: u32) -> Vec<RankingEntry> {
+        let ranking: Vec<RankingEntry> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        
+        let mut top = Vec::new(env);
+        for entry in ranking.iter().take(limit.min(LEADERBOARD_SIZE) as usize) {
+            top.push_back(entry);
+        }
+        top
+    }
+    
+    /// Move a participant to their new place in a ranking kept sorted on write
+    fn update_ranking(env: &Env, key: SocialKey, participant: &Address, value: i128) {
+        let mut ranking: Vec<RankingEntry> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        
+        // Skip the write entirely if the participant can't make the board
+        if ranking.len() >= LEADERBOARD_SIZE {
+            if let Some(last) = ranking.last() {
+                if last.participant != *participant && value <= last.value {
+                    return;
+                }
+            }
+        }
+        
+        // Drop the participant's old entry
+        for i in 0..ranking.len() {
+            if ranking.get(i).unwrap().participant == *participant {
+                ranking.remove(i);
+                break;
+            }
+        }
+        
+        // Insert after every entry with an equal or higher value
+        let mut position = ranking.len();
+        for i in 0..ranking.len() {
+            if ranking.get(i).unwrap().value < value {
+                position = i;
+                break;
+            }
+        }
+        ranking.insert(position, RankingEntry {
+            participant: participant.clone(),
+            value,
+        });
+        
+        while ranking.len() > LEADERBOARD_SIZE {
+            ranking.pop_back();
+        }
+        
+        env.storage().persistent().set(&key, &ranking);
+    }
+    
+    /// Count a participant's vote for the pending extension, applying it on a majority
+    fn record_extension_vote(env: &Env, challenge_id: u32, user: &Address) -> Result<(), SavingsError> {
+        let mut challenge: SavingsChallenge = env.storage().persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .ok_or(SavingsError::ChallengeNotFound)?;
+        
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        
+        if !challenge.participants.contains(user) {
+            return Err(SavingsError::NotParticipant);
+        }
+        
+        let mut proposal: ExtensionProposal = env.storage().persistent()
+            .get(&DataKey::Extension(challenge_id))
+            .ok_or(SavingsError::NoExtensionProposed)?;
+        
+        if proposal.applied {
+            return Err(SavingsError::ExtensionUsed);
+        }
+        
+        if proposal.votes.contains(user) {
+            return Err(SavingsError::AlreadyVoted);
+        }
+        proposal.votes.push_back(user.clone());
+        
+        if proposal.votes.len() * 2 > challenge.participants.len() {
+            proposal.applied = true;
+            challenge.deadline += proposal.extra_weeks as u64 * WEEK_IN_SECONDS;
+            env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+            
+            env.events().publish(
+                (symbol_short!("extended"), challenge_id), 
+                (proposal.extra_weeks, challenge.deadline)
+            );
+        }
+        
+        env.storage().persistent().set(&DataKey::Extension(challenge_id), &proposal);
+        
+        Ok(())
+    }
+    
+    fn remove_from_ranking(env: &Env, key: SocialKey, participant: &Address) {
+        let mut ranking: Vec<RankingEntry> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        
+        for i in 0..ranking.len() {
+            if ranking.get(i).unwrap().participant == *participant {
+                ranking.remove(i);
+                env.storage().persistent().set(&key, &ranking);
+                return;
+            }
+        }
+    }
+    
+    /// Push an expired challenge's deadline past now if its auto-extend rule allows; true if it moved
+    fn auto_extend(env: &Env, challenge: &mut SavingsChallenge) -> bool {
+        let mut rule = match Self::get_auto_extend(env.clone(), challenge.id) {
+            Some(rule) => rule,
+            None => return false,
+        };
+        
+        if challenge.current_amount * 10000 < challenge.goal_amount * rule.threshold_bps as i128 {
+            return false;
+        }
+        
+        // Whole weeks at a time, stopping hard at the rule's maximum
+        let now = env.ledger().timestamp();
+        let mut deadline = challenge.deadline;
+        let mut weeks_added: u32 = 0;
+        while now > deadline && rule.weeks_used + weeks_added < rule.max_weeks {
+            deadline += WEEK_IN_SECONDS;
+            weeks_added += 1;
+        }
+        if now > deadline {
+            return false;
+        }
+        
+        rule.weeks_used += weeks_added;
+        challenge.deadline = deadline;
+        env.storage().persistent().set(&DataKey::AutoExtend(challenge.id), &rule);
+        env.storage().persistent().set(&DataKey::Challenge(challenge.id), &*challenge);
+        
+        env.events().publish(
+            (symbol_short!("auto_ext"), challenge.id), 
+            (weeks_added, challenge.deadline)
+        );
+        
+        true
+    }
+    
+    /// Mark a challenge finished and settle anything that settles at the end
+    fn close_challenge(env: &Env, challenge: &mut SavingsChallenge) -> Result<(), SavingsError> {
+        let current_time = env.ledger().timestamp();
+        let goal_reached = challenge.current_amount >= challenge.goal_amount;
+        
+        // Mark as inactive and settled
+        challenge.is_active = false;
+        challenge.finalized = true;
+        env.storage().persistent().set(&DataKey::Challenge(challenge.id), &*challenge);
+        Self::update_public_index(env, challenge);
+        
+        if env.storage().persistent().has(&FinanceKey::PrizePool(challenge.id)) {
+            Self::settle_prize_pool(env, challenge)?;
+            Self::release_pending_donation(env, challenge);
+        }
+        
+        Self::settle_bond(env, challenge);
+        Self::close_sponsorship(env, challenge);
+        Self::snapshot_bonus_weights(env, challenge);
+        Self::settle_teams(env, challenge);
+        Self::settle_competition(env, challenge.id);
+        if let Some(quorum) = Self::quorum_status(env, challenge) {
+            env.events().publish(
+                (symbol_short!("quorum"), challenge.id), 
+                (quorum.completed_count, quorum.met)
+            );
+        }
+        Self::issue_certificate(env, challenge);
+        
+        // Emit finalization event
+        env.events().publish(
+            (symbol_short!("finalized"), challenge.id), 
+            (goal_reached, challenge.current_amount, current_time)
+        );
+        
+        log!(env, "Challenge {} finalized. Goal reached: {}", challenge.id, goal_reached);
+        
+        Ok(())
+    }
+    
+    /// Assign an ID to a new challenge and set up its storage
+    fn insert_challenge(env: &Env, mut challenge: SavingsChallenge) -> Result<u32, SavingsError> {
+        Self::require_not_paused(env)?;
+        
+        // Get next challenge ID
+        let challenge_id: u32 = env.storage().instance()
+            .get(&DataKey::NextChallengeId)
+            .unwrap_or(1);
+        challenge.id = challenge_id;
+        
+        // Creators put up a refundable bond so abandoned challenges cost them something
+        if let Some(bond) = Self::get_bond_config(env.clone()) {
+            if bond.amount > 0 {
+                token::Client::new(env, &challenge.token).transfer(
+                    &challenge.creator,
+                    &env.current_contract_address(),
+                    &bond.amount
+                );
+                env.storage().persistent().set(&FinanceKey::Bond(challenge_id), &bond.amount);
+            }
+        }
+        
+        if let ChallengeMode::Quorum(required_bps) = challenge.mode {
+            if required_bps == 0 || required_bps > 10000 {
+                return Err(SavingsError::InvalidParameters);
+            }
+        }
+        
+        if let ChallengeMode::NoLossPrize(winner_count) = challenge.mode {
+            if winner_count == 0 {
+                return Err(SavingsError::InvalidParameters);
+            }
+            
+            let vault: Address = env.storage().instance()
+                .get(&FinanceKey::YieldVault(challenge.token.clone()))
+                .ok_or(SavingsError::VaultNotConfigured)?;
+            
+            let prize_pool = PrizePool {
+                vault,
+                split: YieldSplit::PrizePot(winner_count),
+                shares: 0,
+                principal: 0,
+                prize: 0,
+                shortfall: 0,
+                winners: Vec::new(env),
+                settled: false,
+            };
+            env.storage().persistent().set(&FinanceKey::PrizePool(challenge_id), &prize_pool);
+        }
+        
+        if challenge.mode == ChallengeMode::Rotating {
+            let rotation = RotationState {
+                next_round: 1,
+                pot: 0,
+                received: Vec::new(env),
+                missed: Map::new(env),
+            };
+            env.storage().persistent().set(&DataKey::Rotation(challenge_id), &rotation);
+        }
+        
+        // Store challenge
+        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        Self::update_public_index(env, &challenge);
+        
+        // Every challenge starts with 25/50/75% milestones on the group goal
+        env.storage().persistent().set(
+            &SocialKey::GroupMilestones(challenge_id), 
+            &Self::default_milestones(env, challenge.goal_amount)
+        );
+        
+        // Add challenge to participants' challenge lists
+        for participant in challenge.participants.iter() {
+            Self::init_participant(env, challenge_id, &participant);
+        }
+        
+        // Update next challenge ID
+        env.storage().instance().set(&DataKey::NextChallengeId, &(challenge_id + 1));
+        Self::bump_instance(env);
+        Self::bump_entry(env, &DataKey::Challenge(challenge_id));
+        
+        // Emit event
+        env.events().publish(
+            (symbol_short!("created"), challenge_id), 
+            (challenge.creator.clone(), challenge.name.clone(), challenge.goal_amount, challenge.participants.len())
+        );
+        
+        if let Some(previous) = challenge.previous_challenge {
+            env.events().publish(
+                (symbol_short!("restarted"), challenge_id), 
+                previous
+            );
+        }
+        
+        log!(env, "Challenge {} created by {} with goal {}", challenge_id, challenge.creator, challenge.goal_amount);
+        
+        Ok(challenge_id)
+    }
+    
+    /// Credit a participant's bonus with the sponsor's match, within what is left of the cap
+    fn apply_sponsor_match(env: &Env, challenge_id: u32, contributor: &Address, amount: i128) {
+        let mut sponsorship = match Self::get_sponsorship(env.clone(), challenge_id) {
+            Some(sponsorship) => sponsorship,
+            None => return,
+        };
+        
+        let matched = ((amount * sponsorship.match_bps as i128) / 10000).min(sponsorship.cap - sponsorship.matched);
+        if matched <= 0 {
+            return;
+        }
+        
+        sponsorship.matched += matched;
+        env.storage().persistent().set(&FinanceKey::Sponsor(challenge_id), &sponsorship);
+        
+        let bonus: i128 = env.storage().persistent()
+            .get(&ParticipantKey::Bonus(challenge_id, contributor.clone()))
+            .unwrap_or(0);
+        env.storage().persistent().set(&ParticipantKey::Bonus(challenge_id, contributor.clone()), &(bonus + matched));
+        
+        env.events().publish(
+            (symbol_short!("matched"), challenge_id), 
+            (contributor.clone(), matched)
+        );
+    }
+    
+    /// Move the unused part of a sponsor's cap into the bonus pot once the challenge closes
+    fn close_sponsorship(env: &Env, challenge: &SavingsChallenge) {
+        let mut sponsorship = match Self::get_sponsorship(env.clone(), challenge.id) {
+            Some(sponsorship) => sponsorship,
+            None => return,
+        };
+        
+        let unused = sponsorship.cap - sponsorship.matched;
+        if unused > 0 {
+            sponsorship.cap = sponsorship.matched;
+            env.storage().persistent().set(&FinanceKey::Sponsor(challenge.id), &sponsorship);
+            Self::add_to_bonus_pot(env, challenge.id, unused);
+        }
+    }
+    
+    /// Return everything a sponsor escrowed that hasn't left the challenge, matched or not
+    fn refund_sponsorship(env: &Env, challenge: &SavingsChallenge) {
+        let sponsorship = match Self::get_sponsorship(env.clone(), challenge.id) {
+            Some(sponsorship) => sponsorship,
+            None => return,
+        };
+        env.storage().persistent().remove(&FinanceKey::Sponsor(challenge.id));
+        
+        if sponsorship.cap > 0 {
+            token::Client::new(env, &challenge.token).transfer(
+                &env.current_contract_address(),
+                &sponsorship.sponsor,
+                &sponsorship.cap
+            );
+        }
+        
+        env.events().publish(
+            (symbol_short!("sp_refund"), challenge.id), 
+            (sponsorship.sponsor, sponsorship.cap)
+        );
+    }
+    
+    /// Pay a rotation's undistributed pot back pro rata to whoever paid into the rounds it holds
+    fn refund_rotation_pot(env: &Env, challenge: &SavingsChallenge) -> Result<(), SavingsError> {
+        let mut rotation = Self::get_rotation(env.clone(), challenge.id)?;
+        let pot = rotation.pot;
+        if pot <= 0 {
+            return Ok(());
+        }
+        
+        // Everything paid in since the last payout is in a round from next_round on
+        let total_weeks = ((challenge.deadline - challenge.start_time) / WEEK_IN_SECONDS) as u32;
+        let current_week = (env.ledger().timestamp().saturating_sub(challenge.start_time) / WEEK_IN_SECONDS) as u32 + 1;
+        let mut paid_in: Map<Address, i128> = Map::new(env);
+        let mut total_paid: i128 = 0;
+        for participant in challenge.participants.iter() {
+            let mut paid: i128 = 0;
+            for week in rotation.next_round..=current_week.min(total_weeks) {
+                paid += env.storage().persistent()
+                    .get(&ParticipantKey::WeekPaid(challenge.id, week, participant.clone()))
+                    .unwrap_or(0);
+            }
+            if paid > 0 {
+                paid_in.set(participant, paid);
+                total_paid += paid;
+            }
+        }
+        if total_paid == 0 {
+            return Ok(());
         }
         
-        // Get challenge
-        let mut challenge: SavingsChallenge = env.storage().persistent()
-            .get(&DataKey::Challenge(challenge_id))
-            .ok_or(SavingsError::ChallengeNotFound)?;
+        rotation.pot = 0;
+        env.storage().persistent().set(&DataKey::Rotation(challenge.id), &rotation);
         
-        // Validate challenge state
+        let token_client = token::Client::new(env, &challenge.token);
+        let mut remaining = pot;
+        for (i, (participant, paid)) in paid_in.iter().enumerate() {
+            // The last contributor also takes the rounding dust
+            let refund = if i as u32 + 1 == paid_in.len() { remaining } else { pot * paid / total_paid };
+            remaining -= refund;
+            if refund > 0 {
+                token_client.transfer(&env.current_contract_address(), &participant, &refund);
+            }
+            
+            env.events().publish(
+                (symbol_short!("rot_rfnd"), challenge.id), 
+                (participant, refund)
+            );
+        }
+        
+        Ok(())
+    }
+    
+    /// Send penalties and forfeited money to the challenge's charity if it has one, else the bonus pot
+    fn route_forfeit(env: &Env, challenge: &SavingsChallenge, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let charity = match Self::get_charity(env.clone(), challenge.id) {
+            Some(charity) => charity,
+            None => {
+                Self::add_to_bonus_pot(env, challenge.id, amount);
+                return;
+            },
+        };
+        
+        // Vault-held escrow only comes back to the contract when the prize pool settles
+        let prize_pool: Option<PrizePool> = env.storage().persistent().get(&FinanceKey::PrizePool(challenge.id));
+        if prize_pool.is_some_and(|prize_pool| !prize_pool.settled) {
+            let pending: i128 = env.storage().persistent()
+                .get(&FinanceKey::PendingDonation(challenge.id))
+                .unwrap_or(0);
+            env.storage().persistent().set(&FinanceKey::PendingDonation(challenge.id), &(pending + amount));
+            return;
+        }
+        
+        token::Client::new(env, &challenge.token).transfer(
+            &env.current_contract_address(),
+            &charity,
+            &amount
+        );
+        
+        let donated = Self::get_donated(env.clone(), challenge.id) + amount;
+        env.storage().persistent().set(&FinanceKey::Donated(challenge.id), &donated);
+        
+        env.events().publish(
+            (symbol_short!("donated"), challenge.id), 
+            (charity, amount, donated)
+        );
+    }
+    
+    /// Pay the charity what was held back while the challenge's escrow sat in the vault
+    fn release_pending_donation(env: &Env, challenge: &SavingsChallenge) {
+        let pending: i128 = env.storage().persistent()
+            .get(&FinanceKey::PendingDonation(challenge.id))
+            .unwrap_or(0);
+        if pending <= 0 {
+            return;
+        }
+        env.storage().persistent().remove(&FinanceKey::PendingDonation(challenge.id));
+        Self::route_forfeit(env, challenge, pending);
+    }
+    
+    /// Add money already held by the contract to a challenge's bonus pot
+    fn add_to_bonus_pot(env: &Env, challenge_id: u32, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let pot = Self::get_bonus_pot(env.clone(), challenge_id);
+        env.storage().persistent().set(&FinanceKey::BonusPot(challenge_id), &(pot + amount));
+        
+        env.events().publish(
+            (symbol_short!("bonus_pot"), challenge_id), 
+            amount
+        );
+    }
+    
+    /// Fix each participant's share of the bonus pot from their savings and streak at finalization
+    fn snapshot_bonus_weights(env: &Env, challenge: &SavingsChallenge) {
+        let formula = Self::get_bonus_formula(env.clone(), challenge.id);
+        
+        let mut all_stats: Vec<ParticipantStats> = Vec::new(env);
+        let mut total_saved: i128 = 0;
+        let mut total_streak: i128 = 0;
+        for participant in challenge.participants.iter() {
+            if let Ok(stats) = Self::get_participant_stats(env.clone(), challenge.id, participant) {
+                total_saved += stats.total_contributed;
+                total_streak += stats.current_streak as i128;
+                all_stats.push_back(stats);
+            }
+        }
+        
+        for (participant, stats) in challenge.participants.iter().zip(all_stats.iter()) {
+            let mut weight: i128 = 0;
+            if total_saved > 0 {
+                weight += (formula.amount_weight_bps as i128 * stats.total_contributed) / total_saved;
+            }
+            if total_streak > 0 {
+                weight += (formula.streak_weight_bps as i128 * stats.current_streak as i128) / total_streak;
+            }
+            if weight > 0 {
+                env.storage().persistent().set(&ParticipantKey::BonusWeight(challenge.id, participant), &(weight as u32));
+            }
+        }
+    }
+    
+    /// Return the creator's bond, or slash it into the bonus pot if finalization came after the grace period
+    fn settle_bond(env: &Env, challenge: &SavingsChallenge) {
+        let bond: i128 = env.storage().persistent()
+            .get(&FinanceKey::Bond(challenge.id))
+            .unwrap_or(0);
+        if bond <= 0 {
+            return;
+        }
+        env.storage().persistent().remove(&FinanceKey::Bond(challenge.id));
+        
+        let grace_period = match Self::get_bond_config(env.clone()) {
+            Some(config) => config.grace_period,
+            None => 0,
+        };
+        if env.ledger().timestamp() <= challenge.deadline + grace_period || challenge.participants.is_empty() {
+            token::Client::new(env, &challenge.token).transfer(&env.current_contract_address(), &challenge.creator, &bond);
+            return;
+        }
+        
+        // Abandoned: the bond goes to the participants' bonus pot, or the charity
+        Self::route_forfeit(env, challenge, bond);
+        
+        env.events().publish(
+            (symbol_short!("slashed"), challenge.id), 
+            (challenge.creator.clone(), bond)
+        );
+    }
+    
+    /// Add a participant to a running challenge that still has room
+    fn admit_participant(
+        env: &Env,
+        mut challenge: SavingsChallenge,
+        user: &Address,
+    ) -> Result<(), SavingsError> {
         if !challenge.is_active {
             return Err(SavingsError::ChallengeInactive);
         }
         
-        let current_time = env.ledger().timestamp();
-        if current_time > challenge.deadline {
+        if env.ledger().timestamp() > challenge.deadline {
             return Err(SavingsError::ChallengeExpired);
         }
         
-        // Check if contributor is a participant
-        if !challenge.participants.contains(&contributor) {
+        if challenge.participants.contains(user) {
+            return Err(SavingsError::AlreadyParticipant);
+        }
+        
+        if challenge.max_participants > 0 && challenge.participants.len() >= challenge.max_participants {
+            return Err(SavingsError::ChallengeFull);
+        }
+        
+        challenge.participants.push_back(user.clone());
+        env.storage().persistent().set(&DataKey::Challenge(challenge.id), &challenge);
+        
+        Self::init_participant(env, challenge.id, user);
+        
+        env.events().publish(
+            (symbol_short!("joined"), challenge.id), 
+            (user.clone(), challenge.participants.len())
+        );
+        
+        log!(env, "User {} joined challenge {}", user, challenge.id);
+        
+        Ok(())
+    }
+    
+    /// Anchor a hash of the challenge's final totals and streaks. Verifiers rebuild the
+    /// CompletionSummary from the certificate's finalized_at and the final stats, then compare hashes.
+    fn issue_certificate(env: &Env, challenge: &SavingsChallenge) {
+        let finalized_at = env.ledger().timestamp();
+        
+        let mut lines: Vec<CertificateLine> = Vec::new(env);
+        for participant in challenge.participants.iter() {
+            if let Ok(stats) = Self::get_participant_stats(env.clone(), challenge.id, participant.clone()) {
+                lines.push_back(CertificateLine {
+                    participant,
+                    total_contributed: stats.total_contributed,
+                    streak: stats.current_streak,
+                });
+            }
+        }
+        
+        let summary = CompletionSummary {
+            challenge_id: challenge.id,
+            goal_amount: challenge.goal_amount,
+            total_saved: challenge.current_amount,
+            finalized_at,
+            lines,
+        };
+        let certificate = Certificate {
+            hash: env.crypto().sha256(&summary.to_xdr(env)).into(),
+            finalized_at,
+        };
+        env.storage().persistent().set(&DataKey::Certificate(challenge.id), &certificate);
+        
+        env.events().publish(
+            (symbol_short!("cert"), challenge.id), 
+            certificate.hash
+        );
+    }
+    
+    /// How much of their personal target a participant has saved, as a tier
+    fn completion_tier(env: &Env, challenge: &SavingsChallenge, participant: &Address) -> CompletionTier {
+        let saved = env.storage().persistent()
+            .get::<_, ParticipantStats>(&ParticipantKey::ParticipantStats(challenge.id, participant.clone()))
+            .map(|stats| stats.total_contributed)
+            .unwrap_or(0);
+        let target = Self::personal_target(env, challenge, participant);
+        if saved >= target {
+            CompletionTier::Completed
+        } else if saved * 10 >= target * 8 {
+            CompletionTier::Most
+        } else if saved * 2 >= target {
+            CompletionTier::Half
+        } else {
+            CompletionTier::Incomplete
+        }
+    }
+    
+    /// Build a completed participant's trophy, ranking them by amount saved
+    fn trophy(env: &Env, challenge: &SavingsChallenge, holder: &Address, finalized_at: u64) -> Trophy {
+        let saved_by = |participant: &Address| -> i128 {
+            env.storage().persistent()
+                .get::<_, ParticipantStats>(&ParticipantKey::ParticipantStats(challenge.id, participant.clone()))
+                .map(|stats| stats.total_contributed)
+                .unwrap_or(0)
+        };
+        
+        let amount_saved = saved_by(holder);
+        let mut rank: u32 = 1;
+        for participant in challenge.participants.iter() {
+            if saved_by(&participant) > amount_saved {
+                rank += 1;
+            }
+        }
+        
+        Trophy {
+            challenge_id: challenge.id,
+            name: challenge.name.clone(),
+            holder: holder.clone(),
+            started_at: challenge.start_time,
+            finalized_at,
+            amount_saved,
+            rank,
+            participant_count: challenge.participants.len(),
+        }
+    }
+    
+    /// Move the first waitlisted user into a freed place
+    fn promote_from_waitlist(env: &Env, challenge: SavingsChallenge) -> Result<(), SavingsError> {
+        let mut waitlist = Self::get_waitlist(env.clone(), challenge.id);
+        let next = match waitlist.pop_front() {
+            Some(next) => next,
+            None => return Ok(()),
+        };
+        env.storage().persistent().set(&DataKey::Waitlist(challenge.id), &waitlist);
+        
+        env.events().publish(
+            (symbol_short!("promoted"), challenge.id), 
+            next.clone()
+        );
+        
+        Self::admit_participant(env, challenge, &next)
+    }
+    
+    /// Keep the discovery index in sync with a challenge's visibility and status
+    fn update_public_index(env: &Env, challenge: &SavingsChallenge) {
+        let should_list = challenge.is_active && challenge.visibility == Visibility::Open;
+        Self::set_discoverable(env, challenge, should_list);
+    }
+    
+    /// Add a challenge to or drop it from the public, category and tag indexes
+    fn set_discoverable(env: &Env, challenge: &SavingsChallenge, should_list: bool) {
+        Self::set_listed(env, DataKey::PublicChallenges, challenge.id, should_list);
+        Self::set_listed(env, DataKey::CategoryChallenges(challenge.category.clone()), challenge.id, should_list);
+        for tag in challenge.tags.iter() {
+            Self::set_listed(env, DataKey::TagChallenges(tag), challenge.id, should_list);
+        }
+    }
+    
+    /// Add a challenge ID to or remove it from one discovery index
+    fn set_listed(env: &Env, key: DataKey, challenge_id: u32, should_list: bool) {
+        let mut index: Vec<u32> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        
+        let listed = index.first_index_of(challenge_id);
+        
+        match (listed, should_list) {
+            (None, true) => index.push_back(challenge_id),
+            (Some(position), false) => { index.remove(position); },
+            _ => return,
+        }
+        
+        env.storage().persistent().set(&key, &index);
+    }
+    
+    /// Index the challenge for a new participant and start their stats at zero
+    fn init_participant(env: &Env, challenge_id: u32, participant: &Address) {
+        Self::add_challenge_to_user(env, participant, challenge_id);
+        Self::bump_entry(env, &ParticipantKey::UserChallenges(participant.clone()));
+        
+        let stats = ParticipantStats {
+            total_contributed: 0,
+            contribution_count: 0,
+            last_contribution: 0,
+            current_streak: 0,
+            freezes_used: 0,
+            weeks_met: 0,
+            last_week_met: 0,
+        };
+        env.storage().persistent().set(
+            &ParticipantKey::ParticipantStats(challenge_id, participant.clone()), 
+            &stats
+        );
+    }
+    
+    /// Extend the contract instance, and with it the admin config, to the maximum TTL
+    fn bump_instance(env: &Env) {
+        let max_ttl = env.storage().max_ttl();
+        env.storage().instance().extend_ttl(max_ttl - WEEK_OF_LEDGERS, max_ttl);
+    }
+    
+    /// Extend a persistent entry to the maximum TTL, if it exists
+    fn bump_entry<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        if env.storage().persistent().has(key) {
+            let max_ttl = env.storage().max_ttl();
+            env.storage().persistent().extend_ttl(key, max_ttl - WEEK_OF_LEDGERS, max_ttl);
+        }
+    }
+    
+    /// Extend the entries a participant needs to withdraw and claim from a challenge
+    fn bump_participant(env: &Env, challenge_id: u32, participant: &Address) {
+        for key in [
+            ParticipantKey::ParticipantStats(challenge_id, participant.clone()),
+            ParticipantKey::UserChallenges(participant.clone()),
+            ParticipantKey::UserMilestones(challenge_id, participant.clone()),
+            ParticipantKey::Penalty(challenge_id, participant.clone()),
+            ParticipantKey::Bonus(challenge_id, participant.clone()),
+            ParticipantKey::BonusWeight(challenge_id, participant.clone()),
+            ParticipantKey::BonusClaimed(challenge_id, participant.clone()),
+            ParticipantKey::PrizeWinnings(challenge_id, participant.clone()),
+            ParticipantKey::Withdrawn(challenge_id, participant.clone()),
+            ParticipantKey::HeldRewards(challenge_id, participant.clone()),
+            ParticipantKey::TeamOf(challenge_id, participant.clone()),
+            ParticipantKey::DeclaredIncome(challenge_id, participant.clone()),
+            ParticipantKey::Pauses(challenge_id, participant.clone()),
+        ] {
+            Self::bump_entry(env, &key);
+        }
+        for asset in Self::get_accepted_assets(env.clone(), challenge_id).keys().iter() {
+            Self::bump_entry(env, &ParticipantKey::AssetEscrow(challenge_id, participant.clone(), asset));
+        }
+        for index in 0..Self::get_group_milestones(env.clone(), challenge_id).len() {
+            Self::bump_entry(env, &ParticipantKey::GroupBonusShare(challenge_id, index, participant.clone()));
+        }
+    }
+    
+    /// Whether a user can moderate a challenge: its creator or one of its co-admins
+    fn is_moderator(env: &Env, challenge: &SavingsChallenge, user: &Address) -> bool {
+        challenge.creator == *user || Self::get_co_admins(env.clone(), challenge.id).contains(user)
+    }
+    
+    /// Record the ledger a user first showed up at, returning it
+    fn record_first_seen(env: &Env, user: &Address) -> u32 {
+        let key = ParticipantKey::FirstSeen(user.clone());
+        if let Some(first_seen) = env.storage().persistent().get(&key) {
+            return first_seen;
+        }
+        let first_seen = env.ledger().sequence();
+        env.storage().persistent().set(&key, &first_seen);
+        first_seen
+    }
+    
+    /// Fail unless the user passes every gate the challenge sets on joining
+    fn check_join_requirements(env: &Env, challenge_id: u32, user: &Address) -> Result<(), SavingsError> {
+        let requirements = match Self::get_join_requirements(env.clone(), challenge_id) {
+            Some(requirements) => requirements,
+            None => return Ok(()),
+        };
+        
+        if requirements.min_account_age > 0 {
+            let age = match Self::get_first_seen(env.clone(), user.clone()) {
+                Some(first_seen) => env.ledger().sequence() - first_seen,
+                None => 0,
+            };
+            if age < requirements.min_account_age {
+                return Err(SavingsError::JoinRequirementsNotMet);
+            }
+        }
+        
+        if requirements.min_stake <= 0 && requirements.badge.is_none() {
+            return Ok(());
+        }
+        
+        // Stake and badge gates can't be checked without SaveCoin, so they fail closed
+        let savecoin: Address = env.storage().instance()
+            .get(&DataKey::SaveCoinToken)
+            .ok_or(SavingsError::JoinRequirementsNotMet)?;
+        let client = SaveCoinClient::new(env, &savecoin);
+        
+        if requirements.min_stake > 0 && client.get_staked(user) < requirements.min_stake {
+            return Err(SavingsError::JoinRequirementsNotMet);
+        }
+        
+        if let Some(badge) = requirements.badge {
+            if client.badge_balance(user, &badge) == 0 {
+                return Err(SavingsError::JoinRequirementsNotMet);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Add challenge ID to user's challenge list
+    fn add_challenge_to_user(env: &Env, user: &Address, challenge_id: u32) {
+        Self::record_first_seen(env, user);
+        
+        let mut user_challenges: Vec<u32> = env.storage().persistent()
+            .get(&ParticipantKey::UserChallenges(user.clone()))
+            .unwrap_or(Vec::new(env));
+        
+        if !user_challenges.contains(challenge_id) {
+            user_challenges.push_back(challenge_id);
+            env.storage().persistent().set(&ParticipantKey::UserChallenges(user.clone()), &user_challenges);
+        }
+    }
+    
+    /// Remove challenge ID from user's challenge list
+    fn remove_challenge_from_user(env: &Env, user: &Address, challenge_id: u32) {
+        let mut user_challenges: Vec<u32> = env.storage().persistent()
+            .get(&ParticipantKey::UserChallenges(user.clone()))
+            .unwrap_or(Vec::new(env));
+        
+        if let Some(position) = user_challenges.first_index_of(challenge_id) {
+            user_challenges.remove(position);
+            env.storage().persistent().set(&ParticipantKey::UserChallenges(user.clone()), &user_challenges);
+        }
+    }
+    
+    /// Amount a participant is owed once the challenge is finalized
+    fn payout_share(env: &Env, challenge: &SavingsChallenge, participant: &Address) -> i128 {
+        // Rotating pots are paid out round by round instead
+        if challenge.mode == ChallengeMode::Rotating {
+            return 0;
+        }
+        
+        let stats: Option<ParticipantStats> = env.storage().persistent()
+            .get(&ParticipantKey::ParticipantStats(challenge.id, participant.clone()));
+        
+        let mut principal = match stats {
+            Some(stats) => stats.total_contributed,
+            None => 0,
+        };
+        
+        let prize_pool: Option<PrizePool> = env.storage().persistent().get(&FinanceKey::PrizePool(challenge.id));
+        if let Some(prize_pool) = prize_pool {
+            principal -= Self::principal_haircut(&prize_pool, principal);
+        }
+        
+        let prize: i128 = env.storage().persistent()
+            .get(&ParticipantKey::PrizeWinnings(challenge.id, participant.clone()))
+            .unwrap_or(0);
+        
+        let (mut penalty, bonus) = Self::get_penalty_and_bonus(env.clone(), challenge.id, participant.clone());
+        
+        // Penalties given to a charity have left the contract, so no ruling can hand them back
+        if Self::get_charity(env.clone(), challenge.id).is_some() {
+            principal -= penalty;
+            penalty = 0;
+        }
+        let adjustments = bonus + prize - penalty;
+        
+        // An arbiter's ruling can void some or all of the remaining adjustments to principal
+        match Self::get_dispute(env.clone(), challenge.id).map(|dispute| dispute.resolution) {
+            Some(Resolution::RefundAll) => principal,
+            Some(Resolution::Partial(bps)) => principal + (adjustments * bps as i128) / 10000,
+            _ => principal + adjustments,
+        }
+    }
+    
+    /// Fail while the admin has halted new activity across the contract
+    fn require_not_paused(env: &Env) -> Result<(), SavingsError> {
+        if Self::is_contract_paused(env.clone()) {
+            return Err(SavingsError::ContractPaused);
+        }
+        Ok(())
+    }
+    
+    /// Fail while a dispute on the challenge is waiting for the arbiter
+    fn require_not_frozen(env: &Env, challenge_id: u32) -> Result<(), SavingsError> {
+        match Self::get_dispute(env.clone(), challenge_id) {
+            Some(dispute) if dispute.resolution == Resolution::Pending => Err(SavingsError::ChallengeDisputed),
+            _ => Ok(()),
+        }
+    }
+    
+    /// Mint a SaveCoin reward sized by its calculate_reward, once per slot, never failing the surrounding call.
+    /// Challenges that hold rewards keep it until withdraw_share instead.
+    fn issue_reward(
+        env: &Env,
+        to: &Address,
+        challenge: &SavingsChallenge,
+        reward_type: RewardType,
+        contribution_amount: i128,
+        streak_weeks: u32,
+        slot: u32,
+    ) {
+        let savecoin: Option<Address> = env.storage().instance().get(&DataKey::SaveCoinToken);
+        let savecoin = match savecoin {
+            Some(savecoin) => savecoin,
+            None => return,
+        };
+        let client = SaveCoinClient::new(env, &savecoin);
+        
+        let reward = match client.try_calculate_reward(&contribution_amount, &reward_type, &streak_weeks) {
+            Ok(Ok(reward)) if reward > 0 => reward,
+            _ => return,
+        };
+        
+        // The dedupe key stops a slot paying twice, e.g. after an early withdrawal and top-up
+        let mut key_data = Bytes::from_array(env, &challenge.id.to_be_bytes());
+        key_data.append(&to.clone().to_xdr(env));
+        key_data.extend_from_array(&(reward_type as u32).to_be_bytes());
+        key_data.extend_from_array(&slot.to_be_bytes());
+        let dedupe_key: BytesN<32> = env.crypto().sha256(&key_data).into();
+        
+        if Self::holds_rewards(challenge) {
+            let held_key = ParticipantKey::HeldRewards(challenge.id, to.clone());
+            let mut held: Map<BytesN<32>, i128> = env.storage().persistent()
+                .get(&held_key)
+                .unwrap_or(Map::new(env));
+            held.set(dedupe_key, reward);
+            env.storage().persistent().set(&held_key, &held);
+            return;
+        }
+        
+        let minted = client.try_mint_reward(
+            &env.current_contract_address(),
+            to,
+            &reward,
+            &reward_type,
+            &challenge.id,
+            &10000,
+            &Some(dedupe_key),
+            &false
+        );
+        if minted.is_err() {
+            log!(env, "Reward {} could not be minted for {}", reward, to);
+        } else {
+            Self::record_reward(env, to, reward);
+        }
+    }
+    
+    /// Mint a participant's held rewards in one go if any quorum was met, else drop them
+    fn release_held_rewards(env: &Env, challenge: &SavingsChallenge, participant: &Address, quorum_met: bool) {
+        let held_key = ParticipantKey::HeldRewards(challenge.id, participant.clone());
+        let held: Option<Map<BytesN<32>, i128>> = env.storage().persistent().get(&held_key);
+        let held = match held {
+            Some(held) => held,
+            None => return,
+        };
+        env.storage().persistent().remove(&held_key);
+        
+        let savecoin: Option<Address> = env.storage().instance().get(&DataKey::SaveCoinToken);
+        let reward: i128 = held.values().iter().sum();
+        let savecoin = match savecoin {
+            Some(savecoin) if quorum_met && reward > 0 => savecoin,
+            _ => return,
+        };
+        
+        let mut key_data = Bytes::from_array(env, &challenge.id.to_be_bytes());
+        key_data.append(&participant.clone().to_xdr(env));
+        key_data.append(&symbol_short!("held").to_xdr(env));
+        let dedupe_key: BytesN<32> = env.crypto().sha256(&key_data).into();
+        
+        let minted = SaveCoinClient::new(env, &savecoin).try_mint_reward(
+            &env.current_contract_address(),
+            participant,
+            &reward,
+            &RewardType::WeeklyContribution,
+            &challenge.id,
+            &10000,
+            &Some(dedupe_key),
+            &false
+        );
+        if minted.is_err() {
+            log!(env, "Held rewards {} could not be minted for {}", reward, participant);
+        } else {
+            Self::record_reward(env, participant, reward);
+        }
+    }
+    
+    /// Shrink a participant's held rewards in proportion to savings they took back out
+    fn scale_held_rewards(env: &Env, challenge_id: u32, participant: &Address, kept: i128, saved: i128) {
+        let held_key = ParticipantKey::HeldRewards(challenge_id, participant.clone());
+        let held: Option<Map<BytesN<32>, i128>> = env.storage().persistent().get(&held_key);
+        let mut held = match held {
+            Some(held) if saved > 0 => held,
+            _ => return,
+        };
+        for (dedupe_key, reward) in held.iter() {
+            held.set(dedupe_key, reward * kept / saved);
+        }
+        env.storage().persistent().set(&held_key, &held);
+    }
+    
+    /// Add SaveCoin minted to a user to their lifetime stats
+    fn record_reward(env: &Env, user: &Address, amount: i128) {
+        let mut lifetime = Self::get_user_lifetime_stats(env.clone(), user.clone());
+        lifetime.rewards_earned += amount;
+        env.storage().persistent().set(&ParticipantKey::Lifetime(user.clone()), &lifetime);
+    }
+    
+    /// Take savings handed back before a challenge ended out of a user's lifetime total
+    fn reduce_lifetime_saved(env: &Env, user: &Address, amount: i128) {
+        let mut lifetime = Self::get_user_lifetime_stats(env.clone(), user.clone());
+        lifetime.total_saved = (lifetime.total_saved - amount).max(0);
+        env.storage().persistent().set(&ParticipantKey::Lifetime(user.clone()), &lifetime);
+    }
+    
+    /// Mint an achievement badge through SaveCoin, never failing the surrounding call
+    fn award_badge(env: &Env, to: &Address, badge_id: u32, challenge_id: u32) {
+        let badge_token: Option<Address> = env.storage().instance().get(&DataKey::SaveCoinToken);
+        if let Some(badge_token) = badge_token {
+            let minted = SaveCoinClient::new(env, &badge_token).try_mint_badge(
+                &env.current_contract_address(),
+                to,
+                &badge_id,
+                &challenge_id
+            );
+            if minted.is_err() {
+                log!(env, "Badge {} could not be minted for {}", badge_id, to);
+            }
+        }
+    }
+    
+    /// Award the completion badge, plus the first-challenge badge if this is the saver's first
+    fn award_completion_badges(env: &Env, challenge_id: u32, participant: &Address) {
+        let badge_token: Option<Address> = env.storage().instance().get(&DataKey::SaveCoinToken);
+        if let Some(badge_token) = badge_token {
+            let held = SaveCoinClient::new(env, &badge_token)
+                .try_badge_balance(participant, &BADGE_FIRST_CHALLENGE);
+            if matches!(held, Ok(Ok(0))) {
+                Self::award_badge(env, participant, BADGE_FIRST_CHALLENGE, challenge_id);
+            }
+            Self::award_badge(env, participant, BADGE_CHALLENGE_COMPLETED, challenge_id);
+        }
+    }
+    
+    /// Mint the SaveCoin completion reward: the challenge's base rate plus 0.5% for every 4 weeks of streak,
+    /// in bps of the personal target and scaled by the participant's tier (milestone bonuses were already
+    /// paid as they were reached)
+    fn issue_completion_reward(
+        env: &Env,
+        challenge: &SavingsChallenge,
+        participant: &Address,
+        stats: &ParticipantStats,
+        scale_bps: u32,
+    ) {
+        let savecoin: Option<Address> = env.storage().instance().get(&DataKey::SaveCoinToken);
+        let savecoin = match savecoin {
+            Some(savecoin) => savecoin,
+            None => return,
+        };
+        
+        let reward_bps = challenge.reward_bps + (stats.current_streak / 4) * 50;
+        
+        let mut reward = Self::personal_target(env, challenge, participant) * reward_bps as i128 / 10000;
+        reward = reward * scale_bps as i128 / 10000;
+        
+        // Members of the winning team get their reward multiplied
+        let winner: Option<Symbol> = env.storage().persistent().get(&SocialKey::WinningTeam(challenge.id));
+        if winner.is_some() && winner == Self::get_team(env.clone(), challenge.id, participant.clone()) {
+            reward = reward * Self::get_team_bonus(env.clone(), challenge.id) as i128 / 10000;
+        }
+        
+        if reward <= 0 {
+            return;
+        }
+        
+        let mut key_data = Bytes::from_array(env, &challenge.id.to_be_bytes());
+        key_data.append(&participant.clone().to_xdr(env));
+        key_data.extend_from_array(&(RewardType::ChallengeCompleted as u32).to_be_bytes());
+        let dedupe_key: BytesN<32> = env.crypto().sha256(&key_data).into();
+        
+        let minted = SaveCoinClient::new(env, &savecoin).try_mint_reward(
+            &env.current_contract_address(),
+            participant,
+            &reward,
+            &RewardType::ChallengeCompleted,
+            &challenge.id,
+            &10000,
+            &Some(dedupe_key),
+            &false
+        );
+        if minted.is_err() {
+            log!(env, "Completion reward {} could not be minted for {}", reward, participant);
+        } else {
+            Self::record_reward(env, participant, reward);
+        }
+    }
+    
+    /// What a participant has to save in a given week under the challenge type
+    fn amount_due(env: &Env, challenge: &SavingsChallenge, user: &Address, week: u32) -> i128 {
+        match &challenge.challenge_type {
+            ChallengeType::Fixed => challenge.weekly_amount,
+            ChallengeType::Incremental(step) => challenge.weekly_amount + step * (week.max(1) - 1) as i128,
+            ChallengeType::Percentage(bps) => {
+                // weekly_amount is the floor, so an undeclared or tiny income still owes something each week
+                let income: Option<i128> = env.storage().persistent()
+                    .get(&ParticipantKey::DeclaredIncome(challenge.id, user.clone()));
+                match income {
+                    Some(income) => (income * *bps as i128 / 10000).max(challenge.weekly_amount),
+                    None => challenge.weekly_amount,
+                }
+            },
+            ChallengeType::Custom(schedule) => schedule.get(week.max(1) - 1).unwrap_or(challenge.weekly_amount),
+        }
+    }
+    
+    /// A participant's progress toward their own target, with their milestones
+    fn user_progress(env: &Env, challenge: &SavingsChallenge, user: &Address) -> Result<UserProgress, SavingsError> {
+        if !challenge.participants.contains(user) {
             return Err(SavingsError::NotParticipant);
         }
         
-        // Calculate week number
-        let weeks_elapsed = (current_time - challenge.created_at) / (7 * 24 * 60 * 60);
-        let week_number = weeks_elapsed as u32 + 1;
+        let stats = Self::get_participant_stats(env.clone(), challenge.id, user.clone())?;
+        let target_amount = Self::personal_target(env, challenge, user);
         
-        // Create contribution record
-        let contribution = Contribution {
-            contributor: contributor.clone(),
-            amount,
-            timestamp: current_time,
-            week_number,
+        Ok(UserProgress {
+            current_amount: stats.total_contributed,
+            target_amount,
+            last_deposit_time: stats.last_contribution,
+            streak_weeks: stats.current_streak,
+            completed: stats.total_contributed >= target_amount,
+            milestones: Self::user_milestones(env, challenge, user),
+            quorum: Self::quorum_status(env, challenge).unwrap_or(QuorumStatus {
+                required_bps: 0,
+                completed_count: 0,
+                participant_count: challenge.participants.len(),
+                met: true,
+            }),
+        })
+    }
+    
+    /// How many participants have reached their own target against a quorum challenge's requirement
+    fn quorum_status(env: &Env, challenge: &SavingsChallenge) -> Option<QuorumStatus> {
+        let required_bps = match challenge.mode {
+            ChallengeMode::Quorum(required_bps) => required_bps,
+            _ => return None,
         };
         
-        // Add to contributions
-        let mut contributions: Vec<Contribution> = env.storage().persistent()
-            .get(&DataKey::Contributions(challenge_id))
-            .unwrap_or(Vec::new(&env));
-        contributions.push_back(contribution);
-        env.storage().persistent().set(&DataKey::Contributions(challenge_id), &contributions);
+        let mut completed_count: u32 = 0;
+        for participant in challenge.participants.iter() {
+            if Self::completion_tier(env, challenge, &participant) == CompletionTier::Completed {
+                completed_count += 1;
+            }
+        }
+        let participant_count = challenge.participants.len();
+        
+        Some(QuorumStatus {
+            required_bps,
+            completed_count,
+            participant_count,
+            met: participant_count > 0
+                && completed_count as u64 * 10000 >= participant_count as u64 * required_bps as u64,
+        })
+    }
+    
+    /// What each participant is expected to save: the amount due for every week of the challenge
+    fn personal_target(env: &Env, challenge: &SavingsChallenge, user: &Address) -> i128 {
+        let total_weeks = ((challenge.deadline - challenge.start_time) / WEEK_IN_SECONDS) as i128;
+        match &challenge.challenge_type {
+            ChallengeType::Fixed => challenge.weekly_amount * total_weeks,
+            ChallengeType::Incremental(step) => {
+                challenge.weekly_amount * total_weeks + step * total_weeks * (total_weeks - 1) / 2
+            },
+            ChallengeType::Percentage(_) => Self::amount_due(env, challenge, user, 1) * total_weeks,
+            ChallengeType::Custom(schedule) => schedule.iter().sum(),
+        }
+    }
+    
+    /// The 25/50/75% milestones every target starts with
+    fn default_milestones(env: &Env, target: i128) -> Vec<Milestone> {
+        let mut milestones = Vec::new(env);
+        for (description, quarters, reward_bonus) in [
+            (symbol_short!("pct_25"), 1, 50),  // 0.5% bonus
+            (symbol_short!("pct_50"), 2, 100), // 1% bonus
+            (symbol_short!("pct_75"), 3, 150), // 1.5% bonus
+        ] {
+            milestones.push_back(Milestone {
+                description,
+                target_amount: target * quarters / 4,
+                reached: false,
+                reached_at: 0,
+                reward_bonus,
+            });
+        }
+        milestones
+    }
+    
+    /// A participant's milestones, starting from the defaults on their personal target
+    fn user_milestones(env: &Env, challenge: &SavingsChallenge, user: &Address) -> Vec<Milestone> {
+        env.storage().persistent()
+            .get(&ParticipantKey::UserMilestones(challenge.id, user.clone()))
+            .unwrap_or_else(|| Self::default_milestones(env, Self::personal_target(env, challenge, user)))
+    }
+    
+    /// Mark the participant milestones their savings have now reached
+    fn check_user_milestones(env: &Env, challenge: &SavingsChallenge, user: &Address, saved: i128) {
+        let mut milestones = Self::user_milestones(env, challenge, user);
+        let mut updated = false;
         
-        // Update challenge amount
-        challenge.current_amount += amount;
-        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        for i in 0..milestones.len() {
+            let mut milestone = milestones.get(i).unwrap();
+            if !milestone.reached && saved >= milestone.target_amount {
+                milestone.reached = true;
+                milestone.reached_at = env.ledger().timestamp();
+                milestones.set(i, milestone.clone());
+                updated = true;
+                
+                env.events().publish(
+                    (symbol_short!("milestone"), challenge.id), 
+                    (user.clone(), milestone.description.clone())
+                );
+                
+                if !Self::holds_rewards(challenge) {
+                    Self::pay_milestone_bonus(env, challenge.id, user, &milestone);
+                }
+            }
+        }
         
-        // Update participant stats
-        let mut stats: ParticipantStats = env.storage().persistent()
-            .get(&DataKey::ParticipantStats(challenge_id, contributor.clone()))
-            .unwrap_or(ParticipantStats {
-                total_contributed: 0,
-                contribution_count: 0,
-                last_contribution: 0,
-                current_streak: 0,
-            });
+        if updated {
+            env.storage().persistent().set(&ParticipantKey::UserMilestones(challenge.id, user.clone()), &milestones);
+        }
+    }
+    
+    /// Whether SaveCoin rewards and milestone bonuses wait for withdraw_share: savings that can come back out
+    /// early could otherwise earn them and leave, and a quorum challenge only pays out rewards if the quorum is met
+    fn holds_rewards(challenge: &SavingsChallenge) -> bool {
+        (challenge.allow_early_withdrawal && challenge.mode != ChallengeMode::Rotating)
+            || matches!(challenge.mode, ChallengeMode::Quorum(_))
+    }
+    
+    /// Pay the bonuses held back for milestones still covered by what the participant kept in;
+    /// ones already minted are skipped by their dedupe key
+    fn pay_deferred_milestone_bonuses(env: &Env, challenge: &SavingsChallenge, user: &Address, saved: i128) {
+        if !Self::holds_rewards(challenge) {
+            return;
+        }
+        for milestone in Self::user_milestones(env, challenge, user).iter() {
+            if milestone.reached && saved >= milestone.target_amount {
+                Self::pay_milestone_bonus(env, challenge.id, user, &milestone);
+            }
+        }
+    }
+    
+    /// Mint a reached milestone's bonus in SaveCoin, never failing the surrounding call
+    fn pay_milestone_bonus(env: &Env, challenge_id: u32, to: &Address, milestone: &Milestone) {
+        let savecoin: Option<Address> = env.storage().instance().get(&DataKey::SaveCoinToken);
+        let reward = milestone.target_amount * milestone.reward_bonus as i128 / 10000;
+        let savecoin = match savecoin {
+            Some(savecoin) if reward > 0 => savecoin,
+            _ => return,
+        };
         
-        stats.total_contributed += amount;
-        stats.contribution_count += 1;
-        stats.last_contribution = current_time;
+        let mut key_data = Bytes::from_array(env, &challenge_id.to_be_bytes());
+        key_data.append(&to.clone().to_xdr(env));
+        key_data.extend_from_array(&(RewardType::MilestoneReached as u32).to_be_bytes());
+        key_data.append(&milestone.description.clone().to_xdr(env));
+        let dedupe_key: BytesN<32> = env.crypto().sha256(&key_data).into();
         
-        // Update streak (simplified: increment if within 8 days of last contribution)
-        if stats.last_contribution > 0 && (current_time - stats.last_contribution) <= (8 * 24 * 60 * 60) {
-            stats.current_streak += 1;
-        } else if stats.contribution_count == 1 {
-            stats.current_streak = 1;
+        let minted = SaveCoinClient::new(env, &savecoin).try_mint_reward(
+            &env.current_contract_address(),
+            to,
+            &reward,
+            &RewardType::MilestoneReached,
+            &challenge_id,
+            &10000,
+            &Some(dedupe_key),
+            &false
+        );
+        if minted.is_err() {
+            log!(env, "Milestone bonus {} could not be minted for {}", reward, to);
         } else {
-            stats.current_streak = 1; // Reset streak
+            Self::record_reward(env, to, reward);
+        }
+    }
+    
+    /// Split a reached group milestone's bonus by what each participant had saved toward it
+    fn share_group_bonus(env: &Env, challenge: &SavingsChallenge, index: u32, milestone: &Milestone) {
+        // Without a set bonus the milestone's own bps apply to its target
+        let bonus = Self::get_group_bonus(env.clone(), challenge.id)
+            .unwrap_or(milestone.target_amount * milestone.reward_bonus as i128 / 10000);
+        if bonus <= 0 || challenge.current_amount <= 0 {
+            return;
         }
         
-        env.storage().persistent().set(
-            &DataKey::ParticipantStats(challenge_id, contributor.clone()), 
-            &stats
-        );
+        for participant in challenge.participants.iter() {
+            let saved = env.storage().persistent()
+                .get::<_, ParticipantStats>(&ParticipantKey::ParticipantStats(challenge.id, participant.clone()))
+                .map(|stats| stats.total_contributed)
+                .unwrap_or(0);
+            let share = bonus * saved / challenge.current_amount;
+            if share > 0 {
+                env.storage().persistent().set(
+                    &ParticipantKey::GroupBonusShare(challenge.id, index, participant),
+                    &share
+                );
+            }
+        }
         
-        // Emit event
         env.events().publish(
-            (symbol_short!("contrib"), challenge_id), 
-            (contributor, amount, challenge.current_amount)
+            (symbol_short!("grp_bonus"), challenge.id), 
+            (milestone.description.clone(), bonus)
         );
+    }
+    
+    /// Mark the group milestones the challenge total has now reached
+    fn check_group_milestones(env: &Env, challenge: &SavingsChallenge) {
+        let mut milestones = Self::get_group_milestones(env.clone(), challenge.id);
+        let mut updated = false;
         
-        log!(&env, "Contribution {} to challenge {} by {}", amount, challenge_id, contributor);
+        for i in 0..milestones.len() {
+            let mut milestone = milestones.get(i).unwrap();
+            if !milestone.reached && challenge.current_amount >= milestone.target_amount {
+                milestone.reached = true;
+                milestone.reached_at = env.ledger().timestamp();
+                milestones.set(i, milestone.clone());
+                updated = true;
+                
+                env.events().publish(
+                    (symbol_short!("grp_mile"), challenge.id), 
+                    milestone.description.clone()
+                );
+                
+                Self::share_group_bonus(env, challenge, i, &milestone);
+            }
+        }
         
-        // Check if goal is reached
-        if challenge.current_amount >= challenge.goal_amount {
-            env.events().publish(
-                (symbol_short!("goal_met"), challenge_id), 
-                challenge.current_amount
-            );
-            log!(&env, "Challenge {} goal reached!", challenge_id);
+        if updated {
+            env.storage().persistent().set(&SocialKey::GroupMilestones(challenge.id), &milestones);
+        }
+    }
+    
+    /// Total up each team from its current members' savings, highest first
+    fn team_standings(env: &Env, challenge: &SavingsChallenge) -> Vec<TeamStanding> {
+        let mut standings: Vec<TeamStanding> = Vec::new(env);
+        for team in Self::get_teams(env.clone(), challenge.id).iter() {
+            standings.push_back(TeamStanding {
+                team,
+                total: 0,
+                members: 0,
+            });
+        }
+        if standings.is_empty() {
+            return standings;
         }
         
-        Ok(())
+        for participant in challenge.participants.iter() {
+            let team = match Self::get_team(env.clone(), challenge.id, participant.clone()) {
+                Some(team) => team,
+                None => continue,
+            };
+            let saved = Self::get_participant_stats(env.clone(), challenge.id, participant)
+                .map(|stats| stats.total_contributed)
+                .unwrap_or(0);
+            for i in 0..standings.len() {
+                let mut standing = standings.get(i).unwrap();
+                if standing.team == team {
+                    standing.total += saved;
+                    standing.members += 1;
+                    standings.set(i, standing);
+                    break;
+                }
+            }
+        }
+        
+        // Insertion sort; ties keep team creation order
+        for i in 1..standings.len() {
+            let mut j = i;
+            while j > 0 && standings.get(j).unwrap().total > standings.get(j - 1).unwrap().total {
+                let above = standings.get(j - 1).unwrap();
+                standings.set(j - 1, standings.get(j).unwrap());
+                standings.set(j, above);
+                j -= 1;
+            }
+        }
+        standings
     }
     
-    /// Finalize a completed challenge
-    pub fn finalize_challenge(
-        env: Env,
-        challenge_id: u32,
-        finalizer: Address,
-    ) -> Result<(), SavingsError> {
-        finalizer.require_auth();
+    /// Record the team that saved the most; a tie at the top means no winner
+    fn settle_teams(env: &Env, challenge: &SavingsChallenge) {
+        let standings = Self::team_standings(env, challenge);
+        let leader = match standings.get(0) {
+            Some(leader) if leader.total > 0 => leader,
+            _ => return,
+        };
+        if let Some(runner_up) = standings.get(1) {
+            if runner_up.total == leader.total {
+                return;
+            }
+        }
         
-        // Get challenge
-        let mut challenge: SavingsChallenge = env.storage().persistent()
+        env.storage().persistent().set(&SocialKey::WinningTeam(challenge.id), &leader.team);
+        
+        env.events().publish(
+            (symbol_short!("team_won"), challenge.id), 
+            (leader.team, leader.total)
+        );
+    }
+    
+    /// A challenge's score on a competition metric
+    fn competition_score(env: &Env, challenge_id: u32, metric: CompetitionMetric) -> Result<i128, SavingsError> {
+        let challenge: SavingsChallenge = env.storage().persistent()
             .get(&DataKey::Challenge(challenge_id))
             .ok_or(SavingsError::ChallengeNotFound)?;
         
-        // Check if finalizer is authorized (creator or participant)
-        if challenge.creator != finalizer && !challenge.participants.contains(&finalizer) {
-            return Err(SavingsError::NotAuthorized);
-        }
-        
-        if !challenge.is_active {
-            return Err(SavingsError::AlreadyFinalized);
-        }
+        Ok(match metric {
+            CompetitionMetric::GoalPercent => challenge.current_amount * 10000 / challenge.goal_amount,
+            CompetitionMetric::TotalSaved => challenge.current_amount,
+        })
+    }
+    
+    /// Once both sides of a challenge's competition are finalized, pick the winner and split the
+    /// prize among its participants. A tie settles with no winner and no prize.
+    fn settle_competition(env: &Env, challenge_id: u32) {
+        let competition_id = match Self::get_challenge_competition(env.clone(), challenge_id) {
+            Some(competition_id) => competition_id,
+            None => return,
+        };
+        let mut competition = match Self::get_competition(env.clone(), competition_id) {
+            Some(competition) if !competition.settled => competition,
+            _ => return,
+        };
         
-        let current_time = env.ledger().timestamp();
-        let goal_reached = challenge.current_amount >= challenge.goal_amount;
-        let time_expired = current_time > challenge.deadline;
+        let challenge_a: Option<SavingsChallenge> = env.storage().persistent()
+            .get(&DataKey::Challenge(competition.challenge_a));
+        let challenge_b: Option<SavingsChallenge> = env.storage().persistent()
+            .get(&DataKey::Challenge(competition.challenge_b));
+        let (challenge_a, challenge_b) = match (challenge_a, challenge_b) {
+            (Some(a), Some(b)) if a.finalized && b.finalized => (a, b),
+            _ => return,
+        };
         
-        // Can only finalize if goal reached or time expired
-        if !goal_reached && !time_expired {
-            return Err(SavingsError::InvalidParameters);
-        }
+        let (score_a, score_b) = match Self::get_competition_progress(env.clone(), competition_id) {
+            Ok(scores) => scores,
+            Err(_) => return,
+        };
+        let winner = if score_a > score_b {
+            Some(challenge_a)
+        } else if score_b > score_a {
+            Some(challenge_b)
+        } else {
+            None
+        };
         
-        // Mark as inactive
-        challenge.is_active = false;
-        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        competition.settled = true;
+        competition.winner = winner.as_ref().map(|winner| winner.id);
+        env.storage().persistent().set(&DataKey::Competition(competition_id), &competition);
         
-        // Emit finalization event
         env.events().publish(
-            (symbol_short!("finalized"), challenge_id), 
-            (goal_reached, challenge.current_amount, current_time)
+            (symbol_short!("comp_won"), competition_id), 
+            (competition.winner, score_a, score_b)
         );
         
-        log!(&env, "Challenge {} finalized. Goal reached: {}", challenge_id, goal_reached);
+        let winner = match winner {
+            Some(winner) if !winner.participants.is_empty() && competition.prize > 0 => winner,
+            _ => return,
+        };
+        let savecoin: Option<Address> = env.storage().instance().get(&DataKey::SaveCoinToken);
+        let savecoin = match savecoin {
+            Some(savecoin) => savecoin,
+            None => return,
+        };
+        let client = SaveCoinClient::new(env, &savecoin);
         
+        // Split evenly; the first participant also takes the rounding dust
+        let count = winner.participants.len() as i128;
+        let per_participant = competition.prize / count;
+        let dust = competition.prize - per_participant * count;
+        for (i, participant) in winner.participants.iter().enumerate() {
+            let amount = if i == 0 { per_participant + dust } else { per_participant };
+            if amount <= 0 {
+                continue;
+            }
+            
+            let mut key_data = Bytes::from_array(env, &competition_id.to_be_bytes());
+            key_data.append(&participant.clone().to_xdr(env));
+            let dedupe_key: BytesN<32> = env.crypto().sha256(&key_data).into();
+            
+            let minted = client.try_mint_reward(
+                &env.current_contract_address(),
+                &participant,
+                &amount,
+                &RewardType::ChallengeCompleted,
+                &winner.id,
+                &10000,
+                &Some(dedupe_key),
+                &false
+            );
+            if minted.is_err() {
+                log!(env, "Competition prize {} could not be minted for {}", amount, participant);
+            } else {
+                Self::record_reward(env, &participant, amount);
+            }
+        }
+    }
+    
+    /// Plan parameters can only change while a challenge is still enrolling
+    fn require_not_started(env: &Env, challenge: &SavingsChallenge) -> Result<(), SavingsError> {
+        if !challenge.is_active {
+            return Err(SavingsError::ChallengeInactive);
+        }
+        if env.ledger().timestamp() >= challenge.start_time {
+            return Err(SavingsError::ChallengeStarted);
+        }
         Ok(())
     }
     
-    // ===== QUERY FUNCTIONS =====
+    /// The last week that counted toward a participant's streak; only meaningful once one has
+    fn last_counted_week(challenge: &SavingsChallenge, stats: &ParticipantStats) -> u32 {
+        if challenge.min_weekly_required {
+            stats.last_week_met
+        } else {
+            ((stats.last_contribution - challenge.start_time) / WEEK_IN_SECONDS) as u32 + 1
+        }
+    }
     
-    /// Get challenge details
-    pub fn get_challenge(env: Env, challenge_id: u32) -> Result<SavingsChallenge, SavingsError> {
-        env.storage().persistent()
-            .get(&DataKey::Challenge(challenge_id))
-            .ok_or(SavingsError::ChallengeNotFound)
+    /// Whether a participant declared a pause covering `week`, or the contract was halted during it
+    fn is_paused_in(env: &Env, challenge: &SavingsChallenge, user: &Address, week: u32) -> bool {
+        Self::paused_weeks_between(env, challenge, user, week.saturating_sub(1), week + 1) > 0
     }
     
-    /// Get challenge contributions
-    pub fn get_contributions(env: Env, challenge_id: u32) -> Result<Vec<Contribution>, SavingsError> {
-        // Verify challenge exists
-        let _challenge: SavingsChallenge = env.storage().persistent()
-            .get(&DataKey::Challenge(challenge_id))
-            .ok_or(SavingsError::ChallengeNotFound)?;
-        
-        let contributions: Vec<Contribution> = env.storage().persistent()
-            .get(&DataKey::Contributions(challenge_id))
-            .unwrap_or(Vec::new(&env));
+    /// Count a participant's paused weeks strictly between `after` and `before`, halted weeks included
+    fn paused_weeks_between(env: &Env, challenge: &SavingsChallenge, user: &Address, after: u32, before: u32) -> u32 {
+        let pauses = Self::get_pauses(env.clone(), challenge.id, user.clone());
+        let mut halts = Self::get_halt_windows(env.clone());
+        let halted_since: Option<u64> = env.storage().instance().get(&DataKey::ContractPaused);
+        if let Some(from) = halted_since {
+            halts.push_back(HaltWindow { from, until: u64::MAX });
+        }
         
-        Ok(contributions)
+        let mut paused = 0;
+        for week in (after + 1)..before {
+            let week_start = challenge.start_time + (week.saturating_sub(1) as u64 * WEEK_IN_SECONDS);
+            let week_end = week_start + WEEK_IN_SECONDS;
+            if pauses.iter().any(|pause| pause.from_week <= week && week <= pause.until_week)
+                || halts.iter().any(|halt| halt.from < week_end && halt.until > week_start)
+            {
+                paused += 1;
+            }
+        }
+        paused
     }
     
-    /// Get participant statistics for a challenge
-    pub fn get_participant_stats(
-        env: Env, 
-        challenge_id: u32, 
-        participant: Address
-    ) -> Result<ParticipantStats, SavingsError> {
-        // Verify challenge exists
-        let _challenge: SavingsChallenge = env.storage().persistent()
-            .get(&DataKey::Challenge(challenge_id))
-            .ok_or(SavingsError::ChallengeNotFound)?;
+    /// Whether a challenge shows its participant rankings publicly
+    fn leaderboard_visible(env: &Env, challenge_id: u32) -> bool {
+        let challenge: Option<SavingsChallenge> = env.storage().persistent().get(&DataKey::Challenge(challenge_id));
+        challenge.is_some_and(|challenge| challenge.social_features.public_leaderboard)
+    }
+    
+    /// Pay out a share, returning escrowed extra assets as-is and the rest in the challenge token.
+    /// A fee is kept in each asset paid; returns its value in the challenge token.
+    fn transfer_share(env: &Env, challenge: &SavingsChallenge, participant: &Address, share: i128, fee_bps: u32) -> i128 {
+        let mut remaining = share;
+        let mut fee_value = 0;
         
-        let stats: ParticipantStats = env.storage().persistent()
-            .get(&DataKey::ParticipantStats(challenge_id, participant))
-            .unwrap_or(ParticipantStats {
-                total_contributed: 0,
-                contribution_count: 0,
-                last_contribution: 0,
-                current_streak: 0,
-            });
+        for asset in Self::get_accepted_assets(env.clone(), challenge.id).keys().iter() {
+            let key = ParticipantKey::AssetEscrow(challenge.id, participant.clone(), asset.clone());
+            let escrow: Option<AssetEscrow> = env.storage().persistent().get(&key);
+            if let Some(escrow) = escrow {
+                env.storage().persistent().remove(&key);
+                let fee = (escrow.amount * fee_bps as i128) / 10000;
+                Self::accrue_fee(env, &asset, fee);
+                token::Client::new(env, &asset).transfer(
+                    &env.current_contract_address(),
+                    participant,
+                    &(escrow.amount - fee)
+                );
+                remaining -= escrow.value;
+                fee_value += (escrow.value * fee_bps as i128) / 10000;
+            }
+        }
         
-        Ok(stats)
+        if remaining > 0 {
+            let fee = (remaining * fee_bps as i128) / 10000;
+            Self::accrue_fee(env, &challenge.token, fee);
+            token::Client::new(env, &challenge.token).transfer(
+                &env.current_contract_address(),
+                participant,
+                &(remaining - fee)
+            );
+            fee_value += fee;
+        }
+        
+        fee_value
     }
     
-    /// Get challenges for a user
-    pub fn get_user_challenges(env: Env, user: Address) -> Vec<u32> {
-        env.storage().persistent()
-            .get(&DataKey::UserChallenges(user))
-            .unwrap_or(Vec::new(&env))
+    /// Add to the protocol fees held for the treasury in a token
+    fn accrue_fee(env: &Env, token: &Address, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let accrued = Self::get_accrued_fees(env.clone(), token.clone());
+        env.storage().persistent().set(&FinanceKey::AccruedFees(token.clone()), &(accrued + amount));
     }
     
-    /// Get challenge progress information
-    pub fn get_challenge_progress(env: Env, challenge_id: u32) -> Result<(i128, i128, u32, bool), SavingsError> {
-        let challenge: SavingsChallenge = env.storage().persistent()
-            .get(&DataKey::Challenge(challenge_id))
-            .ok_or(SavingsError::ChallengeNotFound)?;
+    /// Split a removed participant's extra-asset escrow among those remaining, returning the value moved
+    fn forfeit_asset_escrow(env: &Env, challenge: &SavingsChallenge, user: &Address) -> i128 {
+        let count = challenge.participants.len() as i128;
+        let mut moved = 0;
         
-        let current_time = env.ledger().timestamp();
-        let weeks_elapsed = ((current_time - challenge.created_at) / (7 * 24 * 60 * 60)) as u32;
-        let is_expired = current_time > challenge.deadline;
+        for asset in Self::get_accepted_assets(env.clone(), challenge.id).keys().iter() {
+            let key = ParticipantKey::AssetEscrow(challenge.id, user.clone(), asset.clone());
+            let escrow: Option<AssetEscrow> = env.storage().persistent().get(&key);
+            let escrow = match escrow {
+                Some(escrow) => escrow,
+                None => continue,
+            };
+            env.storage().persistent().remove(&key);
+            
+            for (i, participant) in challenge.participants.iter().enumerate() {
+                // The first participant also takes the rounding dust
+                let (amount, value) = if i == 0 {
+                    (escrow.amount - (escrow.amount / count) * (count - 1), escrow.value - (escrow.value / count) * (count - 1))
+                } else {
+                    (escrow.amount / count, escrow.value / count)
+                };
+                let their_key = ParticipantKey::AssetEscrow(challenge.id, participant.clone(), asset.clone());
+                let mut theirs: AssetEscrow = env.storage().persistent()
+                    .get(&their_key)
+                    .unwrap_or(AssetEscrow { amount: 0, value: 0 });
+                theirs.amount += amount;
+                theirs.value += value;
+                env.storage().persistent().set(&their_key, &theirs);
+                
+                // Their share grows by what they now hold
+                let bonus: i128 = env.storage().persistent()
+                    .get(&ParticipantKey::Bonus(challenge.id, participant.clone()))
+                    .unwrap_or(0);
+                env.storage().persistent().set(&ParticipantKey::Bonus(challenge.id, participant), &(bonus + value));
+            }
+            moved += escrow.value;
+        }
         
-        Ok((
-            challenge.current_amount,
-            challenge.goal_amount,
-            weeks_elapsed,
-            is_expired
-        ))
+        moved
     }
     
-    /// Check if user is participant in challenge
-    pub fn is_participant(env: Env, challenge_id: u32, user: Address) -> Result<bool, SavingsError> {
-        let challenge: SavingsChallenge = env.storage().persistent()
-            .get(&DataKey::Challenge(challenge_id))
-            .ok_or(SavingsError::ChallengeNotFound)?;
+    /// Move escrowed contributions into the challenge's yield vault
+    fn deposit_to_vault(env: &Env, challenge_id: u32, token: &Address, amount: i128) -> Result<(), SavingsError> {
+        let mut prize_pool: PrizePool = env.storage().persistent()
+            .get(&FinanceKey::PrizePool(challenge_id))
+            .ok_or(SavingsError::VaultNotConfigured)?;
         
-        Ok(challenge.participants.contains(&user))
+        // The vault pulls from this contract, so pre-authorize that nested transfer
+        let this = env.current_contract_address();
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: (this.clone(), prize_pool.vault.clone(), amount).into_val(env),
+                },
+                sub_invocations: Vec::new(env),
+            }),
+        ]);
+        
+        let shares = YieldVaultClient::new(env, &prize_pool.vault).deposit(&this, &amount);
+        
+        prize_pool.shares += shares;
+        prize_pool.principal += amount;
+        env.storage().persistent().set(&FinanceKey::PrizePool(challenge_id), &prize_pool);
+        
+        Ok(())
     }
     
-    // ===== ADMIN FUNCTIONS =====
-    
-    /// Emergency pause/unpause (admin only)
-    pub fn set_challenge_active(
-        env: Env,
-        challenge_id: u32,
-        active: bool,
-        admin: Address
-    ) -> Result<(), SavingsError> {
-        admin.require_auth();
+    /// Redeem the vault position and split the yield pro-rata or among randomly drawn consistent savers
+    fn settle_prize_pool(env: &Env, challenge: &SavingsChallenge) -> Result<(), SavingsError> {
+        let mut prize_pool: PrizePool = env.storage().persistent()
+            .get(&FinanceKey::PrizePool(challenge.id))
+            .ok_or(SavingsError::VaultNotConfigured)?;
         
-        let stored_admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .ok_or(SavingsError::NotAuthorized)?;
+        if prize_pool.settled {
+            return Ok(());
+        }
         
-        if admin != stored_admin {
-            return Err(SavingsError::NotAuthorized);
+        let redeemed = if prize_pool.shares > 0 {
+            YieldVaultClient::new(env, &prize_pool.vault)
+                .redeem(&env.current_contract_address(), &prize_pool.shares)
+        } else {
+            0
+        };
+        prize_pool.shares = 0;
+        prize_pool.settled = true;
+        
+        // Only what came back above principal is prize money
+        prize_pool.prize = (redeemed - prize_pool.principal).max(0);
+        
+        // A vault that lost money cuts everyone's principal pro rata instead of paying out what it doesn't have
+        if redeemed < prize_pool.principal {
+            prize_pool.shortfall = prize_pool.principal - redeemed;
+            let mut covered: i128 = 0;
+            for participant in challenge.participants.iter() {
+                let stats: Option<ParticipantStats> = env.storage().persistent()
+                    .get(&ParticipantKey::ParticipantStats(challenge.id, participant));
+                if let Some(stats) = stats {
+                    covered += Self::principal_haircut(&prize_pool, stats.total_contributed);
+                }
+            }
+            
+            // Principal forfeited into the bonus pot takes the rest of the loss
+            let pot = Self::get_bonus_pot(env.clone(), challenge.id);
+            let uncovered = (prize_pool.shortfall - covered).max(0).min(pot);
+            if uncovered > 0 {
+                env.storage().persistent().set(&FinanceKey::BonusPot(challenge.id), &(pot - uncovered));
+            }
         }
         
-        let mut challenge: SavingsChallenge = env.storage().persistent()
-            .get(&DataKey::Challenge(challenge_id))
-            .ok_or(SavingsError::ChallengeNotFound)?;
+        let winner_count = match prize_pool.split {
+            YieldSplit::PrizePot(winner_count) => winner_count,
+            YieldSplit::ProRata => {
+                Self::split_yield_pro_rata(env, challenge, &prize_pool);
+                env.storage().persistent().set(&FinanceKey::PrizePool(challenge.id), &prize_pool);
+                return Ok(());
+            },
+            YieldSplit::BonusPot => {
+                Self::add_to_bonus_pot(env, challenge.id, prize_pool.prize);
+                env.storage().persistent().set(&FinanceKey::PrizePool(challenge.id), &prize_pool);
+                return Ok(());
+            },
+        };
         
-        challenge.is_active = active;
-        env.storage().persistent().set(&DataKey::Challenge(challenge_id), &challenge);
+        // Consistent savers met the amount due in every week that passed
+        let weeks_elapsed = (env.ledger().timestamp().min(challenge.deadline).saturating_sub(challenge.start_time)
+            / WEEK_IN_SECONDS).max(1) as u32;
+        let mut eligible: Vec<Address> = Vec::new(env);
+        for participant in challenge.participants.iter() {
+            let stats: Option<ParticipantStats> = env.storage().persistent()
+                .get(&ParticipantKey::ParticipantStats(challenge.id, participant.clone()));
+            if let Some(stats) = stats {
+                if stats.weeks_met >= weeks_elapsed {
+                    eligible.push_back(participant);
+                }
+            }
+        }
         
-        env.events().publish(
-            (symbol_short!("admin_set"), challenge_id), 
-            (admin, active)
-        );
+        // With nobody eligible the yield still belongs to the group
+        if prize_pool.prize > 0 && eligible.is_empty() {
+            Self::add_to_bonus_pot(env, challenge.id, prize_pool.prize);
+        }
+        
+        if prize_pool.prize > 0 && !eligible.is_empty() {
+            let draws = winner_count.min(eligible.len());
+            for _ in 0..draws {
+                let pick = env.prng().gen_range::<u64>(0..eligible.len() as u64) as u32;
+                prize_pool.winners.push_back(eligible.get(pick).unwrap());
+                eligible.remove(pick);
+            }
+            
+            // Split evenly; the first winner also takes the rounding dust
+            let per_winner = prize_pool.prize / draws as i128;
+            let dust = prize_pool.prize - per_winner * draws as i128;
+            for (i, winner) in prize_pool.winners.iter().enumerate() {
+                let amount = if i == 0 { per_winner + dust } else { per_winner };
+                env.storage().persistent().set(&ParticipantKey::PrizeWinnings(challenge.id, winner.clone()), &amount);
+                
+                env.events().publish(
+                    (symbol_short!("prize"), challenge.id), 
+                    (winner, amount)
+                );
+            }
+        }
+        
+        env.storage().persistent().set(&FinanceKey::PrizePool(challenge.id), &prize_pool);
         
         Ok(())
     }
     
-    /// Get contract information
-    pub fn get_contract_info(env: Env) -> (String, String) {
-        env.storage().instance()
-            .get(&DataKey::ContractInfo)
-            .unwrap_or((
-                String::from_str(&env, "StellarSave Challenge Contract"),
-                String::from_str(&env, "1.0.0")
-            ))
+    /// A participant's part of a vault shortfall, rounded up so the payouts never exceed what was redeemed
+    fn principal_haircut(prize_pool: &PrizePool, contributed: i128) -> i128 {
+        if prize_pool.shortfall <= 0 || prize_pool.principal <= 0 || contributed <= 0 {
+            return 0;
+        }
+        ((contributed * prize_pool.shortfall + prize_pool.principal - 1) / prize_pool.principal).min(contributed)
     }
     
-    // ===== HELPER FUNCTIONS =====
-    
-    /// Add challenge ID to user's challenge list
-    fn add_challenge_to_user(env: &Env, user: &Address, challenge_id: u32) {
-        let mut user_challenges: Vec<u32> = env.storage().persistent()
-            .get(&DataKey::UserChallenges(user.clone()))
-            .unwrap_or(Vec::new(env));
+    /// Credit each participant yield in proportion to what they had in the vault
+    fn split_yield_pro_rata(env: &Env, challenge: &SavingsChallenge, prize_pool: &PrizePool) {
+        if prize_pool.prize <= 0 || prize_pool.principal <= 0 {
+            return;
+        }
         
-        if !user_challenges.contains(&challenge_id) {
-            user_challenges.push_back(challenge_id);
-            env.storage().persistent().set(&DataKey::UserChallenges(user.clone()), &user_challenges);
+        let mut paid = 0;
+        for (i, participant) in challenge.participants.iter().enumerate() {
+            let stats: Option<ParticipantStats> = env.storage().persistent()
+                .get(&ParticipantKey::ParticipantStats(challenge.id, participant.clone()));
+            let contributed = match stats {
+                Some(stats) => stats.total_contributed,
+                None => continue,
+            };
+            
+            // The last participant takes the rounding dust
+            let amount = if i as u32 == challenge.participants.len() - 1 {
+                prize_pool.prize - paid
+            } else {
+                (prize_pool.prize * contributed) / prize_pool.principal
+            };
+            if amount <= 0 {
+                continue;
+            }
+            paid += amount;
+            env.storage().persistent().set(&ParticipantKey::PrizeWinnings(challenge.id, participant.clone()), &amount);
+            
+            env.events().publish(
+                (symbol_short!("yield"), challenge.id), 
+                (participant, amount)
+            );
         }
     }
     
-    /// Calculate expected contribution amount by week
-    pub fn get_expected_amount(env: Env, challenge_id: u32) -> Result<i128, SavingsError> {
+    /// Calculate how much a participant should have saved by now under the challenge type
+    pub fn get_expected_amount(env: Env, challenge_id: u32, user: Address) -> Result<i128, SavingsError> {
         let challenge: SavingsChallenge = env.storage().persistent()
             .get(&DataKey::Challenge(challenge_id))
             .ok_or(SavingsError::ChallengeNotFound)?;
         
-        let current_time = env.ledger().timestamp();
-        let weeks_elapsed = (current_time - challenge.created_at) / (7 * 24 * 60 * 60);
+        let current_time = env.ledger().timestamp().min(challenge.deadline);
+        let weeks_elapsed = (current_time.saturating_sub(challenge.start_time) / WEEK_IN_SECONDS) as u32;
         
-        let expected = (weeks_elapsed as i128) * challenge.weekly_amount;
-        Ok(expected.min(challenge.goal_amount))
+        let mut expected: i128 = 0;
+        for week in 1..=weeks_elapsed {
+            expected += Self::amount_due(&env, &challenge, &user, week);
+        }
+        Ok(expected.min(Self::personal_target(&env, &challenge, &user)))
     }
     
     /// Get challenge statistics
@@ -505,12 +6844,8 @@ impl SavingsChallengeContract {
             .get(&DataKey::Challenge(challenge_id))
             .ok_or(SavingsError::ChallengeNotFound)?;
         
-        let contributions: Vec<Contribution> = env.storage().persistent()
-            .get(&DataKey::Contributions(challenge_id))
-            .unwrap_or(Vec::new(&env));
-        
         let participant_count = challenge.participants.len();
-        let total_contributions = contributions.len();
+        let total_contributions = Self::get_contribution_count(env.clone(), challenge_id);
         let average_contribution = if total_contributions > 0 {
             challenge.current_amount / total_contributions as i128
         } else {
@@ -524,4 +6859,6 @@ impl SavingsChallengeContract {
             total_contributions
         ))
     }
-}
\ No newline at end of file
+}
+
+mod test;
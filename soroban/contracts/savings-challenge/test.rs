@@ -0,0 +1,303 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+struct Setup<'a> {
+    env: Env,
+    client: SavingsChallengeContractClient<'a>,
+    token: TokenClient<'a>,
+    admin: Address,
+    creator: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let token_address = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let minter = StellarAssetClient::new(&env, &token_address);
+    minter.mint(&alice, &1_000);
+    minter.mint(&bob, &1_000);
+
+    let contract_id = env.register_contract(None, SavingsChallengeContract);
+    let client = SavingsChallengeContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    Setup {
+        token: TokenClient::new(&env, &token_address),
+        env,
+        client,
+        admin,
+        creator,
+        alice,
+        bob,
+    }
+}
+
+fn options(allow_early_withdrawal: bool, start_time: u64) -> ChallengeOptions {
+    ChallengeOptions {
+        min_weekly_required: false,
+        allow_early_withdrawal,
+        visibility: Visibility::Open,
+        max_participants: 0,
+        mode: ChallengeMode::Standard,
+        start_time,
+    }
+}
+
+fn create(s: &Setup, participants: Vec<Address>, goal: i128, options: ChallengeOptions) -> u32 {
+    s.client.create_challenge(
+        &s.creator,
+        &s.token.address,
+        &String::from_str(&s.env, "Rainy day"),
+        &String::from_str(&s.env, "Save together"),
+        &goal,
+        &100,
+        &participants,
+        &2,
+        &options,
+    )
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().set_timestamp(timestamp);
+}
+
+#[test]
+fn contribute_escrows_and_withdraw_share_pays_back_after_finalization() {
+    let s = setup();
+    let id = create(&s, vec![&s.env, s.alice.clone(), s.bob.clone()], 200, options(false, 0));
+
+    s.client.contribute(&id, &s.alice, &100);
+    s.client.contribute(&id, &s.bob, &100);
+    assert_eq!(s.token.balance(&s.client.address), 200);
+    assert_eq!(s.client.get_challenge(&id).current_amount, 200);
+    assert_eq!(s.client.get_participant_stats(&id, &s.alice).total_contributed, 100);
+
+    // Nothing comes out before the challenge settles
+    assert_eq!(s.client.try_withdraw_share(&id, &s.alice), Err(Ok(SavingsError::ChallengeStillActive)));
+
+    s.client.finalize_challenge(&id, &s.creator);
+    let challenge = s.client.get_challenge(&id);
+    assert!(challenge.finalized);
+    assert!(!challenge.is_active);
+    assert_eq!(s.client.try_finalize_challenge(&id, &s.creator), Err(Ok(SavingsError::AlreadyFinalized)));
+
+    assert_eq!(s.client.withdraw_share(&id, &s.alice), 100);
+    assert_eq!(s.token.balance(&s.alice), 1_000);
+    assert!(s.client.has_withdrawn(&id, &s.alice));
+    assert_eq!(s.client.try_withdraw_share(&id, &s.alice), Err(Ok(SavingsError::AlreadyWithdrawn)));
+
+    assert_eq!(s.client.withdraw_share(&id, &s.bob), 100);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn contribute_rejects_bad_amounts_and_outsiders() {
+    let s = setup();
+    let id = create(&s, vec![&s.env, s.alice.clone()], 200, options(false, 0));
+
+    assert_eq!(s.client.try_contribute(&id, &s.alice, &0), Err(Ok(SavingsError::InsufficientAmount)));
+    assert_eq!(s.client.try_contribute(&id, &s.bob, &100), Err(Ok(SavingsError::NotParticipant)));
+    assert_eq!(s.client.try_contribute(&99, &s.alice, &100), Err(Ok(SavingsError::ChallengeNotFound)));
+}
+
+#[test]
+fn contribute_waits_for_start_and_stops_after_deadline() {
+    let s = setup();
+    let id = create(&s, vec![&s.env, s.alice.clone()], 1_000, options(false, 2_000));
+
+    assert_eq!(s.client.try_contribute(&id, &s.alice, &100), Err(Ok(SavingsError::ChallengeNotStarted)));
+
+    set_time(&s.env, 2_000);
+    s.client.contribute(&id, &s.alice, &100);
+
+    let deadline = s.client.get_challenge(&id).deadline;
+    set_time(&s.env, deadline + 1);
+    assert_eq!(s.client.try_contribute(&id, &s.alice, &100), Err(Ok(SavingsError::ChallengeExpired)));
+}
+
+#[test]
+fn finalize_needs_goal_or_deadline() {
+    let s = setup();
+    let id = create(&s, vec![&s.env, s.alice.clone()], 500, options(false, 0));
+    s.client.contribute(&id, &s.alice, &100);
+
+    assert_eq!(s.client.try_finalize_challenge(&id, &s.alice), Err(Ok(SavingsError::InvalidParameters)));
+    assert_eq!(s.client.try_finalize_challenge(&id, &s.bob), Err(Ok(SavingsError::NotAuthorized)));
+
+    // Short of the goal, the deadline settles it and savers still get their principal back
+    set_time(&s.env, s.client.get_challenge(&id).deadline + 1);
+    s.client.finalize_challenge(&id, &s.alice);
+    assert!(s.client.get_challenge(&id).finalized);
+    assert_eq!(s.client.withdraw_share(&id, &s.alice), 100);
+}
+
+#[test]
+fn early_withdraw_penalty_feeds_bonus_pot() {
+    let s = setup();
+    let id = create(&s, vec![&s.env, s.alice.clone(), s.bob.clone()], 1_000, options(true, 0));
+    s.client.contribute(&id, &s.alice, &200);
+    s.client.contribute(&id, &s.bob, &200);
+
+    // The default 10% stays behind for the rest of the group
+    assert_eq!(s.client.early_withdraw(&s.alice, &id, &100), 90);
+    assert_eq!(s.token.balance(&s.alice), 890);
+    assert_eq!(s.client.get_bonus_pot(&id), 10);
+    assert_eq!(s.client.get_participant_stats(&id, &s.alice).total_contributed, 100);
+    assert_eq!(s.client.get_challenge(&id).current_amount, 310);
+
+    assert_eq!(s.client.try_early_withdraw(&s.alice, &id, &101), Err(Ok(SavingsError::InsufficientAmount)));
+}
+
+#[test]
+fn early_withdraw_penalty_goes_to_charity_when_set() {
+    let s = setup();
+    let charity = Address::generate(&s.env);
+    let id = create(&s, vec![&s.env, s.alice.clone(), s.bob.clone()], 1_000, options(true, 2_000));
+    s.client.set_charity(&id, &s.creator, &Some(charity.clone()));
+
+    set_time(&s.env, 2_000);
+    s.client.contribute(&id, &s.alice, &200);
+
+    assert_eq!(s.client.early_withdraw(&s.alice, &id, &100), 90);
+    assert_eq!(s.token.balance(&charity), 10);
+    assert_eq!(s.client.get_donated(&id), 10);
+    assert_eq!(s.client.get_bonus_pot(&id), 0);
+}
+
+#[test]
+fn early_withdraw_is_free_for_solo_savers_and_gated_by_the_challenge() {
+    let s = setup();
+    let id = create(&s, vec![&s.env, s.alice.clone()], 1_000, options(true, 0));
+    s.client.contribute(&id, &s.alice, &200);
+
+    assert_eq!(s.client.early_withdraw(&s.alice, &id, &100), 100);
+    assert_eq!(s.client.get_bonus_pot(&id), 0);
+
+    let locked = create(&s, vec![&s.env, s.alice.clone()], 1_000, options(false, 0));
+    s.client.contribute(&locked, &s.alice, &100);
+    assert_eq!(
+        s.client.try_early_withdraw(&s.alice, &locked, &50),
+        Err(Ok(SavingsError::EarlyWithdrawalDisabled))
+    );
+}
+
+/// Two savers over two weeks; Bob skips week 2, is penalized 50 of his 100 and flags the challenge
+fn penalized_challenge(s: &Setup) -> (u32, Address) {
+    let mut opts = options(false, 2_000);
+    opts.min_weekly_required = true;
+    let id = create(s, vec![&s.env, s.alice.clone(), s.bob.clone()], 1_000, opts);
+    s.client.set_missed_week_penalty(&id, &s.creator, &50);
+
+    set_time(&s.env, 2_000);
+    s.client.contribute(&id, &s.alice, &100);
+    s.client.contribute(&id, &s.bob, &100);
+
+    set_time(&s.env, 2_000 + WEEK_IN_SECONDS);
+    s.client.contribute(&id, &s.alice, &100);
+
+    set_time(&s.env, 2_000 + 2 * WEEK_IN_SECONDS);
+    assert_eq!(s.client.assess_week(&id, &2), 50);
+    assert_eq!(s.client.get_penalty_and_bonus(&id, &s.bob), (50, 0));
+
+    set_time(&s.env, s.client.get_challenge(&id).deadline + 1);
+    s.client.finalize_challenge(&id, &s.creator);
+
+    let arbiter = Address::generate(&s.env);
+    s.client.set_arbiter(&s.admin, &arbiter);
+    s.client.flag_challenge(&id, &s.bob, &String::from_str(&s.env, "unfair penalty"));
+    (id, arbiter)
+}
+
+#[test]
+fn dispute_freezes_payouts_until_resolved() {
+    let s = setup();
+    let (id, arbiter) = penalized_challenge(&s);
+
+    assert_eq!(s.client.try_withdraw_share(&id, &s.bob), Err(Ok(SavingsError::ChallengeDisputed)));
+    assert_eq!(
+        s.client.try_flag_challenge(&id, &s.alice, &String::from_str(&s.env, "again")),
+        Err(Ok(SavingsError::ChallengeDisputed))
+    );
+
+    assert_eq!(s.client.try_resolve_dispute(&id, &s.bob, &Resolution::Release), Err(Ok(SavingsError::NotAuthorized)));
+    assert_eq!(
+        s.client.try_resolve_dispute(&id, &arbiter, &Resolution::Pending),
+        Err(Ok(SavingsError::InvalidParameters))
+    );
+    assert_eq!(
+        s.client.try_resolve_dispute(&id, &arbiter, &Resolution::Partial(10_001)),
+        Err(Ok(SavingsError::InvalidParameters))
+    );
+
+    s.client.resolve_dispute(&id, &arbiter, &Resolution::Release);
+    assert_eq!(
+        s.client.try_resolve_dispute(&id, &arbiter, &Resolution::Release),
+        Err(Ok(SavingsError::NoDispute))
+    );
+
+    // Release lets the penalty stand
+    assert_eq!(s.client.withdraw_share(&id, &s.bob), 50);
+    assert_eq!(s.client.withdraw_share(&id, &s.alice), 200);
+}
+
+#[test]
+fn refund_all_voids_penalties() {
+    let s = setup();
+    let (id, arbiter) = penalized_challenge(&s);
+
+    s.client.resolve_dispute(&id, &arbiter, &Resolution::RefundAll);
+    assert_eq!(s.client.withdraw_share(&id, &s.bob), 100);
+}
+
+#[test]
+fn partial_ruling_voids_part_of_the_penalty() {
+    let s = setup();
+    let (id, arbiter) = penalized_challenge(&s);
+
+    s.client.resolve_dispute(&id, &arbiter, &Resolution::Partial(5_000));
+    assert_eq!(s.client.withdraw_share(&id, &s.bob), 75);
+}
+
+#[test]
+fn donated_penalties_stand_under_any_ruling() {
+    let s = setup();
+    let charity = Address::generate(&s.env);
+    let mut opts = options(false, 2_000);
+    opts.min_weekly_required = true;
+    let id = create(&s, vec![&s.env, s.alice.clone(), s.bob.clone()], 1_000, opts);
+    s.client.set_missed_week_penalty(&id, &s.creator, &50);
+    s.client.set_charity(&id, &s.creator, &Some(charity.clone()));
+
+    set_time(&s.env, 2_000);
+    s.client.contribute(&id, &s.alice, &100);
+    s.client.contribute(&id, &s.bob, &100);
+    set_time(&s.env, 2_000 + WEEK_IN_SECONDS);
+    s.client.contribute(&id, &s.alice, &100);
+    set_time(&s.env, 2_000 + 2 * WEEK_IN_SECONDS);
+    s.client.assess_week(&id, &2);
+    assert_eq!(s.token.balance(&charity), 50);
+
+    let arbiter = Address::generate(&s.env);
+    s.client.set_arbiter(&s.admin, &arbiter);
+    s.client.flag_challenge(&id, &s.bob, &String::from_str(&s.env, "unfair penalty"));
+    s.client.resolve_dispute(&id, &arbiter, &Resolution::RefundAll);
+
+    // RefundAll closes the challenge; the donated 50 is gone for good
+    assert!(s.client.get_challenge(&id).finalized);
+    assert_eq!(s.client.withdraw_share(&id, &s.bob), 50);
+    assert_eq!(s.client.withdraw_share(&id, &s.alice), 200);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
@@ -1,77 +1,232 @@
 use soroban_sdk::{
-    contract, contractimpl, contracttype, token, Address, Env, Map, Symbol
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, Vec
 };
 
 #[contracttype]
 pub enum DataKey {
     Admin,
-    TokenAddress,
-    Deposit(Address), // User address -> Deposit
-    TotalDeposits,
+    AcceptedTokens,    // Vec<Address> of tokens that can be locked, managed by the admin
+    Deposit(Address, u32), // User address, Deposit ID -> Deposit
+    DepositCount(Address), // User address -> deposits ever made (the next deposit ID)
+    TotalDeposits(Address), // Token -> amount locked in it
+    EarlyExit,         // EarlyExitConfig, unset while early withdrawal is off
+    InterestTiers,     // Vec<InterestTier>, shortest lock first
+    RewardReserve(Address), // Token -> set aside to pay interest on matured deposits in it
+    Beneficiary(Address, u32), // User address, Deposit ID -> BeneficiaryDesignation
+    LastActive(Address), // User address -> time of their last interaction with the vault
 }
 
+// Entries written before deposits were keyed by ID; vaults deployed back then still hold them in instance storage
 #[contracttype]
-pub struct Deposit {
+pub enum LegacyKey {
+    TokenAddress,      // The one token the vault locked
+    Deposit(Address),  // User address -> LegacyDeposit
+    TotalDeposits,     // Amount still locked in legacy deposits
+}
+
+#[contracttype]
+pub struct LegacyDeposit {
     user: Address,
     amount: i128,
     lock_time: u64,
     withdrawn: bool,
 }
 
+// Most deposits get_deposits returns in one call
+const MAX_PAGE_SIZE: u32 = 50;
+
+#[contracttype]
+pub struct Deposit {
+    id: u32,
+    user: Address,
+    token: Address,
+    amount: i128,
+    deposited_at: u64,
+    lock_time: u64,    // When the last of the deposit unlocks
+    committed: u64,    // Seconds the deposit has been held locked for, which sets its interest tier
+    tranches: u32,     // Equal parts unlocking one per interval; 1 for a single cliff at lock_time
+    interval: u64,     // Seconds between tranches (0 for a cliff)
+    claimed: i128,     // Already paid out from unlocked tranches
+    withdrawn: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestTier {
+    min_days: u32,     // Shortest lock that earns this rate
+    rate_bps: u32,     // Paid once on the deposit amount at withdrawal
+}
+
+#[contracttype]
+pub struct BeneficiaryDesignation {
+    beneficiary: Address,
+    inactivity_days: u32, // Owner silence after unlock before the beneficiary can claim
+}
+
+#[contracttype]
+pub struct EarlyExitConfig {
+    penalty_bps: u32,  // Taken from a deposit withdrawn before its lock_time
+    treasury: Address, // Receives the penalties
+    decaying: bool,    // Scale the penalty down linearly as the unlock approaches
+}
+
 #[contract]
 pub struct TimelockVault;
 
 #[contractimpl]
 impl TimelockVault {
-    // Initialize the contract with an admin and the token address to be used
+    // Initialize the contract with an admin and the first token that can be locked
     pub fn initialize(env: Env, admin: Address, token_address: Address) {
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::TokenAddress, &token_address);
-        env.storage().instance().set(&DataKey::TotalDeposits, &0i128);
+        env.storage().instance().set(&DataKey::AcceptedTokens, &Vec::from_array(&env, [token_address]));
+    }
+    
+    // Accept another token for deposits (admin only)
+    pub fn add_accepted_token(env: Env, token_address: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        
+        let mut tokens = Self::get_accepted_tokens(env.clone());
+        if tokens.contains(&token_address) {
+            panic!("Token already accepted");
+        }
+        tokens.push_back(token_address);
+        env.storage().instance().set(&DataKey::AcceptedTokens, &tokens);
+    }
+    
+    // Stop accepting new deposits in a token; existing ones can still be withdrawn (admin only)
+    pub fn remove_accepted_token(env: Env, token_address: Address) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        
+        let mut tokens = Self::get_accepted_tokens(env.clone());
+        let position = tokens.first_index_of(&token_address).expect("Token not accepted");
+        tokens.remove(position);
+        env.storage().instance().set(&DataKey::AcceptedTokens, &tokens);
     }
 
-    // Deposit tokens with a timelock
-    pub fn deposit(env: Env, user: Address, amount: i128, lock_days: u32) {
+    // Deposit tokens with a timelock, returning the new deposit's ID
+    pub fn deposit(env: Env, user: Address, token_address: Address, amount: i128, lock_days: u32) -> u32 {
         user.require_auth();
         
+        Self::open_deposit(&env, &user, token_address, amount, 1, lock_days as u64 * 24 * 60 * 60)
+    }
+    
+    // Deposit tokens that unlock in equal tranches, one every interval_days, returning the new deposit's ID
+    pub fn deposit_laddered(
+        env: Env,
+        user: Address,
+        token_address: Address,
+        amount: i128,
+        tranches: u32,
+        interval_days: u32,
+    ) -> u32 {
+        user.require_auth();
+        
+        if tranches < 2 || interval_days == 0 {
+            panic!("A ladder needs at least two tranches and a positive interval");
+        }
+        
+        Self::open_deposit(&env, &user, token_address, amount, tranches, interval_days as u64 * 24 * 60 * 60)
+    }
+    
+    // Claim whatever tranches of a laddered deposit have unlocked and not been paid out yet
+    pub fn claim_unlocked(env: Env, user: Address, deposit_id: u32) -> i128 {
+        user.require_auth();
+        
+        let mut deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user.clone(), deposit_id))
+            .expect("No deposit found for this user");
+        
+        if deposit.withdrawn {
+            panic!("Deposit already withdrawn");
+        }
+        
+        let claimable = Self::unlocked_amount(&deposit, env.ledger().timestamp()) - deposit.claimed;
+        if claimable <= 0 {
+            panic!("Nothing unlocked to claim");
+        }
+        Self::touch(&env, &user);
+        
+        // The final tranche closes the deposit out with its interest
+        if deposit.claimed + claimable == deposit.amount {
+            return Self::release(&env, deposit, &user);
+        }
+        
+        deposit.claimed += claimable;
+        env.storage().persistent().set(&DataKey::Deposit(user.clone(), deposit_id), &deposit);
+        
+        let total_deposits: i128 = env.storage().persistent().get(&DataKey::TotalDeposits(deposit.token.clone())).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::TotalDeposits(deposit.token.clone()), &(total_deposits - claimable));
+        
+        let token_client = token::Client::new(&env, &deposit.token);
+        token_client.transfer(&env.current_contract_address(), &user, &claimable);
+        
+        env.events().publish(
+            (symbol_short!("tranche"), user),
+            (deposit_id, claimable, deposit.claimed)
+        );
+        
+        claimable
+    }
+    
+    // Take the tokens in and record a deposit unlocking in `tranches` parts `interval` seconds apart
+    fn open_deposit(env: &Env, user: &Address, token_address: Address, amount: i128, tranches: u32, interval: u64) -> u32 {
         if amount <= 0 {
             panic!("Amount must be positive");
         }
         
+        if !Self::get_accepted_tokens(env.clone()).contains(&token_address) {
+            panic!("Token not accepted");
+        }
+        
         // Get the token client
-        let token_address: Address = env.storage().instance().get(&DataKey::TokenAddress).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+        let token_client = token::Client::new(env, &token_address);
         
         // Transfer tokens from user to this contract
-        token_client.transfer(&user, &env.current_contract_address(), &amount);
+        token_client.transfer(user, &env.current_contract_address(), &amount);
+        Self::touch(env, user);
         
-        // Calculate lock time (current time + lock_days in seconds)
+        // The last tranche unlocks at the lock time
         let current_time = env.ledger().timestamp();
-        let lock_time = current_time + (lock_days as u64 * 24 * 60 * 60);
+        let lock_time = current_time + interval * tranches as u64;
+        
+        // Each deposit gets its own ID so earlier ones are never overwritten
+        let deposit_id: u32 = env.storage().persistent().get(&DataKey::DepositCount(user.clone())).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::DepositCount(user.clone()), &(deposit_id + 1));
         
         // Create deposit record
         let deposit = Deposit {
+            id: deposit_id,
             user: user.clone(),
+            token: token_address.clone(),
             amount,
+            deposited_at: current_time,
             lock_time,
+            committed: lock_time - current_time,
+            tranches,
+            interval: if tranches > 1 { interval } else { 0 },
+            claimed: 0,
             withdrawn: false,
         };
         
         // Store the deposit
-        env.storage().instance().set(&DataKey::Deposit(user), &deposit);
+        env.storage().persistent().set(&DataKey::Deposit(user.clone(), deposit_id), &deposit);
         
         // Update total deposits
-        let total_deposits: i128 = env.storage().instance().get(&DataKey::TotalDeposits).unwrap_or(0);
-        env.storage().instance().set(&DataKey::TotalDeposits, &(total_deposits + amount));
+        let total_deposits: i128 = env.storage().persistent().get(&DataKey::TotalDeposits(token_address.clone())).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::TotalDeposits(token_address), &(total_deposits + amount));
+        
+        deposit_id
     }
     
-    // Withdraw tokens after timelock expires
-    pub fn withdraw(env: Env, user: Address) -> i128 {
+    // Withdraw a deposit's tokens after its timelock expires
+    pub fn withdraw(env: Env, user: Address, deposit_id: u32) -> i128 {
         user.require_auth();
         
         // Get user's deposit
-        let mut deposit: Deposit = env.storage().instance()
-            .get(&DataKey::Deposit(user.clone()))
+        let deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user.clone(), deposit_id))
             .expect("No deposit found for this user");
         
         // Check if already withdrawn
@@ -85,16 +240,34 @@ impl TimelockVault {
             panic!("Tokens are still locked");
         }
         
-        // Mark as withdrawn
-        deposit.withdrawn = true;
-        env.storage().instance().set(&DataKey::Deposit(user.clone()), &deposit);
+        Self::touch(&env, &user);
+        Self::release(&env, deposit, &user)
+    }
+    
+    // Withdraw a deposit made before deposits were keyed by ID, once its timelock has expired
+    pub fn withdraw_legacy(env: Env, user: Address) -> i128 {
+        user.require_auth();
         
-        // Update total deposits
-        let total_deposits: i128 = env.storage().instance().get(&DataKey::TotalDeposits).unwrap_or(0);
-        env.storage().instance().set(&DataKey::TotalDeposits, &(total_deposits - deposit.amount));
+        let deposit: LegacyDeposit = env.storage().instance()
+            .get(&LegacyKey::Deposit(user.clone()))
+            .expect("No legacy deposit found for this user");
         
-        // Transfer tokens back to user
-        let token_address: Address = env.storage().instance().get(&DataKey::TokenAddress).unwrap();
+        if deposit.withdrawn {
+            panic!("Deposit already withdrawn");
+        }
+        
+        if env.ledger().timestamp() < deposit.lock_time {
+            panic!("Tokens are still locked");
+        }
+        
+        // Drop the entry rather than mark it, freeing its space in instance storage
+        env.storage().instance().remove(&LegacyKey::Deposit(user.clone()));
+        
+        let total_deposits: i128 = env.storage().instance().get(&LegacyKey::TotalDeposits).unwrap_or(0);
+        env.storage().instance().set(&LegacyKey::TotalDeposits, &(total_deposits - deposit.amount));
+        Self::touch(&env, &user);
+        
+        let token_address: Address = env.storage().instance().get(&LegacyKey::TokenAddress).unwrap();
         let token_client = token::Client::new(&env, &token_address);
         token_client.transfer(
             &env.current_contract_address(),
@@ -105,15 +278,300 @@ impl TimelockVault {
         deposit.amount
     }
     
+    // Get a user's deposit from before deposits were keyed by ID, if one is still held
+    pub fn get_legacy_deposit(env: Env, user: Address) -> Option<LegacyDeposit> {
+        env.storage().instance().get(&LegacyKey::Deposit(user))
+    }
+    
+    // Name who can claim a deposit if its owner goes quiet for inactivity_days after it unlocks
+    pub fn set_beneficiary(env: Env, user: Address, deposit_id: u32, beneficiary: Address, inactivity_days: u32) {
+        user.require_auth();
+        
+        if inactivity_days == 0 {
+            panic!("Inactivity period must be positive");
+        }
+        
+        let deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user.clone(), deposit_id))
+            .expect("No deposit found for this user");
+        
+        if deposit.withdrawn {
+            panic!("Deposit already withdrawn");
+        }
+        
+        let designation = BeneficiaryDesignation {
+            beneficiary: beneficiary.clone(),
+            inactivity_days,
+        };
+        env.storage().persistent().set(&DataKey::Beneficiary(user.clone(), deposit_id), &designation);
+        Self::touch(&env, &user);
+        
+        env.events().publish(
+            (symbol_short!("benef"), user),
+            (deposit_id, beneficiary, inactivity_days)
+        );
+    }
+    
+    // Show the vault the owner is still around, holding off their beneficiaries
+    pub fn check_in(env: Env, user: Address) {
+        user.require_auth();
+        
+        Self::touch(&env, &user);
+    }
+    
+    // Claim an unlocked deposit whose owner has been inactive for the designated period
+    pub fn claim_as_beneficiary(env: Env, beneficiary: Address, user: Address, deposit_id: u32) -> i128 {
+        beneficiary.require_auth();
+        
+        let designation: BeneficiaryDesignation = env.storage().persistent()
+            .get(&DataKey::Beneficiary(user.clone(), deposit_id))
+            .expect("No beneficiary designated for this deposit");
+        
+        if designation.beneficiary != beneficiary {
+            panic!("Not the designated beneficiary");
+        }
+        
+        let deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user.clone(), deposit_id))
+            .expect("No deposit found for this user");
+        
+        if deposit.withdrawn {
+            panic!("Deposit already withdrawn");
+        }
+        
+        // The inactivity clock starts at unlock or the owner's last interaction, whichever is later
+        let last_active: u64 = env.storage().persistent().get(&DataKey::LastActive(user.clone())).unwrap_or(0);
+        let claimable_at = deposit.lock_time.max(last_active) + (designation.inactivity_days as u64 * 24 * 60 * 60);
+        if env.ledger().timestamp() < claimable_at {
+            panic!("Owner is not inactive yet");
+        }
+        
+        env.events().publish(
+            (symbol_short!("inherited"), user),
+            (deposit_id, beneficiary.clone())
+        );
+        
+        Self::release(&env, deposit, &beneficiary)
+    }
+    
+    // Mark a deposit withdrawn and pay out what is left of it with its interest
+    fn release(env: &Env, mut deposit: Deposit, to: &Address) -> i128 {
+        let remaining = deposit.amount - deposit.claimed;
+        
+        // Mark as withdrawn
+        deposit.withdrawn = true;
+        env.storage().persistent().set(&DataKey::Deposit(deposit.user.clone(), deposit.id), &deposit);
+        
+        // Update total deposits
+        let total_deposits: i128 = env.storage().persistent().get(&DataKey::TotalDeposits(deposit.token.clone())).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::TotalDeposits(deposit.token.clone()), &(total_deposits - remaining));
+        
+        // Interest for the lock length, paid out of the reserve while it lasts
+        let reserve = Self::get_reward_reserve(env.clone(), deposit.token.clone());
+        let interest = Self::interest_for(env, &deposit).min(reserve);
+        if interest > 0 {
+            env.storage().persistent().set(&DataKey::RewardReserve(deposit.token.clone()), &(reserve - interest));
+        }
+        
+        // Transfer tokens out
+        let token_client = token::Client::new(env, &deposit.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            to,
+            &(remaining + interest)
+        );
+        
+        if interest > 0 {
+            env.events().publish(
+                (symbol_short!("interest"), deposit.user.clone()),
+                (deposit.id, interest)
+            );
+        }
+        
+        remaining + interest
+    }
+    
+    // How much of a deposit has unlocked by a given time, tranche by tranche
+    fn unlocked_amount(deposit: &Deposit, current_time: u64) -> i128 {
+        if current_time >= deposit.lock_time {
+            return deposit.amount;
+        }
+        if deposit.tranches <= 1 || deposit.interval == 0 {
+            return 0;
+        }
+        
+        let passed = ((current_time - deposit.deposited_at) / deposit.interval).min(deposit.tranches as u64);
+        deposit.amount * passed as i128 / deposit.tranches as i128
+    }
+    
+    // Record that the user just interacted with the vault
+    fn touch(env: &Env, user: &Address) {
+        env.storage().persistent().set(&DataKey::LastActive(user.clone()), &env.ledger().timestamp());
+    }
+    
+    // Set the interest paid on matured deposits by lock length, shortest lock first (admin only)
+    pub fn set_interest_tiers(env: Env, tiers: Vec<InterestTier>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        
+        let mut previous_days: Option<u32> = None;
+        for tier in tiers.iter() {
+            if tier.rate_bps > 10000 {
+                panic!("Rate cannot exceed 100%");
+            }
+            if previous_days.is_some_and(|days| tier.min_days <= days) {
+                panic!("Tiers must be ordered by lock length");
+            }
+            previous_days = Some(tier.min_days);
+        }
+        
+        env.storage().instance().set(&DataKey::InterestTiers, &tiers);
+    }
+    
+    // Add to the reserve that pays interest on matured deposits in an accepted token
+    pub fn fund_reserve(env: Env, funder: Address, token_address: Address, amount: i128) {
+        funder.require_auth();
+        
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        
+        if !Self::get_accepted_tokens(env.clone()).contains(&token_address) {
+            panic!("Token not accepted");
+        }
+        
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&funder, &env.current_contract_address(), &amount);
+        
+        let reserve = Self::get_reward_reserve(env.clone(), token_address.clone());
+        env.storage().persistent().set(&DataKey::RewardReserve(token_address), &(reserve + amount));
+    }
+    
+    // Push a deposit's lock further out; it can never be brought earlier
+    pub fn extend_lock(env: Env, user: Address, deposit_id: u32, extra_days: u32) -> u64 {
+        user.require_auth();
+        
+        if extra_days == 0 {
+            panic!("Extension must be positive");
+        }
+        
+        let mut deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user.clone(), deposit_id))
+            .expect("No deposit found for this user");
+        
+        if deposit.withdrawn {
+            panic!("Deposit already withdrawn");
+        }
+        
+        if deposit.tranches > 1 {
+            panic!("Laddered deposits keep their schedule");
+        }
+        
+        Self::touch(&env, &user);
+        
+        // An already unlocked deposit is relocked from now and only earns for the new commitment
+        let current_time = env.ledger().timestamp();
+        let extra = extra_days as u64 * 24 * 60 * 60;
+        if current_time >= deposit.lock_time {
+            deposit.deposited_at = current_time;
+            deposit.lock_time = current_time + extra;
+            deposit.committed = extra;
+        } else {
+            deposit.lock_time += extra;
+            deposit.committed += extra;
+        }
+        env.storage().persistent().set(&DataKey::Deposit(user.clone(), deposit_id), &deposit);
+        
+        env.events().publish(
+            (symbol_short!("extended"), user),
+            (deposit_id, deposit.lock_time)
+        );
+        
+        deposit.lock_time
+    }
+    
+    // Turn on early withdrawal with a penalty sent to the treasury (admin only)
+    pub fn set_early_exit(env: Env, penalty_bps: u32, treasury: Address, decaying: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        
+        if penalty_bps > 10000 {
+            panic!("Penalty cannot exceed 100%");
+        }
+        
+        let config = EarlyExitConfig {
+            penalty_bps,
+            treasury,
+            decaying,
+        };
+        env.storage().instance().set(&DataKey::EarlyExit, &config);
+    }
+    
+    // Turn early withdrawal off again (admin only)
+    pub fn disable_early_exit(env: Env) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        
+        env.storage().instance().remove(&DataKey::EarlyExit);
+    }
+    
+    // Withdraw a deposit before its lock expires, paying the early-exit penalty; returns what the user receives
+    pub fn early_withdraw(env: Env, user: Address, deposit_id: u32) -> i128 {
+        user.require_auth();
+        
+        let config: EarlyExitConfig = env.storage().instance()
+            .get(&DataKey::EarlyExit)
+            .expect("Early withdrawal is not enabled");
+        
+        let mut deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user.clone(), deposit_id))
+            .expect("No deposit found for this user");
+        
+        if deposit.withdrawn {
+            panic!("Deposit already withdrawn");
+        }
+        
+        let current_time = env.ledger().timestamp();
+        if current_time >= deposit.lock_time {
+            panic!("Deposit is already unlocked");
+        }
+        
+        let remaining = deposit.amount - deposit.claimed;
+        let penalty = Self::early_exit_penalty(&config, &deposit, current_time);
+        let payout = remaining - penalty;
+        Self::touch(&env, &user);
+        
+        // Mark as withdrawn
+        deposit.withdrawn = true;
+        env.storage().persistent().set(&DataKey::Deposit(user.clone(), deposit_id), &deposit);
+        
+        // Update total deposits
+        let total_deposits: i128 = env.storage().persistent().get(&DataKey::TotalDeposits(deposit.token.clone())).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::TotalDeposits(deposit.token.clone()), &(total_deposits - remaining));
+        
+        let token_client = token::Client::new(&env, &deposit.token);
+        if penalty > 0 {
+            token_client.transfer(&env.current_contract_address(), &config.treasury, &penalty);
+        }
+        token_client.transfer(&env.current_contract_address(), &user, &payout);
+        
+        env.events().publish(
+            (symbol_short!("early"), user),
+            (deposit_id, payout, penalty)
+        );
+        
+        payout
+    }
+    
     // Emergency withdraw (admin only, for emergency situations)
-    pub fn emergency_withdraw(env: Env, user: Address) -> i128 {
+    pub fn emergency_withdraw(env: Env, user: Address, deposit_id: u32) -> i128 {
         // Check admin permission
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
         
         // Get user's deposit
-        let mut deposit: Deposit = env.storage().instance()
-            .get(&DataKey::Deposit(user.clone()))
+        let mut deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user.clone(), deposit_id))
             .expect("No deposit found for this user");
         
         // Check if already withdrawn
@@ -121,36 +579,56 @@ impl TimelockVault {
             panic!("Deposit already withdrawn");
         }
         
+        // Only what has not been claimed yet is still held
+        let remaining = deposit.amount - deposit.claimed;
+        
         // Mark as withdrawn
         deposit.withdrawn = true;
-        env.storage().instance().set(&DataKey::Deposit(user.clone()), &deposit);
+        env.storage().persistent().set(&DataKey::Deposit(user.clone(), deposit_id), &deposit);
         
         // Update total deposits
-        let total_deposits: i128 = env.storage().instance().get(&DataKey::TotalDeposits).unwrap_or(0);
-        env.storage().instance().set(&DataKey::TotalDeposits, &(total_deposits - deposit.amount));
+        let total_deposits: i128 = env.storage().persistent().get(&DataKey::TotalDeposits(deposit.token.clone())).unwrap_or(0);
+        env.storage().persistent().set(&DataKey::TotalDeposits(deposit.token.clone()), &(total_deposits - remaining));
         
         // Transfer tokens back to user
-        let token_address: Address = env.storage().instance().get(&DataKey::TokenAddress).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+        let token_client = token::Client::new(&env, &deposit.token);
         token_client.transfer(
             &env.current_contract_address(),
             &user,
-            &deposit.amount
+            &remaining
         );
         
-        deposit.amount
+        remaining
     }
     
     // Get deposit information
-    pub fn get_deposit(env: Env, user: Address) -> Deposit {
-        env.storage().instance()
-            .get(&DataKey::Deposit(user))
+    pub fn get_deposit(env: Env, user: Address, deposit_id: u32) -> Deposit {
+        env.storage().persistent()
+            .get(&DataKey::Deposit(user, deposit_id))
             .expect("No deposit found for this user")
     }
     
+    // Get up to `limit` of a user's deposits from deposit ID `start` on, oldest first
+    pub fn get_deposits(env: Env, user: Address, start: u32, limit: u32) -> Vec<Deposit> {
+        let count = Self::get_deposit_count(env.clone(), user.clone());
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut deposits = Vec::new(&env);
+        for deposit_id in start..end {
+            if let Some(deposit) = env.storage().persistent().get(&DataKey::Deposit(user.clone(), deposit_id)) {
+                deposits.push_back(deposit);
+            }
+        }
+        deposits
+    }
+    
+    // Get how many deposits a user has ever made, i.e. the next deposit ID
+    pub fn get_deposit_count(env: Env, user: Address) -> u32 {
+        env.storage().persistent().get(&DataKey::DepositCount(user)).unwrap_or(0)
+    }
+    
     // Check if deposit can be withdrawn
-    pub fn can_withdraw(env: Env, user: Address) -> bool {
-        let deposit: Deposit = match env.storage().instance().get(&DataKey::Deposit(user)) {
+    pub fn can_withdraw(env: Env, user: Address, deposit_id: u32) -> bool {
+        let deposit: Deposit = match env.storage().persistent().get(&DataKey::Deposit(user, deposit_id)) {
             Some(d) => d,
             None => return false,
         };
@@ -163,8 +641,91 @@ impl TimelockVault {
         current_time >= deposit.lock_time
     }
     
-    // Get total deposits in the vault
-    pub fn get_total_deposits(env: Env) -> i128 {
-        env.storage().instance().get(&DataKey::TotalDeposits).unwrap_or(0)
+    // Get how much of a deposit could be claimed right now
+    pub fn get_claimable(env: Env, user: Address, deposit_id: u32) -> i128 {
+        let deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user, deposit_id))
+            .expect("No deposit found for this user");
+        
+        if deposit.withdrawn {
+            return 0;
+        }
+        
+        Self::unlocked_amount(&deposit, env.ledger().timestamp()) - deposit.claimed
+    }
+    
+    // Get total deposits in the vault in a token
+    pub fn get_total_deposits(env: Env, token_address: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::TotalDeposits(token_address)).unwrap_or(0)
+    }
+    
+    // Get the tokens that can be locked
+    pub fn get_accepted_tokens(env: Env) -> Vec<Address> {
+        env.storage().instance().get(&DataKey::AcceptedTokens).unwrap_or(Vec::new(&env))
+    }
+    
+    // Get who can claim a deposit if its owner goes inactive, if anyone
+    pub fn get_beneficiary(env: Env, user: Address, deposit_id: u32) -> Option<BeneficiaryDesignation> {
+        env.storage().persistent().get(&DataKey::Beneficiary(user, deposit_id))
+    }
+    
+    // Get the interest tiers for matured deposits
+    pub fn get_interest_tiers(env: Env) -> Vec<InterestTier> {
+        env.storage().instance().get(&DataKey::InterestTiers).unwrap_or(Vec::new(&env))
+    }
+    
+    // Get what is left to pay interest from in a token
+    pub fn get_reward_reserve(env: Env, token_address: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::RewardReserve(token_address)).unwrap_or(0)
+    }
+    
+    // Get the interest a deposit earns at withdrawal, before any shortfall in the reserve
+    pub fn get_interest(env: Env, user: Address, deposit_id: u32) -> i128 {
+        let deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user, deposit_id))
+            .expect("No deposit found for this user");
+        
+        Self::interest_for(&env, &deposit)
+    }
+    
+    // Interest at the highest tier the deposit's committed lock length reaches
+    fn interest_for(env: &Env, deposit: &Deposit) -> i128 {
+        let lock_days = (deposit.committed / (24 * 60 * 60)) as u32;
+        let mut rate_bps: u32 = 0;
+        for tier in Self::get_interest_tiers(env.clone()).iter() {
+            if lock_days >= tier.min_days {
+                rate_bps = tier.rate_bps;
+            }
+        }
+        deposit.amount * rate_bps as i128 / 10000
+    }
+    
+    // Get the penalty for withdrawing a deposit early right now (0 once it is unlocked)
+    pub fn get_early_exit_penalty(env: Env, user: Address, deposit_id: u32) -> i128 {
+        let config: EarlyExitConfig = env.storage().instance()
+            .get(&DataKey::EarlyExit)
+            .expect("Early withdrawal is not enabled");
+        let deposit: Deposit = env.storage().persistent()
+            .get(&DataKey::Deposit(user, deposit_id))
+            .expect("No deposit found for this user");
+        
+        Self::early_exit_penalty(&config, &deposit, env.ledger().timestamp())
+    }
+    
+    // Penalty on the still-locked part of a deposit at a given time, decaying toward zero at lock_time if configured
+    fn early_exit_penalty(config: &EarlyExitConfig, deposit: &Deposit, current_time: u64) -> i128 {
+        if current_time >= deposit.lock_time {
+            return 0;
+        }
+        
+        let locked = deposit.amount - Self::unlocked_amount(deposit, current_time).max(deposit.claimed);
+        let penalty = locked * config.penalty_bps as i128 / 10000;
+        if !config.decaying {
+            return penalty;
+        }
+        
+        let remaining = (deposit.lock_time - current_time) as i128;
+        let total = (deposit.lock_time - deposit.deposited_at).max(1) as i128;
+        penalty * remaining.min(total) / total
     }
 }